@@ -1,11 +1,20 @@
 use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{burn, mint_to, Burn, Mint, MintTo, Token, TokenAccount},
+};
 use gpl_session::{SessionError, SessionToken, session_auth_or, Session};
 pub mod state;
 use solana_program::pubkey;
 pub use state::*;
 
 declare_id!("HsT4yX959Qh1vis8fEqoQdgrHEJuKvaWGtHoPcTjk4mJ");
+// Default treasury of the GameConfig, the admin can change it with update_game_config
 pub const TREASURY_PUBKEY: Pubkey = pubkey!("CYg2vSJdujzEC1E7kHMzB9QhjiPLRdsAa4Js7MkuXfYq");
+// Wallet allowed to update the game config, create the resource mints and migrate the legacy
+// board. It is a different key than the treasury, its key pair is
+// YNxhutfSA9JqhA2HEaPmGZ7LErJMMquE9Twbr3x3Hrr.json next to Anchor.toml
+pub const ADMIN_PUBKEY: Pubkey = pubkey!("YNxhutfSA9JqhA2HEaPmGZ7LErJMMquE9Twbr3x3Hrr");
 
 #[error_code]
 pub enum GameErrorCode {
@@ -29,13 +38,85 @@ pub enum GameErrorCode {
     NotEnoughStone,
     #[msg("Not enough wood")]
     NotEnoughWood,
+    #[msg("Unknown faction")]
+    UnknownFaction,
+    #[msg("Player already joined a faction in this game")]
+    AlreadyInFaction,
+    #[msg("Only members of the faction can upgrade its building")]
+    NotInFaction,
+    #[msg("Game is already over")]
+    GameAlreadyOver,
+    #[msg("No faction reward to claim")]
+    NoFactionReward,
+    #[msg("Only the owner or authorised players can use this building")]
+    NotBuildingOwner,
+    #[msg("Tile has no building")]
+    TileHasNoBuilding,
+    #[msg("No room to authorise more players")]
+    TooManyAuthorizedPlayers,
+    #[msg("Player is not authorised")]
+    PlayerNotAuthorized,
+    #[msg("Invalid board size")]
+    InvalidBoardSize,
+    #[msg("Tile is outside of the board")]
+    TileOutOfBounds,
+    #[msg("Building type can not be built")]
+    BuildingTypeNotBuildable,
+    #[msg("Houses and warehouses can not be transferred")]
+    BuildingCantBeTransferred,
+    #[msg("Building is being upgraded")]
+    BuildingIsUpgrading,
+    #[msg("Building has no upgrade in progress")]
+    NoUpgradeInProgress,
+    #[msg("Upgrade not finished yet")]
+    UpgradeNotFinished,
+    #[msg("Not enough food")]
+    NotEnoughFood,
+    #[msg("Cost curve value out of range")]
+    CostOverflow,
+    #[msg("Energy is already full")]
+    EnergyAlreadyFull,
+    #[msg("Refilling energy with stone is disabled")]
+    StoneRefillDisabled,
+    #[msg("Invalid game config")]
+    InvalidGameConfig,
+    #[msg("Season is not over yet")]
+    SeasonNotOver,
+    #[msg("No war reward to claim")]
+    NoWarReward,
+    #[msg("Only buildings of other players can be raided")]
+    CantRaidBuilding,
+    #[msg("The player account of the building owner is missing")]
+    BuildingOwnerMissing,
+    #[msg("Building is at full health")]
+    BuildingAtFullHealth,
+    #[msg("Orders sell wood or stone for a different resource or SOL")]
+    InvalidOrder,
+    #[msg("Amount is more than the order has left or too small to be paid")]
+    InvalidFillAmount,
+    #[msg("Not enough storage for the resources")]
+    StorageFull,
+    #[msg("Orders can't be filled by their maker")]
+    OwnOrder,
+    #[msg("Only wood and stone can be traded")]
+    ResourceNotTradable,
+    #[msg("The board of the season is already in use")]
+    BoardAlreadyInUse,
 }
 
 const TIME_TO_REFILL_ENERGY: i64 = 60;
-const MAX_ENERGY: u64 = 10;
+const RAID_ENERGY_COST: u64 = 2;
+// Defaults for the GameConfig account, the admin can change them with update_game_config
+pub const MAX_ENERGY: u64 = 10;
+const MAX_AUTHORIZED_PLAYERS: usize = 5;
+const BASE_STORAGE: u64 = 500; // Per resource, warehouses add to it
+// Maximum board size, the size of each round is chosen on restart
 const BOARD_SIZE_X: usize = 10;
 const BOARD_SIZE_Y: usize = 10;
-pub const ENERGY_REFILL_FEE: u64 = LAMPORTS_PER_SOL / 20; // 0.05 SOL
+pub const ENERGY_REFILL_FEE: u64 = LAMPORTS_PER_SOL / 200; // 0.005 SOL per energy point
+pub const ENERGY_REFILL_STONE: u64 = 2; // stone per energy point
+pub const WAR_POOL_SHARE: u64 = 5_000; // half of every SOL refill goes to the winners of the game
+const BASIS_POINTS: u64 = 10_000;
 
 #[program]
 pub mod lumberjack {
@@ -43,21 +124,55 @@ pub mod lumberjack {
     use super::*;
 
     pub fn init_player(ctx: Context<InitPlayer>) -> Result<()> {
-        ctx.accounts.player.energy = MAX_ENERGY;
+        init_game_config_if_needed(&mut ctx.accounts.game_config);
+        ctx.accounts.player.energy = ctx.accounts.game_config.max_energy;
         ctx.accounts.player.last_login = Clock::get()?.unix_timestamp;
         ctx.accounts.player.authority = ctx.accounts.signer.key();
 
         Ok(())
     }
 
-    pub fn restart_game(ctx: Context<RestartGame>) -> Result<()> {
-        let board = &mut ctx.accounts.board.load_mut()?;
+    /// Archives the finished season and starts the next one on a new board
+    pub fn restart_game(ctx: Context<RestartGame>, size_x: u8, size_y: u8, seed: u64) -> Result<()> {
+        init_game_config_if_needed(&mut ctx.accounts.game_config);
+        let board = &ctx.accounts.board.load()?;
+        if !board.evil_won && !board.good_won {
+            return err!(GameErrorCode::SeasonNotOver);
+        }
 
-        if board.evil_won || board.good_won {
-            let game_action = &mut ctx.accounts.game_actions.load_mut()?;
-            board.Restart(game_action, ctx.accounts.signer.key())?;            
-        }    
-        
+        let season = ctx.accounts.game_config.season;
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.season_result.set_inner(SeasonResult::new(season, ctx.accounts.board.key(), board, now));
+
+        let next_board = &mut ctx.accounts.next_board.load_init()?;
+        let game_action = &mut ctx.accounts.game_actions.load_mut()?;
+        next_board.carry_over(board);
+        next_board.Restart(game_action, ctx.accounts.signer.key(), size_x, size_y, seed)?;
+        ctx.accounts.game_config.season = season + 1;
+
+        msg!("Season {} is over, season {} started.", season, season + 1);
+        Ok(())
+    }
+
+    /// Moves the board of a game started before seasons existed to the board of the current
+    /// season, including its unclaimed rewards, and closes it. Has to be done before the board
+    /// of the season is played on.
+    pub fn migrate_legacy_board(ctx: Context<MigrateLegacyBoard>) -> Result<()> {
+        init_game_config_if_needed(&mut ctx.accounts.game_config);
+        let legacy_board = &ctx.accounts.legacy_board.load()?;
+        // init_if_needed: a player may have created the board already, but not started it
+        let fresh = ctx.accounts.board.as_ref().try_borrow_data()?[..8] == [0; 8];
+        let board = &mut if fresh {
+            ctx.accounts.board.load_init()?
+        } else {
+            ctx.accounts.board.load_mut()?
+        };
+        if board.initialized {
+            return err!(GameErrorCode::BoardAlreadyInUse);
+        }
+        **board = **legacy_board;
+
+        msg!("Moved the legacy board to season {}.", ctx.accounts.game_config.season);
         Ok(())
     }
 
@@ -73,18 +188,18 @@ pub mod lumberjack {
         let game_action = &mut ctx.accounts.game_actions.load_mut()?;
 
         if !board.initialized {
-            board.Restart(game_action, ctx.accounts.player.key())?;
+            let seed = Clock::get()?.unix_timestamp as u64;
+            board.Restart(game_action, ctx.accounts.player.key(), BOARD_SIZE_X as u8, BOARD_SIZE_Y as u8, seed)?;
         }    
         if ctx.accounts.player.energy < 3 {
             return err!(GameErrorCode::NotEnoughEnergy);
         }
 
-        board.chop_tree(x, y, ctx.accounts.player.key(), ctx.accounts.avatar.key(), game_action)?;
+        let chopped = board.chop_tree(x, y, ctx.accounts.player.key(), &mut ctx.accounts.player, ctx.accounts.avatar.key(), game_action)?;
 
         ctx.accounts.player.energy -= 3;
-        let wood = board.wood;
 
-        msg!("You chopped a tree and got 1 wood. You have {} wood and {} energy left.",wood, ctx.accounts.player.energy);
+        msg!("You chopped and got {}. You have {} wood, {} stone and {} energy left.", chopped, ctx.accounts.player.wood, ctx.accounts.player.stone, ctx.accounts.player.energy);
         Ok(())
     }
 
@@ -102,7 +217,7 @@ pub mod lumberjack {
         }
 
         let game_action = &mut ctx.accounts.game_actions.load_mut()?;
-        board.build(x, y, building_type, ctx.accounts.player.key(), ctx.accounts.avatar.key(), game_action)?;
+        board.build(x, y, building_type, ctx.accounts.player.key(), &mut ctx.accounts.player, ctx.accounts.avatar.key(), game_action)?;
 
         ctx.accounts.player.energy -= 1;
         msg!("You built a building. You have and {} energy left.", ctx.accounts.player.energy);
@@ -122,11 +237,12 @@ pub mod lumberjack {
             return err!(GameErrorCode::NotEnoughEnergy);
         }
         let game_action = &mut ctx.accounts.game_actions.load_mut()?;
-        board.upgrade(x, y, ctx.accounts.player.key(), ctx.accounts.avatar.key(), game_action)?;
+        let building_owner = load_building_owner(ctx.remaining_accounts)?;
+        let building_owner = building_owner.as_ref().map(|owner| (owner.key(), &**owner));
+        board.upgrade(x, y, ctx.accounts.player.key(), &mut ctx.accounts.player, building_owner, ctx.accounts.avatar.key(), game_action)?;
 
         ctx.accounts.player.energy -= 1;
-        let wood = board.wood;
-        msg!("You chopped a tree and got 1 wood. You have {} wood and {} energy left.", wood, ctx.accounts.player.energy);
+        msg!("You started an upgrade. You have {} wood, {} stone and {} energy left.", ctx.accounts.player.wood, ctx.accounts.player.stone, ctx.accounts.player.energy);
         Ok(())
     }
 
@@ -143,27 +259,251 @@ pub mod lumberjack {
             return err!(GameErrorCode::NotEnoughEnergy);
         }
         let game_action = &mut ctx.accounts.game_actions.load_mut()?;
-        board.collect(x, y, ctx.accounts.player.key(), ctx.accounts.avatar.key(), game_action)?;
+        let building_owner = load_building_owner(ctx.remaining_accounts)?;
+        let building_owner = building_owner.as_ref().map(|owner| (owner.key(), &**owner));
+        board.collect(x, y, ctx.accounts.player.key(), &mut ctx.accounts.player, building_owner, ctx.accounts.avatar.key(), game_action)?;
+
+        ctx.accounts.player.energy -= 1;
+        msg!("You collected from building. You have {} wood, {} stone and {} energy left.", ctx.accounts.player.wood, ctx.accounts.player.stone, ctx.accounts.player.energy);
+        Ok(())
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn finish_upgrade(ctx: Context<BoardAction>, x :u8, y :u8) -> Result<()> {
+        let board = &mut ctx.accounts.board.load_mut()?;
+        let game_action = &mut ctx.accounts.game_actions.load_mut()?;
+        let building_owner = load_building_owner(ctx.remaining_accounts)?;
+        let building_owner = building_owner.as_ref().map(|owner| (owner.key(), &**owner));
+        board.finish_upgrade(x, y, ctx.accounts.player.key(), &mut ctx.accounts.player, building_owner, ctx.accounts.avatar.key(), game_action)?;
+
+        msg!("Upgrade finished.");
+        Ok(())
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn speed_up_upgrade(ctx: Context<BoardAction>, x :u8, y :u8) -> Result<()> {
+        let board = &mut ctx.accounts.board.load_mut()?;
+        let game_action = &mut ctx.accounts.game_actions.load_mut()?;
+        let building_owner = load_building_owner(ctx.remaining_accounts)?;
+        let building_owner = building_owner.as_ref().map(|owner| (owner.key(), &**owner));
+        let food_cost = board.speed_up_upgrade(x, y, ctx.accounts.player.key(), &mut ctx.accounts.player, building_owner, ctx.accounts.avatar.key(), game_action)?;
+
+        msg!("Upgrade finished for {} food. You have {} food left.", food_cost, ctx.accounts.player.food);
+        Ok(())
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn plant_tree(mut ctx: Context<BoardAction>, x :u8, y :u8) -> Result<()> {
+        let account = &mut ctx.accounts;
+        update_energy(account)?;
+        let board = &mut ctx.accounts.board.load_mut()?;
+
+        if ctx.accounts.player.energy == 0 {
+            return err!(GameErrorCode::NotEnoughEnergy);
+        }
+        let game_action = &mut ctx.accounts.game_actions.load_mut()?;
+        board.plant_tree(x, y, ctx.accounts.player.key(), &mut ctx.accounts.player, ctx.accounts.avatar.key(), game_action)?;
 
         ctx.accounts.player.energy -= 1;
-        let wood = board.wood;
-        msg!("You collected from building. You have {} wood and {} energy left.", wood, ctx.accounts.player.energy);
+        msg!("You planted a tree. You have {} wood and {} energy left.", ctx.accounts.player.wood, ctx.accounts.player.energy);
+        Ok(())
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn raid(mut ctx: Context<BoardAction>, x :u8, y :u8) -> Result<()> {
+        let account = &mut ctx.accounts;
+        update_energy(account)?;
+        let board = &mut ctx.accounts.board.load_mut()?;
+
+        if ctx.accounts.player.energy < RAID_ENERGY_COST {
+            return err!(GameErrorCode::NotEnoughEnergy);
+        }
+        let game_action = &mut ctx.accounts.game_actions.load_mut()?;
+        let mut building_owner = load_building_owner(ctx.remaining_accounts)?;
+        let owner = building_owner.as_mut().map(|owner| (owner.key(), &mut **owner));
+        board.raid(x, y, ctx.accounts.player.key(), &mut ctx.accounts.player, owner, ctx.accounts.avatar.key(), game_action)?;
+        // Destroyed houses and warehouses took their bonus from the owner
+        if let Some(owner) = building_owner {
+            owner.exit(&crate::ID)?;
+        }
+
+        ctx.accounts.player.energy -= RAID_ENERGY_COST;
+        msg!("You raided the building. You have {} wood, {} stone and {} energy left.", ctx.accounts.player.wood, ctx.accounts.player.stone, ctx.accounts.player.energy);
+        Ok(())
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn defend(mut ctx: Context<BoardAction>, x :u8, y :u8) -> Result<()> {
+        let account = &mut ctx.accounts;
+        update_energy(account)?;
+        let board = &mut ctx.accounts.board.load_mut()?;
+
+        if ctx.accounts.player.energy == 0 {
+            return err!(GameErrorCode::NotEnoughEnergy);
+        }
+        let game_action = &mut ctx.accounts.game_actions.load_mut()?;
+        let building_owner = load_building_owner(ctx.remaining_accounts)?;
+        let building_owner = building_owner.as_ref().map(|owner| (owner.key(), &**owner));
+        board.defend(x, y, ctx.accounts.player.key(), &mut ctx.accounts.player, building_owner, ctx.accounts.avatar.key(), game_action)?;
+
+        ctx.accounts.player.energy -= 1;
+        msg!("You repaired the building. You have {} stone and {} energy left.", ctx.accounts.player.stone, ctx.accounts.player.energy);
         Ok(())
     }
 
     pub fn update(mut ctx: Context<BoardAction>) -> Result<()> {
         let account = &mut ctx.accounts;
         update_energy(account)?;
+        msg!("Updated energy. You have {} wood and {} energy left.", ctx.accounts.player.wood, ctx.accounts.player.energy);
+        Ok(())
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn contribute(ctx: Context<BoardAction>, wood: u64, stone: u64) -> Result<()> {
         let board = &mut ctx.accounts.board.load_mut()?;
-        let wood = board.wood;
-        msg!("Updated energy. You have {} wood and {} energy left.", wood, ctx.accounts.player.energy);
+        let game_action = &mut ctx.accounts.game_actions.load_mut()?;
+        board.contribute(wood, stone, ctx.accounts.player.key(), &mut ctx.accounts.player, ctx.accounts.avatar.key(), game_action)?;
+
+        let (treasury_wood, treasury_stone) = (board.wood, board.stone);
+        msg!("You contributed {} wood and {} stone. The treasury holds {} wood and {} stone.", wood, stone, treasury_wood, treasury_stone);
         Ok(())
     }
 
-    pub fn refill_energy(mut ctx: Context<RefillEnergyAccounts>) -> Result<()> {
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn join_faction(ctx: Context<BoardAction>, faction: u8) -> Result<()> {
+        let board = &mut ctx.accounts.board.load_mut()?;
+        let game_action = &mut ctx.accounts.game_actions.load_mut()?;
+        board.join_faction(faction, ctx.accounts.player.key(), &mut ctx.accounts.player, ctx.accounts.avatar.key(), game_action)?;
+
+        msg!("You joined faction {}.", faction);
+        Ok(())
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn claim_faction_reward(ctx: Context<BoardAction>) -> Result<()> {
+        let board = &mut ctx.accounts.board.load_mut()?;
+        let game_action = &mut ctx.accounts.game_actions.load_mut()?;
+        board.claim_faction_reward(ctx.accounts.player.key(), &mut ctx.accounts.player, ctx.accounts.avatar.key(), game_action)?;
+
+        msg!("You claimed the faction reward. You have {} wood and {} stone.", ctx.accounts.player.wood, ctx.accounts.player.stone);
+        Ok(())
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn transfer_building(ctx: Context<BoardAction>, x :u8, y :u8, new_owner: Pubkey) -> Result<()> {
+        let board = &mut ctx.accounts.board.load_mut()?;
+        let game_action = &mut ctx.accounts.game_actions.load_mut()?;
+        board.transfer_building(x, y, new_owner, ctx.accounts.player.key(), ctx.accounts.avatar.key(), game_action)?;
+
+        msg!("You transferred the building to {}.", new_owner);
+        Ok(())
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn demolish(mut ctx: Context<BoardAction>, x :u8, y :u8) -> Result<()> {
         let account = &mut ctx.accounts;
-        
-        account.player.energy = MAX_ENERGY;
+        update_energy(account)?;
+        let board = &mut ctx.accounts.board.load_mut()?;
+
+        if ctx.accounts.player.energy == 0 {
+            return err!(GameErrorCode::NotEnoughEnergy);
+        }
+        let game_action = &mut ctx.accounts.game_actions.load_mut()?;
+        board.demolish(x, y, ctx.accounts.player.key(), &mut ctx.accounts.player, ctx.accounts.avatar.key(), game_action)?;
+
+        ctx.accounts.player.energy -= 1;
+        msg!("You demolished a building. You have {} energy left.", ctx.accounts.player.energy);
+        Ok(())
+    }
+
+    /// Lets another player upgrade and collect the buildings of the signer
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn authorize_player(ctx: Context<BoardAction>, authorized_player: Pubkey) -> Result<()> {
+        let player = &mut ctx.accounts.player;
+        if player.is_authorized(authorized_player) {
+            return Ok(());
+        }
+        match player.authorized_players.iter_mut().find(|key| **key == Pubkey::default()) {
+            Some(slot) => *slot = authorized_player,
+            None => return err!(GameErrorCode::TooManyAuthorizedPlayers),
+        }
+
+        msg!("Authorised {} to use your buildings.", authorized_player);
+        Ok(())
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn revoke_player(ctx: Context<BoardAction>, authorized_player: Pubkey) -> Result<()> {
+        let player = &mut ctx.accounts.player;
+        match player.authorized_players.iter_mut().find(|key| **key == authorized_player) {
+            Some(slot) => *slot = Pubkey::default(),
+            None => return err!(GameErrorCode::PlayerNotAuthorized),
+        }
+
+        msg!("Revoked {} from using your buildings.", authorized_player);
+        Ok(())
+    }
+
+    pub fn update_game_config(ctx: Context<UpdateGameConfig>, treasury: Pubkey, energy_refill_fee: u64, energy_refill_stone: u64, max_energy: u64, war_pool_share: u64) -> Result<()> {
+        if treasury == Pubkey::default() || max_energy == 0 || war_pool_share > BASIS_POINTS {
+            return err!(GameErrorCode::InvalidGameConfig);
+        }
+        let config = &mut ctx.accounts.game_config;
+        config.treasury = treasury;
+        config.energy_refill_fee = energy_refill_fee;
+        config.energy_refill_stone = energy_refill_stone;
+        config.max_energy = max_energy;
+        config.war_pool_share = war_pool_share;
+
+        msg!("Game config updated: treasury {} fee {} stone {} max energy {} war pool share {}", treasury, energy_refill_fee, energy_refill_stone, max_energy, war_pool_share);
+        Ok(())
+    }
+
+    /// Refills up to the given amount of energy, paid per point in SOL. Part of the fee goes
+    /// to the war pool of the current game, the rest to the treasury.
+    pub fn refill_energy(ctx: Context<RefillEnergyAccounts>, amount: u64) -> Result<()> {
+        let refilled = ctx.accounts.player.refill_energy(amount, &ctx.accounts.game_config)?;
+        let fee = match refilled.checked_mul(ctx.accounts.game_config.energy_refill_fee) {
+            Some(fee) => fee,
+            None => return err!(GameErrorCode::CostOverflow),
+        };
+        let war_pool_fee = (fee as u128 * ctx.accounts.game_config.war_pool_share as u128 / BASIS_POINTS as u128) as u64;
 
         let cpi_context = CpiContext::new(
             ctx.accounts.system_program.to_account_info().clone(),
@@ -174,34 +514,244 @@ pub mod lumberjack {
         );
         anchor_lang::system_program::transfer(
             cpi_context,
-            ENERGY_REFILL_FEE,
+            fee - war_pool_fee,
         )?;
 
-        msg!("Energy refilled");
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info().clone(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.signer.to_account_info().clone(),
+                to: ctx.accounts.reward_vault.to_account_info().clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(
+            cpi_context,
+            war_pool_fee,
+        )?;
+        ctx.accounts.board.load_mut()?.war_pool += war_pool_fee;
+
+        msg!("Refilled {} energy for {} lamports", refilled, fee);
+        Ok(())
+    }
+
+    /// Pays the share of the war pool of the last decided game to a member of the winning faction.
+    /// The lamports always go to the authority of the player, so session keys can claim too.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn claim_war_reward(ctx: Context<ClaimWarReward>) -> Result<()> {
+        let board = &mut ctx.accounts.board.load_mut()?;
+        let game_action = &mut ctx.accounts.game_actions.load_mut()?;
+        let share = board.claim_war_reward(ctx.accounts.player.key(), &mut ctx.accounts.player, ctx.accounts.avatar.key(), game_action)?;
+
+        **ctx.accounts.reward_vault.to_account_info().try_borrow_mut_lamports()? -= share;
+        **ctx.accounts.authority.try_borrow_mut_lamports()? += share;
+
+        msg!("You claimed {} lamports from the war pool.", share);
+        Ok(())
+    }
+
+    /// Offers wood or stone for another resource or SOL. The offered resources are held by the
+    /// order until it is filled or cancelled.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn place_order(ctx: Context<PlaceOrder>, sell_resource: u8, sell_amount: u64, buy_resource: u8, buy_amount: u64) -> Result<()> {
+        MarketOrder::check(sell_resource, sell_amount, buy_resource, buy_amount)?;
+        let player = &mut ctx.accounts.player;
+        player.take_resource(sell_resource, sell_amount)?;
+
+        ctx.accounts.order.set_inner(MarketOrder {
+            maker: player.key(),
+            authority: player.authority,
+            order_id: player.next_order_id,
+            sell_resource,
+            sell_amount,
+            buy_resource,
+            buy_amount,
+            created_at: Clock::get()?.unix_timestamp,
+        });
+        player.next_order_id += 1;
+
+        msg!("Placed order {} selling {} of resource {} for {} of resource {}.", ctx.accounts.order.order_id, sell_amount, sell_resource, buy_amount, buy_resource);
+        Ok(())
+    }
+
+    /// Gives as much of the rest of the order back to the maker as fits into the storage. The
+    /// order is closed once it is empty, otherwise it stays on the market with the rest.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+        let order = &mut ctx.accounts.order;
+        let returned = ctx.accounts.player.add_resource(order.sell_resource, order.sell_amount);
+        if returned == 0 {
+            return err!(GameErrorCode::StorageFull);
+        }
+        order.cancel(returned);
+
+        msg!("Cancelled {} of resource {} of order {}, {} left.", returned, order.sell_resource, order.order_id, order.sell_amount);
+        if order.is_filled() {
+            order.close(ctx.accounts.authority.to_account_info())?;
+        }
+        Ok(())
+    }
+
+    /// Buys the amount of the offered resource at the price of the order. SOL is paid from the
+    /// signer, so orders asking for SOL can't be filled with a session key.
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn fill_order(ctx: Context<FillOrder>, amount: u64) -> Result<()> {
+        let order = &mut ctx.accounts.order;
+        let cost = order.fill(amount)?;
+        ctx.accounts.player.store_resource(order.sell_resource, amount)?;
+
+        if order.buy_resource == RESOURCE_SOL {
+            if ctx.accounts.signer.key() != ctx.accounts.player.authority {
+                return err!(GameErrorCode::WrongAuthority);
+            }
+            let cpi_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info().clone(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.signer.to_account_info().clone(),
+                    to: ctx.accounts.maker_authority.to_account_info().clone(),
+                },
+            );
+            anchor_lang::system_program::transfer(
+                cpi_context,
+                cost,
+            )?;
+        } else {
+            ctx.accounts.player.take_resource(order.buy_resource, cost)?;
+            ctx.accounts.maker.store_resource(order.buy_resource, cost)?;
+        }
+
+        msg!("Bought {} of resource {} for {} of resource {}.", amount, order.sell_resource, cost, order.buy_resource);
+        if order.is_filled() {
+            order.close(ctx.accounts.maker_authority.to_account_info())?;
+        }
+        Ok(())
+    }
+
+    /// Creates the SPL mint of wood or stone. The mint is its own mint authority, so tokens
+    /// only come from withdraw_resources.
+    pub fn create_resource_mint(_ctx: Context<CreateResourceMint>, resource: u8) -> Result<()> {
+        if !MarketOrder::is_tradable(resource) {
+            return err!(GameErrorCode::ResourceNotTradable);
+        }
+
+        msg!("Created the token mint of resource {}.", resource);
+        Ok(())
+    }
+
+    /// Turns wood or stone of the player into tokens in the wallet of the player
+    pub fn withdraw_resources(ctx: Context<ResourceBridge>, resource: u8, amount: u64) -> Result<()> {
+        ctx.accounts.player.take_resource(resource, amount)?;
+
+        // PDA seeds and bump to "sign" for CPI
+        let bump = *ctx.bumps.get("resource_mint").unwrap();
+        let signer: &[&[&[u8]]] = &[&[b"resourceMint", &[resource], &[bump]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.resource_mint.to_account_info(),
+                to: ctx.accounts.player_token_account.to_account_info(),
+                authority: ctx.accounts.resource_mint.to_account_info(),
+            },
+            signer,
+        );
+        mint_to(cpi_ctx, amount)?;
+
+        msg!("Withdrew {} of resource {} as tokens.", amount, resource);
+        Ok(())
+    }
+
+    /// Burns tokens of the wallet and gives the player the resources back
+    pub fn deposit_resources(ctx: Context<ResourceBridge>, resource: u8, amount: u64) -> Result<()> {
+        ctx.accounts.player.store_resource(resource, amount)?;
+
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.resource_mint.to_account_info(),
+                from: ctx.accounts.player_token_account.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            },
+        );
+        burn(cpi_ctx, amount)?;
+
+        msg!("Deposited {} tokens of resource {}.", amount, resource);
+        Ok(())
+    }
+
+    #[session_auth_or(
+        ctx.accounts.player.authority.key() == ctx.accounts.signer.key(),
+        GameErrorCode::WrongAuthority
+    )]
+    pub fn refill_energy_with_stone(mut ctx: Context<BoardAction>, amount: u64) -> Result<()> {
+        let account = &mut ctx.accounts;
+        update_energy(account)?;
+
+        let stone_per_energy = ctx.accounts.game_config.energy_refill_stone;
+        if stone_per_energy == 0 {
+            return err!(GameErrorCode::StoneRefillDisabled);
+        }
+        // Only refill as much as the stone of the player pays for
+        let affordable = ctx.accounts.player.stone / stone_per_energy;
+        if affordable == 0 {
+            return err!(GameErrorCode::NotEnoughStone);
+        }
+        let refilled = ctx.accounts.player.refill_energy(amount.min(affordable), &ctx.accounts.game_config)?;
+        let stone = refilled * stone_per_energy;
+        ctx.accounts.player.stone -= stone;
+
+        msg!("Refilled {} energy for {} stone", refilled, stone);
         Ok(())
     }
 }
 
 pub fn print_state(ctx: &mut BoardAction) -> Result<()> {
-    let board = &mut ctx.board.load_mut()?;
-    let wood = board.wood;
-    msg!("Updated energy. You have {} wood and {} energy left.", wood, ctx.player.energy);
+    msg!("Updated energy. You have {} wood and {} energy left.", ctx.player.wood, ctx.player.energy);
     Ok(())
 }
 
+/// Buildings of other players are used by passing the player account of the owner as the first
+/// remaining account
+pub fn load_building_owner<'info>(remaining_accounts: &[AccountInfo<'info>]) -> Result<Option<Account<'info, PlayerData>>> {
+    match remaining_accounts.first() {
+        Some(owner) => Ok(Some(Account::try_from(owner)?)),
+        None => Ok(None),
+    }
+}
+
+/// Accounts created before the config existed get it with the default settings
+pub fn init_game_config_if_needed(game_config: &mut Account<GameConfig>) {
+    if !game_config.is_initialized() {
+        game_config.set_inner(GameConfig::default());
+    }
+}
+
 pub fn update_energy(ctx: &mut BoardAction) -> Result<()> {
+    ctx.board.load()?.sync_player_bonuses(&mut ctx.player);
+    let max_energy = ctx.player.max_energy(&ctx.game_config);
     let mut time_passed: i64 = &Clock::get()?.unix_timestamp - &ctx.player.last_login;
     let mut time_spent: i64 = 0;
-    while time_passed > TIME_TO_REFILL_ENERGY {
+    // No regeneration above the max energy, which the admin may have lowered
+    while time_passed > TIME_TO_REFILL_ENERGY && ctx.player.energy < max_energy {
         ctx.player.energy = ctx.player.energy + 1;
         time_passed -= TIME_TO_REFILL_ENERGY;
         time_spent += TIME_TO_REFILL_ENERGY;
-        if ctx.player.energy >= MAX_ENERGY {
+        if ctx.player.energy >= max_energy {
             break;
         }
     }
 
-    if ctx.player.energy >= MAX_ENERGY {
+    if ctx.player.energy >= max_energy {
         ctx.player.last_login = Clock::get()?.unix_timestamp;
     } else {
         ctx.player.last_login += time_spent;
@@ -220,10 +770,19 @@ pub struct InitPlayer <'info> {
         bump,
     )]
     pub player: Account<'info, PlayerData>,
+    // Created before the board, its season is part of the board seeds
+    #[account(
+        init_if_needed,
+        seeds = [b"gameConfig"],
+        bump,
+        payer = signer,
+        space = GameConfig::SPACE
+    )]
+    pub game_config: Account<'info, GameConfig>,
     #[account( 
         init_if_needed,
         space = 10024,
-        seeds = [b"board".as_ref()],
+        seeds = [b"board".as_ref(), &game_config.season.to_le_bytes()],
         payer = signer,
         bump,
     )]
@@ -242,16 +801,68 @@ pub struct InitPlayer <'info> {
 
 }
 
+// Boards created before seasons existed have the seeds ["board"]
 #[derive(Accounts)]
-pub struct RestartGame <'info> {
+pub struct MigrateLegacyBoard <'info> {
+    #[account(mut, address = ADMIN_PUBKEY)]
+    pub signer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        seeds = [b"gameConfig"],
+        bump,
+        payer = signer,
+        space = GameConfig::SPACE
+    )]
+    pub game_config: Account<'info, GameConfig>,
+    #[account( 
+        mut,
+        seeds = [b"board".as_ref()],
+        bump,
+        close = signer,
+    )]
+    pub legacy_board: AccountLoader<'info, BoardAccount>,
     #[account( 
         init_if_needed,
         space = 10024,
-        seeds = [b"board".as_ref()],
+        seeds = [b"board".as_ref(), &game_config.season.to_le_bytes()],
         payer = signer,
         bump,
     )]
     pub board: AccountLoader<'info, BoardAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RestartGame <'info> {
+    #[account(
+        init_if_needed,
+        seeds = [b"gameConfig"],
+        bump,
+        payer = signer,
+        space = GameConfig::SPACE
+    )]
+    pub game_config: Account<'info, GameConfig>,
+    #[account( 
+        seeds = [b"board".as_ref(), &game_config.season.to_le_bytes()],
+        bump,
+    )]
+    pub board: AccountLoader<'info, BoardAccount>,
+    #[account( 
+        init,
+        space = 10024,
+        seeds = [b"board".as_ref(), &(game_config.season + 1).to_le_bytes()],
+        payer = signer,
+        bump,
+    )]
+    pub next_board: AccountLoader<'info, BoardAccount>,
+    #[account( 
+        init,
+        space = SeasonResult::SPACE,
+        seeds = [b"seasonResult".as_ref(), &game_config.season.to_le_bytes()],
+        payer = signer,
+        bump,
+    )]
+    pub season_result: Account<'info, SeasonResult>,
     #[account(
         init_if_needed,
         seeds = [b"gameActions"],
@@ -273,7 +884,95 @@ pub struct PlayerData {
     pub level: u8,
     pub xp: u64,    
     pub energy: u64,
-    pub last_login: i64
+    pub last_login: i64,
+    pub wood: u64,
+    pub stone: u64,
+    pub faction: u8,
+    pub faction_game: u64, // Game number of the board in which the faction was joined
+    pub faction_reward_claimed: bool,
+    pub authorized_players: [Pubkey; MAX_AUTHORIZED_PLAYERS], // Player accounts which may use our buildings
+    pub food: u64,
+    pub energy_bonus: u64, // Max energy from houses
+    pub storage_bonus: u64, // Resource storage from warehouses
+    pub bonus_game: u64, // Game number of the board the bonuses were earned in
+    pub contribution: u64, // Wood and stone given to the treasury in the contribution game
+    pub contribution_game: u64, // Game number the contribution and war counters belong to
+    pub upgrade_spent: u64, // Treasury wood and stone spent on good and evil upgrades
+    pub actions_taken: u64,
+    pub damage_dealt: u64, // Damage of our faction building in the fights we started
+    pub war_score: u64,
+    pub war_reward_claimed: bool,
+    pub last_war_game: u64, // Score of the game before the contribution game
+    pub last_war_score: u64,
+    pub last_war_faction: u8,
+    pub next_order_id: u64, // Seeds the market order placed next
+}
+
+impl PlayerData {
+    pub fn max_energy(&self, game_config: &GameConfig) -> u64 {
+        game_config.max_energy + self.energy_bonus
+    }
+
+    /// Adds up to the amount of energy without going above the max energy and returns what was added
+    pub fn refill_energy(&mut self, amount: u64, game_config: &GameConfig) -> Result<u64> {
+        let refilled = self.max_energy(game_config).saturating_sub(self.energy).min(amount);
+        if refilled == 0 {
+            return err!(GameErrorCode::EnergyAlreadyFull);
+        }
+        self.energy += refilled;
+        Ok(refilled)
+    }
+
+    pub fn storage_capacity(&self) -> u64 {
+        BASE_STORAGE + self.storage_bonus
+    }
+
+    /// Adds as much of the amount as fits into the storage and returns what was added
+    pub fn add_resource(&mut self, resource: u8, amount: u64) -> u64 {
+        let capacity = self.storage_capacity();
+        let stored = match resource {
+            RESOURCE_WOOD => &mut self.wood,
+            RESOURCE_STONE => &mut self.stone,
+            RESOURCE_FOOD => &mut self.food,
+            _ => return 0,
+        };
+        let added = capacity.saturating_sub(*stored).min(amount);
+        *stored += added;
+        added
+    }
+
+    /// Removes the amount of wood or stone or fails without changing anything
+    pub fn take_resource(&mut self, resource: u8, amount: u64) -> Result<()> {
+        let (stored, error) = match resource {
+            RESOURCE_WOOD => (&mut self.wood, GameErrorCode::NotEnoughWood),
+            RESOURCE_STONE => (&mut self.stone, GameErrorCode::NotEnoughStone),
+            _ => return err!(GameErrorCode::ResourceNotTradable),
+        };
+        if *stored < amount {
+            return Err(error.into());
+        }
+        *stored -= amount;
+        Ok(())
+    }
+
+    /// Adds the whole amount or fails if it does not fit into the storage, so traded
+    /// resources are never lost
+    pub fn store_resource(&mut self, resource: u8, amount: u64) -> Result<()> {
+        let stored = match resource {
+            RESOURCE_WOOD => self.wood,
+            RESOURCE_STONE => self.stone,
+            _ => return err!(GameErrorCode::ResourceNotTradable),
+        };
+        if stored + amount > self.storage_capacity() {
+            return err!(GameErrorCode::StorageFull);
+        }
+        self.add_resource(resource, amount);
+        Ok(())
+    }
+
+    pub fn is_authorized(&self, player: Pubkey) -> bool {
+        player != Pubkey::default() && self.authorized_players.contains(&player)
+    }
 }
 
 #[derive(Accounts, Session)]
@@ -288,7 +987,7 @@ pub struct BoardAction <'info> {
     pub session_token: Option<Account<'info, SessionToken>>,
     #[account( 
         mut,
-        seeds = [b"board".as_ref()],
+        seeds = [b"board".as_ref(), &game_config.season.to_le_bytes()],
         bump,
     )]
     pub board: AccountLoader<'info, BoardAccount>,
@@ -306,6 +1005,11 @@ pub struct BoardAction <'info> {
         bump,
     )]
     pub player: Account<'info, PlayerData>,
+    #[account(
+        seeds = [b"gameConfig"],
+        bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
     #[account(mut)]
     pub signer: Signer<'info>,
 }
@@ -318,12 +1022,231 @@ pub struct RefillEnergyAccounts <'info> {
         bump,
     )]
     pub player: Account<'info, PlayerData>,
+    #[account(
+        seeds = [b"gameConfig"],
+        bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
     #[account(mut)]
     pub signer: Signer<'info>,
     #[account(mut, 
-        address = TREASURY_PUBKEY,
+        address = game_config.treasury,
     )]
     pub treasury: SystemAccount<'info>,
+    #[account( 
+        mut,
+        seeds = [b"board".as_ref(), &game_config.season.to_le_bytes()],
+        bump,
+    )]
+    pub board: AccountLoader<'info, BoardAccount>,
+    #[account(
+        init_if_needed,
+        seeds = [b"rewardVault"],
+        bump,
+        payer = signer,
+        space = RewardVault::SPACE
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts, Session)]
+pub struct ClaimWarReward <'info> {
+    #[session(
+        // The ephemeral key pair signing the transaction
+        signer = signer,
+        // The authority of the user account which must have created the session
+        authority = player.authority.key()
+    )]
+    // Session Tokens are passed as optional accounts
+    pub session_token: Option<Account<'info, SessionToken>>,
+    #[account( 
+        mut,
+        seeds = [b"board".as_ref(), &game_config.season.to_le_bytes()],
+        bump,
+    )]
+    pub board: AccountLoader<'info, BoardAccount>,
+    #[account(
+        mut,
+        seeds = [b"gameActions"],
+        bump
+    )]
+    pub game_actions: AccountLoader<'info, GameActionHistory>,
+    /// CHECK: can be anything, its ok 
+    pub avatar: AccountInfo<'info>,
+    #[account( 
+        mut,
+        seeds = [b"player".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+    #[account(
+        seeds = [b"gameConfig"],
+        bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
+    #[account(
+        mut,
+        seeds = [b"rewardVault"],
+        bump
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+    /// CHECK: receives the reward, checked to be the authority of the player
+    #[account(mut, address = player.authority)]
+    pub authority: AccountInfo<'info>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts, Session)]
+pub struct PlaceOrder <'info> {
+    #[session(
+        signer = signer,
+        authority = player.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+    #[account( 
+        mut,
+        seeds = [b"player".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+    #[account( 
+        init,
+        space = MarketOrder::SPACE,
+        seeds = [b"order".as_ref(), player.key().as_ref(), &player.next_order_id.to_le_bytes()],
+        payer = signer,
+        bump,
+    )]
+    pub order: Account<'info, MarketOrder>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts, Session)]
+pub struct CancelOrder <'info> {
+    #[session(
+        signer = signer,
+        authority = player.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+    #[account( 
+        mut,
+        seeds = [b"player".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+    // The rent goes back to the wallet of the maker, even if a session key paid it
+    #[account( 
+        mut,
+        seeds = [b"order".as_ref(), player.key().as_ref(), &order.order_id.to_le_bytes()],
+        bump,
+        has_one = authority,
+    )]
+    pub order: Account<'info, MarketOrder>,
+    /// CHECK: receives the rent of the order, checked to be the authority of the order
+    #[account(mut)]
+    pub authority: AccountInfo<'info>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts, Session)]
+pub struct FillOrder <'info> {
+    #[session(
+        signer = signer,
+        authority = player.authority.key()
+    )]
+    pub session_token: Option<Account<'info, SessionToken>>,
+    #[account( 
+        mut,
+        seeds = [b"player".as_ref(), player.authority.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+    #[account( 
+        mut,
+        seeds = [b"order".as_ref(), order.maker.as_ref(), &order.order_id.to_le_bytes()],
+        bump,
+        has_one = maker,
+        constraint = order.maker != player.key() @ GameErrorCode::OwnOrder,
+    )]
+    pub order: Account<'info, MarketOrder>,
+    #[account(mut)]
+    pub maker: Account<'info, PlayerData>,
+    /// CHECK: receives SOL payments and the rent of filled orders, checked to be the authority of the order
+    #[account(mut, address = order.authority)]
+    pub maker_authority: AccountInfo<'info>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(resource: u8)]
+pub struct CreateResourceMint <'info> {
+    #[account(mut, address = ADMIN_PUBKEY)]
+    pub signer: Signer<'info>,
+    // The PDA is both the address of the mint account and the mint authority. One token is one
+    // wood or stone, so the mint has no decimals.
+    #[account(
+        init,
+        seeds = [b"resourceMint".as_ref(), &[resource]],
+        bump,
+        payer = signer,
+        mint::decimals = 0,
+        mint::authority = resource_mint,
+    )]
+    pub resource_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Tokens belong to the wallet, so session keys can't move resources in or out
+#[derive(Accounts)]
+#[instruction(resource: u8)]
+pub struct ResourceBridge <'info> {
+    #[account( 
+        mut,
+        seeds = [b"player".as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub player: Account<'info, PlayerData>,
+    #[account(
+        mut,
+        seeds = [b"resourceMint".as_ref(), &[resource]],
+        bump,
+    )]
+    pub resource_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = resource_mint,
+        associated_token::authority = signer
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGameConfig <'info> {
+    #[account(mut, address = ADMIN_PUBKEY)]
+    pub signer: Signer<'info>,
+    // init_if_needed so games started before the config existed can create it
+    #[account(
+        init_if_needed,
+        seeds = [b"gameConfig"],
+        bump,
+        payer = signer,
+        space = GameConfig::SPACE
+    )]
+    pub game_config: Account<'info, GameConfig>,
     pub system_program: Program<'info, System>,
 }
 
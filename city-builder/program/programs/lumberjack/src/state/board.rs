@@ -1,15 +1,30 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{
+    BuildingDefinition, MapGenerator, RESOURCE_NONE, RESOURCE_STONE, RESOURCE_WOOD,
+};
 use crate::GameErrorCode;
+use crate::PlayerData;
 use crate::BOARD_SIZE_X;
 use crate::BOARD_SIZE_Y;
 
-const BUILDING_TYPE_TREE: u8 = 0;
-const BUILDING_TYPE_EMPTY: u8 = 1;
-const BUILDING_TYPE_SAWMILL: u8 = 2;
-const BUILDING_TYPE_MINE: u8 = 3;
-const BUILDING_TYPE_GOOD: u8 = 4;
-const BUILDING_TYPE_EVIL: u8 = 5;
+pub const BUILDING_TYPE_TREE: u8 = 0;
+pub const BUILDING_TYPE_EMPTY: u8 = 1;
+pub const BUILDING_TYPE_SAWMILL: u8 = 2;
+pub const BUILDING_TYPE_MINE: u8 = 3;
+pub const BUILDING_TYPE_GOOD: u8 = 4;
+pub const BUILDING_TYPE_EVIL: u8 = 5;
+pub const BUILDING_TYPE_STONE_DEPOSIT: u8 = 6;
+pub const BUILDING_TYPE_WATER: u8 = 7; // Also used for the tiles outside of the board size
+pub const BUILDING_TYPE_HOUSE: u8 = 8;
+pub const BUILDING_TYPE_FARM: u8 = 9;
+pub const BUILDING_TYPE_WAREHOUSE: u8 = 10;
+pub const BUILDING_TYPE_SAPLING: u8 = 11;
+pub const BUILDING_TYPE_WALL: u8 = 12;
+pub const BUILDING_TYPE_TOWER: u8 = 13;
+
+// Room for the good and evil buildings and a ring of tiles around them
+pub const MIN_BOARD_SIZE: u8 = 4;
 
 const BUILDING_START_HEALTH: i64 = 9000;
 
@@ -19,12 +34,44 @@ const ACTION_TYPE_UPGRADE: u8 = 2;
 const ACTION_TYPE_COLLECT: u8 = 3;
 const ACTION_TYPE_FIGHT: u8 = 4;
 const ACTION_RESET_GAME: u8 = 5;
-
-const GOOD_POSITION_X: usize = 5;
-const GOOD_POSITION_Y: usize = 4;
-
-const EVIL_POSITION_X: usize = 4;
-const EVIL_POSITION_Y: usize = 4;
+const ACTION_TYPE_CONTRIBUTE: u8 = 6;
+const ACTION_TYPE_JOIN_FACTION: u8 = 7;
+const ACTION_TYPE_CLAIM_FACTION_REWARD: u8 = 8;
+const ACTION_TYPE_TRANSFER_BUILDING: u8 = 9;
+const ACTION_TYPE_DEMOLISH: u8 = 10;
+const ACTION_TYPE_FINISH_UPGRADE: u8 = 11;
+const ACTION_TYPE_PLANT_TREE: u8 = 12;
+const ACTION_TYPE_CLAIM_WAR_REWARD: u8 = 13;
+const ACTION_TYPE_RAID: u8 = 14;
+const ACTION_TYPE_DEFEND: u8 = 15;
+
+// Empty tiles next to a tree sprout a sapling after this many seconds, saplings grow into trees.
+// building_start_time of a tile holds when it became empty or when the sapling was planted.
+pub const SPROUT_TIME: i64 = 300;
+pub const GROW_TIME: i64 = 600;
+pub const PLANT_TREE_WOOD_COST: u64 = 2;
+
+// Raids on the buildings of other players. building_health of player buildings holds the health
+// left, zero means undamaged so buildings from before raids existed and finished upgrades are whole.
+const RAID_DAMAGE: i64 = 40;
+const RAID_STEAL_PERCENT: u64 = 25; // of the production waiting in the raided building
+const MAX_RAID_DEFENSE: u64 = 75; // percent, walls and towers never block a raid completely
+const DEFEND_REPAIR: i64 = 50;
+pub const DEFEND_STONE_COST: u64 = 5;
+
+// Paid speed-ups cost one food for every started block of this many remaining seconds
+const SPEED_UP_SECONDS_PER_FOOD: i64 = 10;
+
+pub const FACTION_NONE: u8 = 0;
+pub const FACTION_GOOD: u8 = 1;
+pub const FACTION_EVIL: u8 = 2;
+
+// Largest contributors to the treasury which are ranked on the board and archived with the season
+pub const TOP_CONTRIBUTORS: usize = 3;
+
+// War score of faction members, the winners share the war pool by their score. Wood and stone
+// count one point each, like damage dealt by the faction building in the fights a player starts.
+pub const WAR_SCORE_PER_ACTION: u64 = 5;
 
 impl BoardAccount {
     /*pub fn new(&mut self) -> Self {
@@ -43,21 +90,48 @@ impl BoardAccount {
         mySelf
     }*/
 
-    pub fn Restart(&mut self, game_actions: &mut GameActionHistory, player: Pubkey) -> Result<()> {
-        self.data = [[TileData::default(); BOARD_SIZE_X]; BOARD_SIZE_Y];
+    pub fn Restart(
+        &mut self,
+        game_actions: &mut GameActionHistory,
+        player: Pubkey,
+        size_x: u8,
+        size_y: u8,
+        seed: u64,
+    ) -> Result<()> {
+        if size_x < MIN_BOARD_SIZE
+            || size_y < MIN_BOARD_SIZE
+            || size_x as usize > BOARD_SIZE_X
+            || size_y as usize > BOARD_SIZE_Y
+        {
+            return err!(GameErrorCode::InvalidBoardSize);
+        }
+
+        self.size_x = size_x;
+        self.size_y = size_y;
+        self.seed = seed;
+        self.generate_map(Clock::get()?.unix_timestamp);
         self.action_id = 0;
         self.wood = 0;
         self.stone = 0;
         self.damm_level = 0;
 
-        self.data[EVIL_POSITION_X][EVIL_POSITION_Y].building_type = BUILDING_TYPE_EVIL;
-        self.data[EVIL_POSITION_X][EVIL_POSITION_Y].building_health = BUILDING_START_HEALTH;
+        let (evil_x, evil_y) = self.evil_position();
+        self.data[evil_x][evil_y].building_type = BUILDING_TYPE_EVIL;
+        self.data[evil_x][evil_y].building_health = BUILDING_START_HEALTH;
 
-        self.data[GOOD_POSITION_X][GOOD_POSITION_Y].building_type = BUILDING_TYPE_GOOD;
-        self.data[GOOD_POSITION_X][GOOD_POSITION_Y].building_health = BUILDING_START_HEALTH;
+        let (good_x, good_y) = self.good_position();
+        self.data[good_x][good_y].building_type = BUILDING_TYPE_GOOD;
+        self.data[good_x][good_y].building_health = BUILDING_START_HEALTH;
         self.initialized = true;
         self.evil_won = false;
         self.good_won = false;
+        self.game_number += 1;
+        self.good_members = 0;
+        self.evil_members = 0;
+        self.top_contributors = [Pubkey::default(); TOP_CONTRIBUTORS];
+        self.top_contributions = [0; TOP_CONTRIBUTORS];
+        self.good_war_score = 0;
+        self.evil_war_score = 0;
 
         game_actions.id_counter = 0;
         game_actions.action_index = 0;
@@ -79,23 +153,194 @@ impl BoardAccount {
         Ok(())
     }
 
-    pub fn chop_tree(
+    /// A new season starts on a new board. The game number keeps counting so faction
+    /// memberships and bonuses of the last season end, and its reward stays claimable.
+    pub fn carry_over(&mut self, previous: &BoardAccount) {
+        self.game_number = previous.game_number;
+        self.reward_game = previous.reward_game;
+        self.reward_faction = previous.reward_faction;
+        self.reward_members = previous.reward_members;
+        self.reward_wood = previous.reward_wood;
+        self.reward_stone = previous.reward_stone;
+        self.war_pool = previous.war_pool;
+        self.reward_war_score = previous.reward_war_score;
+        self.reward_war_pool = previous.reward_war_pool;
+        self.reward_war_claimed = previous.reward_war_claimed;
+        self.reward_war_game = previous.reward_war_game;
+        self.reward_war_faction = previous.reward_war_faction;
+    }
+
+    /// Good wins if both buildings fall in the same fight, like in fight_good_vs_evil
+    pub fn winner(&self) -> u8 {
+        if self.good_won {
+            FACTION_GOOD
+        } else if self.evil_won {
+            FACTION_EVIL
+        } else {
+            FACTION_NONE
+        }
+    }
+
+    pub fn faction_building_levels(&self) -> (u32, u32) {
+        let (good_x, good_y) = self.good_position();
+        let (evil_x, evil_y) = self.evil_position();
+        (
+            self.data[good_x][good_y].building_level,
+            self.data[evil_x][evil_y].building_level,
+        )
+    }
+
+    fn generate_map(&mut self, now: i64) {
+        let mut generator = MapGenerator::new(self.seed);
+        self.data = [[TileData::default(); BOARD_SIZE_X]; BOARD_SIZE_Y];
+        for x in 0..BOARD_SIZE_X {
+            for y in 0..BOARD_SIZE_Y {
+                self.data[x][y].building_start_time = now;
+                self.data[x][y].building_type =
+                    if x < self.size_x as usize && y < self.size_y as usize {
+                        generator.next_tile()
+                    } else {
+                        BUILDING_TYPE_WATER
+                    };
+            }
+        }
+    }
+
+    /// Boards created before the size was configurable use the whole tile array
+    pub fn size(&self) -> (usize, usize) {
+        if self.size_x == 0 || self.size_y == 0 {
+            (BOARD_SIZE_X, BOARD_SIZE_Y)
+        } else {
+            (self.size_x as usize, self.size_y as usize)
+        }
+    }
+
+    // The good and evil buildings face each other in the middle of the board
+    pub fn good_position(&self) -> (usize, usize) {
+        let (size_x, size_y) = self.size();
+        (size_x / 2, size_y / 2 - 1)
+    }
+
+    pub fn evil_position(&self) -> (usize, usize) {
+        let (size_x, size_y) = self.size();
+        (size_x / 2 - 1, size_y / 2 - 1)
+    }
+
+    fn check_tile(&self, x: u8, y: u8) -> Result<()> {
+        let (size_x, size_y) = self.size();
+        if x as usize >= size_x || y as usize >= size_y {
+            return err!(GameErrorCode::TileOutOfBounds);
+        }
+        Ok(())
+    }
+
+    fn is_grown_tree(&self, x: usize, y: usize, now: i64) -> bool {
+        let tile = self.data[x][y];
+        tile.building_type == BUILDING_TYPE_TREE
+            || (tile.building_type == BUILDING_TYPE_SAPLING
+                && tile.building_start_time + GROW_TIME <= now)
+    }
+
+    fn has_tree_neighbour(&self, x: usize, y: usize, now: i64) -> bool {
+        let (size_x, size_y) = self.size();
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        neighbours
+            .iter()
+            .any(|&(nx, ny)| nx < size_x && ny < size_y && self.is_grown_tree(nx, ny, now))
+    }
+
+    /// Building type and start time of a tile after regrowth, trees are not stored when they
+    /// grow but evaluated from the timestamps whenever the tile is used
+    pub fn grown_tile(&self, x: usize, y: usize, now: i64) -> (u8, i64) {
+        let tile = self.data[x][y];
+        let mut building_type = tile.building_type;
+        let mut start_time = tile.building_start_time;
+        if building_type == BUILDING_TYPE_EMPTY
+            && start_time + SPROUT_TIME <= now
+            && self.has_tree_neighbour(x, y, now)
+        {
+            building_type = BUILDING_TYPE_SAPLING;
+            start_time += SPROUT_TIME;
+        }
+        if building_type == BUILDING_TYPE_SAPLING && start_time + GROW_TIME <= now {
+            building_type = BUILDING_TYPE_TREE;
+        }
+        (building_type, start_time)
+    }
+
+    fn grow_tile(&mut self, x: u8, y: u8, now: i64) {
+        let (building_type, start_time) = self.grown_tile(x as usize, y as usize, now);
+        self.data[x as usize][y as usize].building_type = building_type;
+        self.data[x as usize][y as usize].building_start_time = start_time;
+    }
+
+    pub fn plant_tree(
         &mut self,
         x: u8,
         y: u8,
         player: Pubkey,
+        player_data: &mut PlayerData,
         avatar: Pubkey,
         game_actions: &mut GameActionHistory,
     ) -> Result<()> {
-        //let tile = self.board[x][y];
-
-        if self.data[x as usize][y as usize].building_type != BUILDING_TYPE_TREE {
-            return err!(GameErrorCode::TileHasNoTree);
+        self.check_tile(x, y)?;
+        let now = Clock::get()?.unix_timestamp;
+        self.grow_tile(x, y, now);
+        if self.data[x as usize][y as usize].building_type != BUILDING_TYPE_EMPTY {
+            return err!(GameErrorCode::TileAlreadyOccupied);
+        }
+        if player_data.wood < PLANT_TREE_WOOD_COST {
+            return err!(GameErrorCode::NotEnoughWood);
         }
 
+        player_data.wood -= PLANT_TREE_WOOD_COST;
+        self.data[x as usize][y as usize].building_type = BUILDING_TYPE_SAPLING;
+        self.data[x as usize][y as usize].building_start_time = now;
+
+        let new_game_action = GameAction {
+            action_id: self.action_id,
+            action_type: ACTION_TYPE_PLANT_TREE,
+            x,
+            y,
+            player: player.key(),
+            avatar: avatar.key(),
+            tile: self.data[x as usize][y as usize],
+            amount: PLANT_TREE_WOOD_COST,
+        };
+        self.add_new_game_action(game_actions, new_game_action);
+        self.fight_good_vs_evil(game_actions, player, player_data, avatar);
+        Ok(())
+    }
+
+    /// Returns the wood or stone which fit into the storage of the player
+    pub fn chop_tree(
+        &mut self,
+        x: u8,
+        y: u8,
+        player: Pubkey,
+        player_data: &mut PlayerData,
+        avatar: Pubkey,
+        game_actions: &mut GameActionHistory,
+    ) -> Result<u64> {
+        self.check_tile(x, y)?;
+        let now = Clock::get()?.unix_timestamp;
+        self.grow_tile(x, y, now);
+
+        // Stone deposits are quarried the same way trees are chopped
         let wood_per_chop = 5;
+        let resource = match self.data[x as usize][y as usize].building_type {
+            BUILDING_TYPE_TREE => RESOURCE_WOOD,
+            BUILDING_TYPE_STONE_DEPOSIT => RESOURCE_STONE,
+            _ => return err!(GameErrorCode::TileHasNoTree),
+        };
+        let wood_per_chop = player_data.add_resource(resource, wood_per_chop);
         self.data[x as usize][y as usize].building_type = BUILDING_TYPE_EMPTY;
-        self.wood += wood_per_chop;
+        self.data[x as usize][y as usize].building_start_time = now;
 
         let new_game_action = GameAction {
             action_id: self.action_id,
@@ -109,9 +354,9 @@ impl BoardAccount {
         };
 
         self.add_new_game_action(game_actions, new_game_action);
-        self.fight_good_vs_evil(game_actions, player, avatar);
+        self.fight_good_vs_evil(game_actions, player, player_data, avatar);
 
-        Ok(())
+        Ok(wood_per_chop)
     }
 
     pub fn build(
@@ -120,35 +365,40 @@ impl BoardAccount {
         y: u8,
         building_type: u8,
         player: Pubkey,
+        player_data: &mut PlayerData,
         avatar: Pubkey,
         game_actions: &mut GameActionHistory,
     ) -> Result<()> {
-        if self.data[x as usize][y as usize].building_type != BUILDING_TYPE_EMPTY {
+        self.check_tile(x, y)?;
+        // Saplings are cleared by building, grown trees have to be chopped first
+        self.grow_tile(x, y, Clock::get()?.unix_timestamp);
+        let tile_type = self.data[x as usize][y as usize].building_type;
+        if tile_type != BUILDING_TYPE_EMPTY && tile_type != BUILDING_TYPE_SAPLING {
             return err!(GameErrorCode::TileAlreadyOccupied);
         }
 
-        let mut building_cost_stone = 0;
-        let mut building_cost_wood = 0;
-
-        if building_type == BUILDING_TYPE_SAWMILL {
-            building_cost_stone = 15;
-            building_cost_wood = 0;
-        } else if building_type == BUILDING_TYPE_MINE {
-            building_cost_stone = 0;
-            building_cost_wood = 15;
-        }
+        let definition = match BuildingDefinition::get(building_type) {
+            Some(definition) if definition.buildable => definition,
+            _ => return err!(GameErrorCode::BuildingTypeNotBuildable),
+        };
+        let building_cost_stone = definition.build_cost_stone;
+        let building_cost_wood = definition.build_cost_wood;
 
-        if self.wood < building_cost_wood {
+        if player_data.wood < building_cost_wood {
             return err!(GameErrorCode::NotEnoughWood);
         }
-        if self.stone < building_cost_stone {
+        if player_data.stone < building_cost_stone {
             return err!(GameErrorCode::NotEnoughStone);
         }
 
-        self.wood -= building_cost_wood;
-        self.stone -= building_cost_stone;
+        player_data.wood -= building_cost_wood;
+        player_data.stone -= building_cost_stone;
+        self.sync_player_bonuses(player_data);
+        player_data.energy_bonus += definition.energy_per_level;
+        player_data.storage_bonus += definition.storage_per_level;
 
         self.data[x as usize][y as usize].building_type = building_type;
+        self.data[x as usize][y as usize].building_owner = player;
         self.data[x as usize][y as usize].building_start_collect_time =
             Clock::get()?.unix_timestamp;
 
@@ -163,7 +413,7 @@ impl BoardAccount {
             amount: 0,
         };
         self.add_new_game_action(game_actions, new_game_action);
-        self.fight_good_vs_evil(game_actions, player, avatar);
+        self.fight_good_vs_evil(game_actions, player, player_data, avatar);
         Ok(())
     }
 
@@ -172,57 +422,72 @@ impl BoardAccount {
         x: u8,
         y: u8,
         player: Pubkey,
+        player_data: &mut PlayerData,
+        building_owner: Option<(Pubkey, &PlayerData)>,
         avatar: Pubkey,
         game_actions: &mut GameActionHistory,
     ) -> Result<()> {
-        if self.data[x as usize][y as usize].building_type != BUILDING_TYPE_SAWMILL
-            && self.data[x as usize][y as usize].building_type != BUILDING_TYPE_MINE
-            && self.data[x as usize][y as usize].building_type != BUILDING_TYPE_GOOD
-            && self.data[x as usize][y as usize].building_type != BUILDING_TYPE_EVIL
-        {
-            return err!(GameErrorCode::TileCantBeUpgraded);
-        }
-
-        let mut upgrade_cost_stone = 0;
-        let mut upgrade_cost_wood = 0;
-
-        if self.data[x as usize][y as usize].building_type == BUILDING_TYPE_SAWMILL {
-            upgrade_cost_stone = calculate_sawmill_stone_upgrade_cost(
-                self.data[x as usize][y as usize].building_level,
-            );
-            upgrade_cost_wood = calculate_sawmill_wood_upgrade_cost(
-                self.data[x as usize][y as usize].building_level,
-            );
-        } else if self.data[x as usize][y as usize].building_type == BUILDING_TYPE_MINE {
-            upgrade_cost_stone = calculate_stone_mine_stone_upgrade_cost(
-                self.data[x as usize][y as usize].building_level,
-            );
-            upgrade_cost_wood = calculate_stone_mine_wood_upgrade_cost(
-                self.data[x as usize][y as usize].building_level,
-            );
-        } else if self.data[x as usize][y as usize].building_type == BUILDING_TYPE_GOOD {
-            upgrade_cost_stone =
-                calculate_good_stone_upgrade_cost(self.data[x as usize][y as usize].building_level);
-            upgrade_cost_wood =
-                calculate_good_wood_upgrade_cost(self.data[x as usize][y as usize].building_level);
-        } else if self.data[x as usize][y as usize].building_type == BUILDING_TYPE_EVIL {
-            upgrade_cost_stone =
-                calculate_evil_stone_upgrade_cost(self.data[x as usize][y as usize].building_level);
-            upgrade_cost_wood =
-                calculate_evil_wood_upgrade_cost(self.data[x as usize][y as usize].building_level);
-        }
-
-        if self.wood < upgrade_cost_wood {
-            return err!(GameErrorCode::NotEnoughWood);
+        self.check_tile(x, y)?;
+        self.check_building_access(x, y, player, building_owner)?;
+
+        let definition =
+            match BuildingDefinition::get(self.data[x as usize][y as usize].building_type) {
+                Some(definition) => definition,
+                None => return err!(GameErrorCode::TileCantBeUpgraded),
+            };
+        self.finish_upgrade_if_ready(x, y, player, player_data, game_actions, avatar)?;
+        if self.is_upgrading(x, y) {
+            return err!(GameErrorCode::BuildingIsUpgrading);
         }
-        if self.stone < upgrade_cost_stone {
-            return err!(GameErrorCode::NotEnoughStone);
+        // The owner profits from the bonus of houses and warehouses, so only they can upgrade them
+        let grants_bonus = definition.energy_per_level > 0 || definition.storage_per_level > 0;
+        if grants_bonus && self.data[x as usize][y as usize].building_owner != player {
+            return err!(GameErrorCode::NotBuildingOwner);
         }
 
-        self.wood -= upgrade_cost_wood;
-        self.stone -= upgrade_cost_stone;
+        let building_level = self.data[x as usize][y as usize].building_level;
+        let (upgrade_cost_wood, upgrade_cost_stone) = definition.upgrade_cost(building_level)?;
+
+        // The good and evil buildings belong to everybody, so they are paid from the shared
+        // treasury. All other buildings are paid by the player.
+        let building_type = self.data[x as usize][y as usize].building_type;
+        if building_type == BUILDING_TYPE_GOOD || building_type == BUILDING_TYPE_EVIL {
+            let faction = if building_type == BUILDING_TYPE_GOOD {
+                FACTION_GOOD
+            } else {
+                FACTION_EVIL
+            };
+            if self.faction_of(player_data) != faction {
+                return err!(GameErrorCode::NotInFaction);
+            }
+            if self.wood < upgrade_cost_wood {
+                return err!(GameErrorCode::NotEnoughWood);
+            }
+            if self.stone < upgrade_cost_stone {
+                return err!(GameErrorCode::NotEnoughStone);
+            }
 
-        self.data[x as usize][y as usize].building_level += 1;
+            self.wood -= upgrade_cost_wood;
+            self.stone -= upgrade_cost_stone;
+            self.sync_war_score(player_data);
+            player_data.upgrade_spent += upgrade_cost_wood + upgrade_cost_stone;
+        } else {
+            if player_data.wood < upgrade_cost_wood {
+                return err!(GameErrorCode::NotEnoughWood);
+            }
+            if player_data.stone < upgrade_cost_stone {
+                return err!(GameErrorCode::NotEnoughStone);
+            }
+
+            player_data.wood -= upgrade_cost_wood;
+            player_data.stone -= upgrade_cost_stone;
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        self.data[x as usize][y as usize].building_start_upgrade_time = now;
+        if definition.upgrade_time.at(building_level)? == 0 {
+            self.complete_upgrade(x, y, player, player_data, now);
+        }
 
         let new_game_action = GameAction {
             action_id: self.action_id,
@@ -235,7 +500,7 @@ impl BoardAccount {
             amount: 0,
         };
         self.add_new_game_action(game_actions, new_game_action);
-        self.fight_good_vs_evil(game_actions, player, avatar);
+        self.fight_good_vs_evil(game_actions, player, player_data, avatar);
         Ok(())
     }
 
@@ -244,33 +509,38 @@ impl BoardAccount {
         x: u8,
         y: u8,
         player: Pubkey,
+        player_data: &mut PlayerData,
+        building_owner: Option<(Pubkey, &PlayerData)>,
         avatar: Pubkey,
         game_actions: &mut GameActionHistory,
     ) -> Result<()> {
-        if self.data[x as usize][y as usize].building_type != BUILDING_TYPE_SAWMILL
-            && self.data[x as usize][y as usize].building_type != BUILDING_TYPE_MINE
-        {
-            return err!(GameErrorCode::BuildingTypeNotCollectable);
+        self.check_tile(x, y)?;
+        self.check_building_access(x, y, player, building_owner)?;
+
+        let definition =
+            match BuildingDefinition::get(self.data[x as usize][y as usize].building_type) {
+                Some(definition) if definition.production_resource != RESOURCE_NONE => definition,
+                _ => return err!(GameErrorCode::BuildingTypeNotCollectable),
+            };
+
+        self.finish_upgrade_if_ready(x, y, player, player_data, game_actions, avatar)?;
+        if self.is_upgrading(x, y) {
+            return err!(GameErrorCode::BuildingIsUpgrading);
         }
 
-        if (Clock::get()?.unix_timestamp
-            - self.data[x as usize][y as usize].building_start_collect_time)
-            < 60
-        {
+        // Production accrues continuously since the last collection until the storage is full
+        let now = Clock::get()?.unix_timestamp;
+        let collect_amount = definition.accrued(
+            self.data[x as usize][y as usize].building_level,
+            now - self.data[x as usize][y as usize].building_start_collect_time,
+        )?;
+        if collect_amount == 0 {
             return err!(GameErrorCode::ProductionNotReadyYet);
         }
 
-        self.data[x as usize][y as usize].building_start_collect_time =
-            Clock::get()?.unix_timestamp;
-
+        self.data[x as usize][y as usize].building_start_collect_time = now;
         let collect_amount =
-            calculate_building_collection(self.data[x as usize][y as usize].building_level);
-
-        if self.data[x as usize][y as usize].building_type == BUILDING_TYPE_SAWMILL {
-            self.wood += collect_amount;
-        } else if self.data[x as usize][y as usize].building_type == BUILDING_TYPE_MINE {
-            self.stone += collect_amount;
-        }
+            player_data.add_resource(definition.production_resource, collect_amount);
 
         let new_game_action = GameAction {
             action_id: self.action_id,
@@ -283,10 +553,752 @@ impl BoardAccount {
             amount: collect_amount,
         };
         self.add_new_game_action(game_actions, new_game_action);
-        self.fight_good_vs_evil(game_actions, player, avatar);
+        self.fight_good_vs_evil(game_actions, player, player_data, avatar);
+        Ok(())
+    }
+
+    pub fn is_upgrading(&self, x: u8, y: u8) -> bool {
+        self.data[x as usize][y as usize].building_start_upgrade_time != 0
+    }
+
+    /// Time at which the running upgrade of the building is done
+    fn upgrade_finish_time(&self, x: u8, y: u8) -> Result<i64> {
+        let tile = self.data[x as usize][y as usize];
+        let upgrade_time = match BuildingDefinition::get(tile.building_type) {
+            Some(definition) => definition.upgrade_time.at(tile.building_level)?,
+            None => 0,
+        };
+        Ok(tile.building_start_upgrade_time + upgrade_time as i64)
+    }
+
+    // Raises the level and moves the collect time by the time spent upgrading, so the building
+    // produces nothing while it is upgraded
+    fn complete_upgrade(
+        &mut self,
+        x: u8,
+        y: u8,
+        player: Pubkey,
+        player_data: &mut PlayerData,
+        finished_at: i64,
+    ) {
+        let tile = self.data[x as usize][y as usize];
+        let upgrade_duration = finished_at - tile.building_start_upgrade_time;
+        self.data[x as usize][y as usize].building_start_collect_time += upgrade_duration;
+        self.data[x as usize][y as usize].building_start_upgrade_time = 0;
+        self.data[x as usize][y as usize].building_level += 1;
+
+        if let Some(definition) = BuildingDefinition::get(tile.building_type) {
+            // A finished upgrade also repairs player buildings, see RAID_DAMAGE
+            if definition.buildable {
+                self.data[x as usize][y as usize].building_health = 0;
+            }
+            // Houses and warehouses can only be upgraded by their owner, so the player is the owner
+            if tile.building_owner == player {
+                self.sync_player_bonuses(player_data);
+                player_data.energy_bonus += definition.energy_per_level;
+                player_data.storage_bonus += definition.storage_per_level;
+            }
+        }
+    }
+
+    fn finish_upgrade_if_ready(
+        &mut self,
+        x: u8,
+        y: u8,
+        player: Pubkey,
+        player_data: &mut PlayerData,
+        game_actions: &mut GameActionHistory,
+        avatar: Pubkey,
+    ) -> Result<bool> {
+        if !self.is_upgrading(x, y) {
+            return Ok(false);
+        }
+        let finish_time = self.upgrade_finish_time(x, y)?;
+        if Clock::get()?.unix_timestamp < finish_time {
+            return Ok(false);
+        }
+        let owner = self.data[x as usize][y as usize].building_owner;
+        if owner != player && owner != Pubkey::default() {
+            // The bonus of houses and warehouses has to be added to the account of the owner
+            let building_type = self.data[x as usize][y as usize].building_type;
+            if let Some(definition) = BuildingDefinition::get(building_type) {
+                if definition.energy_per_level > 0 || definition.storage_per_level > 0 {
+                    return Ok(false);
+                }
+            }
+        }
+
+        self.complete_upgrade(x, y, player, player_data, finish_time);
+        self.add_finish_upgrade_action(x, y, player, avatar, game_actions);
+        Ok(true)
+    }
+
+    fn add_finish_upgrade_action(
+        &mut self,
+        x: u8,
+        y: u8,
+        player: Pubkey,
+        avatar: Pubkey,
+        game_actions: &mut GameActionHistory,
+    ) {
+        let new_game_action = GameAction {
+            action_id: self.action_id,
+            action_type: ACTION_TYPE_FINISH_UPGRADE,
+            x,
+            y,
+            player: player.key(),
+            avatar: avatar.key(),
+            tile: self.data[x as usize][y as usize],
+            amount: 0,
+        };
+        self.add_new_game_action(game_actions, new_game_action);
+    }
+
+    /// Finishes an upgrade whose construction time has passed. Upgrades are also finished
+    /// lazily by the next upgrade or collect on the building.
+    pub fn finish_upgrade(
+        &mut self,
+        x: u8,
+        y: u8,
+        player: Pubkey,
+        player_data: &mut PlayerData,
+        building_owner: Option<(Pubkey, &PlayerData)>,
+        avatar: Pubkey,
+        game_actions: &mut GameActionHistory,
+    ) -> Result<()> {
+        self.check_tile(x, y)?;
+        self.check_building_access(x, y, player, building_owner)?;
+        if !self.is_upgrading(x, y) {
+            return err!(GameErrorCode::NoUpgradeInProgress);
+        }
+        if !self.finish_upgrade_if_ready(x, y, player, player_data, game_actions, avatar)? {
+            return err!(GameErrorCode::UpgradeNotFinished);
+        }
+        Ok(())
+    }
+
+    /// Finishes a running upgrade right away for food depending on the remaining time
+    pub fn speed_up_upgrade(
+        &mut self,
+        x: u8,
+        y: u8,
+        player: Pubkey,
+        player_data: &mut PlayerData,
+        building_owner: Option<(Pubkey, &PlayerData)>,
+        avatar: Pubkey,
+        game_actions: &mut GameActionHistory,
+    ) -> Result<u64> {
+        self.check_tile(x, y)?;
+        self.check_building_access(x, y, player, building_owner)?;
+        if !self.is_upgrading(x, y) {
+            return err!(GameErrorCode::NoUpgradeInProgress);
+        }
+        let owner = self.data[x as usize][y as usize].building_owner;
+        if owner != player && owner != Pubkey::default() {
+            return err!(GameErrorCode::NotBuildingOwner);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let finish_time = self.upgrade_finish_time(x, y)?;
+        let remaining = (finish_time - now).max(0);
+        let food_cost =
+            ((remaining + SPEED_UP_SECONDS_PER_FOOD - 1) / SPEED_UP_SECONDS_PER_FOOD) as u64;
+        if player_data.food < food_cost {
+            return err!(GameErrorCode::NotEnoughFood);
+        }
+        player_data.food -= food_cost;
+
+        let finished_at = now.min(finish_time);
+        self.complete_upgrade(x, y, player, player_data, finished_at);
+        self.add_finish_upgrade_action(x, y, player, avatar, game_actions);
+        Ok(food_cost)
+    }
+
+    /// Buildings without an owner, like the good and evil buildings, can be used by everybody.
+    /// Other players need to pass the account of the owner which lists them as authorised.
+    fn check_building_access(
+        &self,
+        x: u8,
+        y: u8,
+        player: Pubkey,
+        building_owner: Option<(Pubkey, &PlayerData)>,
+    ) -> Result<()> {
+        let owner = self.data[x as usize][y as usize].building_owner;
+        if owner == Pubkey::default() || owner == player {
+            return Ok(());
+        }
+
+        match building_owner {
+            Some((owner_key, owner_data))
+                if owner_key == owner && owner_data.is_authorized(player) =>
+            {
+                Ok(())
+            }
+            _ => err!(GameErrorCode::NotBuildingOwner),
+        }
+    }
+
+    // Empties the tile and takes the bonus of houses and warehouses from the owner, buildings
+    // without a bonus can be removed without the owner account
+    fn remove_building(&mut self, x: u8, y: u8, owner_data: Option<&mut PlayerData>) -> Result<()> {
+        let definition = BuildingDefinition::get(self.data[x as usize][y as usize].building_type);
+        if let (Some(definition), Some(owner_data)) = (definition, owner_data) {
+            let levels = self.data[x as usize][y as usize].building_level as u64 + 1;
+            self.sync_player_bonuses(owner_data);
+            owner_data.energy_bonus = owner_data
+                .energy_bonus
+                .saturating_sub(definition.energy_per_level * levels);
+            owner_data.storage_bonus = owner_data
+                .storage_bonus
+                .saturating_sub(definition.storage_per_level * levels);
+        }
+
+        self.data[x as usize][y as usize] = TileData {
+            building_type: BUILDING_TYPE_EMPTY,
+            building_start_time: Clock::get()?.unix_timestamp,
+            ..TileData::default()
+        };
+        Ok(())
+    }
+
+    /// Health left of a player building, buildings which were never damaged have the full health
+    pub fn building_health(&self, x: u8, y: u8) -> Result<i64> {
+        let tile = self.data[x as usize][y as usize];
+        if tile.building_health > 0 {
+            return Ok(tile.building_health);
+        }
+        match BuildingDefinition::get(tile.building_type) {
+            Some(definition) => Ok(definition.health.at(tile.building_level)? as i64),
+            None => Ok(0),
+        }
+    }
+
+    /// Percent less raid damage from the walls and towers of the owner in range of the tile
+    pub fn raid_defense(&self, x: u8, y: u8) -> Result<u64> {
+        let owner = self.data[x as usize][y as usize].building_owner;
+        let (size_x, size_y) = self.size();
+        let mut defense = 0;
+        for defender_x in 0..size_x {
+            for defender_y in 0..size_y {
+                let tile = self.data[defender_x][defender_y];
+                if tile.building_owner != owner
+                    || (defender_x == x as usize && defender_y == y as usize)
+                {
+                    continue;
+                }
+                let definition = match BuildingDefinition::get(tile.building_type) {
+                    Some(definition) if definition.defense_range > 0 => definition,
+                    _ => continue,
+                };
+                let distance = (defender_x as i64 - x as i64)
+                    .abs()
+                    .max((defender_y as i64 - y as i64).abs());
+                if distance <= definition.defense_range as i64 {
+                    defense += definition.defense.at(tile.building_level)?;
+                }
+            }
+        }
+        Ok(defense.min(MAX_RAID_DEFENSE))
+    }
+
+    /// Damages a building of another player and steals part of its waiting production. The
+    /// account of the owner is only needed to destroy houses and warehouses, which give a bonus.
+    pub fn raid(
+        &mut self,
+        x: u8,
+        y: u8,
+        player: Pubkey,
+        player_data: &mut PlayerData,
+        building_owner: Option<(Pubkey, &mut PlayerData)>,
+        avatar: Pubkey,
+        game_actions: &mut GameActionHistory,
+    ) -> Result<()> {
+        self.check_tile(x, y)?;
+        let tile = self.data[x as usize][y as usize];
+        let definition = match BuildingDefinition::get(tile.building_type) {
+            Some(definition) if definition.buildable => definition,
+            _ => return err!(GameErrorCode::TileHasNoBuilding),
+        };
+        if tile.building_owner == Pubkey::default() || tile.building_owner == player {
+            return err!(GameErrorCode::CantRaidBuilding);
+        }
+
+        // Part of the production waiting in the building is stolen, the owner keeps the rest
+        let now = Clock::get()?.unix_timestamp;
+        let mut stolen = 0;
+        if definition.production_resource != RESOURCE_NONE && !self.is_upgrading(x, y) {
+            let accrued =
+                definition.accrued(tile.building_level, now - tile.building_start_collect_time)?;
+            stolen = accrued * RAID_STEAL_PERCENT / 100;
+            if stolen > 0 {
+                let left = definition.production_seconds(tile.building_level, accrued - stolen)?;
+                self.data[x as usize][y as usize].building_start_collect_time = now - left;
+                stolen = player_data.add_resource(definition.production_resource, stolen);
+            }
+        }
+
+        let defense = self.raid_defense(x, y)?;
+        let damage = (RAID_DAMAGE * (100 - defense as i64) / 100).max(1);
+        let health = self.building_health(x, y)? - damage;
+        if health > 0 {
+            self.data[x as usize][y as usize].building_health = health;
+        } else if definition.energy_per_level > 0 || definition.storage_per_level > 0 {
+            match building_owner {
+                Some((owner, owner_data)) if owner == tile.building_owner => {
+                    self.remove_building(x, y, Some(owner_data))?
+                }
+                _ => return err!(GameErrorCode::BuildingOwnerMissing),
+            }
+        } else {
+            self.remove_building(x, y, None)?;
+        }
+
+        let new_game_action = GameAction {
+            action_id: self.action_id,
+            action_type: ACTION_TYPE_RAID,
+            x,
+            y,
+            player: player.key(),
+            avatar: avatar.key(),
+            tile: self.data[x as usize][y as usize],
+            amount: stolen,
+        };
+        self.add_new_game_action(game_actions, new_game_action);
+        self.fight_good_vs_evil(game_actions, player, player_data, avatar);
         Ok(())
     }
 
+    /// Repairs a building of the player or of a player who authorised them
+    pub fn defend(
+        &mut self,
+        x: u8,
+        y: u8,
+        player: Pubkey,
+        player_data: &mut PlayerData,
+        building_owner: Option<(Pubkey, &PlayerData)>,
+        avatar: Pubkey,
+        game_actions: &mut GameActionHistory,
+    ) -> Result<()> {
+        self.check_tile(x, y)?;
+        match BuildingDefinition::get(self.data[x as usize][y as usize].building_type) {
+            Some(definition) if definition.buildable => {}
+            _ => return err!(GameErrorCode::TileHasNoBuilding),
+        }
+        self.check_building_access(x, y, player, building_owner)?;
+        if self.data[x as usize][y as usize].building_health == 0 {
+            return err!(GameErrorCode::BuildingAtFullHealth);
+        }
+        if player_data.stone < DEFEND_STONE_COST {
+            return err!(GameErrorCode::NotEnoughStone);
+        }
+        player_data.stone -= DEFEND_STONE_COST;
+
+        let tile = self.data[x as usize][y as usize];
+        let max_health = BuildingDefinition::get(tile.building_type)
+            .map_or(Ok(0), |definition| {
+                definition.health.at(tile.building_level)
+            })? as i64;
+        let health = tile.building_health + DEFEND_REPAIR;
+        self.data[x as usize][y as usize].building_health =
+            if health >= max_health { 0 } else { health };
+
+        let new_game_action = GameAction {
+            action_id: self.action_id,
+            action_type: ACTION_TYPE_DEFEND,
+            x,
+            y,
+            player: player.key(),
+            avatar: avatar.key(),
+            tile: self.data[x as usize][y as usize],
+            amount: DEFEND_REPAIR as u64,
+        };
+        self.add_new_game_action(game_actions, new_game_action);
+        self.fight_good_vs_evil(game_actions, player, player_data, avatar);
+        Ok(())
+    }
+
+    fn check_building_owner(&self, x: u8, y: u8, player: Pubkey) -> Result<()> {
+        let tile = self.data[x as usize][y as usize];
+        match BuildingDefinition::get(tile.building_type) {
+            Some(definition) if definition.buildable => {}
+            _ => return err!(GameErrorCode::TileHasNoBuilding),
+        }
+        if tile.building_owner != player {
+            return err!(GameErrorCode::NotBuildingOwner);
+        }
+        Ok(())
+    }
+
+    pub fn transfer_building(
+        &mut self,
+        x: u8,
+        y: u8,
+        new_owner: Pubkey,
+        player: Pubkey,
+        avatar: Pubkey,
+        game_actions: &mut GameActionHistory,
+    ) -> Result<()> {
+        self.check_tile(x, y)?;
+        self.check_building_owner(x, y, player)?;
+        if let Some(definition) =
+            BuildingDefinition::get(self.data[x as usize][y as usize].building_type)
+        {
+            // The bonus is stored on the player account of the owner and can't be moved with it
+            if definition.energy_per_level > 0 || definition.storage_per_level > 0 {
+                return err!(GameErrorCode::BuildingCantBeTransferred);
+            }
+        }
+
+        self.data[x as usize][y as usize].building_owner = new_owner;
+
+        let new_game_action = GameAction {
+            action_id: self.action_id,
+            action_type: ACTION_TYPE_TRANSFER_BUILDING,
+            x,
+            y,
+            player: player.key(),
+            avatar: avatar.key(),
+            tile: self.data[x as usize][y as usize],
+            amount: 0,
+        };
+        self.add_new_game_action(game_actions, new_game_action);
+        Ok(())
+    }
+
+    /// Tears down a building of the player and leaves an empty tile, nothing is refunded
+    pub fn demolish(
+        &mut self,
+        x: u8,
+        y: u8,
+        player: Pubkey,
+        player_data: &mut PlayerData,
+        avatar: Pubkey,
+        game_actions: &mut GameActionHistory,
+    ) -> Result<()> {
+        self.check_tile(x, y)?;
+        self.check_building_owner(x, y, player)?;
+
+        self.remove_building(x, y, Some(player_data))?;
+
+        let new_game_action = GameAction {
+            action_id: self.action_id,
+            action_type: ACTION_TYPE_DEMOLISH,
+            x,
+            y,
+            player: player.key(),
+            avatar: avatar.key(),
+            tile: self.data[x as usize][y as usize],
+            amount: 0,
+        };
+        self.add_new_game_action(game_actions, new_game_action);
+        self.fight_good_vs_evil(game_actions, player, player_data, avatar);
+        Ok(())
+    }
+
+    /// Moves resources of the player into the shared treasury which pays for the good and
+    /// evil building upgrades
+    pub fn contribute(
+        &mut self,
+        wood: u64,
+        stone: u64,
+        player: Pubkey,
+        player_data: &mut PlayerData,
+        avatar: Pubkey,
+        game_actions: &mut GameActionHistory,
+    ) -> Result<()> {
+        if player_data.wood < wood {
+            return err!(GameErrorCode::NotEnoughWood);
+        }
+        if player_data.stone < stone {
+            return err!(GameErrorCode::NotEnoughStone);
+        }
+
+        player_data.wood -= wood;
+        player_data.stone -= stone;
+        self.wood += wood;
+        self.stone += stone;
+        self.record_contribution(player, player_data, wood + stone);
+
+        let new_game_action = GameAction {
+            action_id: self.action_id,
+            action_type: ACTION_TYPE_CONTRIBUTE,
+            x: 0,
+            y: 0,
+            player: player.key(),
+            avatar: avatar.key(),
+            tile: TileData::default(),
+            amount: wood + stone,
+        };
+        self.add_new_game_action(game_actions, new_game_action);
+        Ok(())
+    }
+
+    // Contributions count per game, the ranking keeps the largest ones sorted from the top
+    fn record_contribution(&mut self, player: Pubkey, player_data: &mut PlayerData, amount: u64) {
+        self.sync_war_score(player_data);
+        player_data.contribution += amount;
+        self.add_war_score(player_data, amount);
+
+        let top_contributors = self.top_contributors;
+        let top_contributions = self.top_contributions;
+        let mut ranking: Vec<(Pubkey, u64)> = top_contributors
+            .iter()
+            .zip(top_contributions.iter())
+            .filter(|(contributor, _)| {
+                **contributor != player && **contributor != Pubkey::default()
+            })
+            .map(|(contributor, contribution)| (*contributor, *contribution))
+            .collect();
+        ranking.push((player, player_data.contribution));
+        ranking.sort_by(|a, b| b.1.cmp(&a.1));
+
+        self.top_contributors = [Pubkey::default(); TOP_CONTRIBUTORS];
+        self.top_contributions = [0; TOP_CONTRIBUTORS];
+        for (index, (contributor, contribution)) in
+            ranking.into_iter().take(TOP_CONTRIBUTORS).enumerate()
+        {
+            self.top_contributors[index] = contributor;
+            self.top_contributions[index] = contribution;
+        }
+    }
+
+    /// Counters are kept per game. The score of the previous game is kept until it is claimed or
+    /// the game after it is decided too.
+    pub fn sync_war_score(&self, player_data: &mut PlayerData) {
+        if player_data.contribution_game == self.game_number {
+            return;
+        }
+        player_data.last_war_game = player_data.contribution_game;
+        player_data.last_war_faction = player_data.faction;
+        player_data.last_war_score = if player_data.war_reward_claimed {
+            0
+        } else {
+            player_data.war_score
+        };
+        player_data.contribution_game = self.game_number;
+        player_data.contribution = 0;
+        player_data.upgrade_spent = 0;
+        player_data.actions_taken = 0;
+        player_data.damage_dealt = 0;
+        player_data.war_score = 0;
+        player_data.war_reward_claimed = false;
+    }
+
+    // Only members earn a score, so the faction total is the sum of the scores of its members.
+    // Once the game is decided the scores are final, they are what the war pool is shared by.
+    // Treasury wood and stone score when they are contributed, not again when an upgrade spends
+    // them, otherwise every resource would count twice.
+    fn add_war_score(&mut self, player_data: &mut PlayerData, score: u64) {
+        if self.good_won || self.evil_won {
+            return;
+        }
+        match self.faction_of(player_data) {
+            FACTION_GOOD => self.good_war_score += score,
+            FACTION_EVIL => self.evil_war_score += score,
+            _ => return,
+        }
+        player_data.war_score += score;
+    }
+
+    /// Score of the player in the last decided game, which has not been claimed yet
+    pub fn claimable_war_score(&self, player_data: &PlayerData) -> u64 {
+        if self.reward_war_score == 0 {
+            return 0;
+        }
+        if player_data.contribution_game == self.reward_war_game {
+            if player_data.war_reward_claimed || player_data.faction != self.reward_war_faction {
+                0
+            } else {
+                player_data.war_score
+            }
+        } else if player_data.last_war_game == self.reward_war_game
+            && player_data.last_war_faction == self.reward_war_faction
+        {
+            player_data.last_war_score
+        } else {
+            0
+        }
+    }
+
+    /// Marks the share of the war pool as claimed and returns it in lamports
+    pub fn claim_war_reward(
+        &mut self,
+        player: Pubkey,
+        player_data: &mut PlayerData,
+        avatar: Pubkey,
+        game_actions: &mut GameActionHistory,
+    ) -> Result<u64> {
+        let score = self.claimable_war_score(player_data);
+        if score == 0 {
+            return err!(GameErrorCode::NoWarReward);
+        }
+        // Never pays out more than is left of the pool, whatever happens to the scores
+        let share = ((self.reward_war_pool as u128 * score as u128 / self.reward_war_score as u128)
+            as u64)
+            .min(self.reward_war_pool.saturating_sub(self.reward_war_claimed));
+        self.reward_war_claimed += share;
+        if player_data.contribution_game == self.reward_war_game {
+            player_data.war_reward_claimed = true;
+        } else {
+            player_data.last_war_score = 0;
+        }
+
+        let new_game_action = GameAction {
+            action_id: self.action_id,
+            action_type: ACTION_TYPE_CLAIM_WAR_REWARD,
+            x: 0,
+            y: 0,
+            player: player.key(),
+            avatar: avatar.key(),
+            tile: TileData::default(),
+            amount: share,
+        };
+        self.add_new_game_action(game_actions, new_game_action);
+        Ok(share)
+    }
+
+    /// Bonuses of houses and warehouses only count for the game their buildings were built in
+    pub fn sync_player_bonuses(&self, player_data: &mut PlayerData) {
+        if player_data.bonus_game != self.game_number {
+            player_data.bonus_game = self.game_number;
+            player_data.energy_bonus = 0;
+            player_data.storage_bonus = 0;
+        }
+    }
+
+    /// Faction the player fights for in the current game
+    pub fn faction_of(&self, player_data: &PlayerData) -> u8 {
+        if player_data.faction_game == self.game_number {
+            player_data.faction
+        } else {
+            FACTION_NONE
+        }
+    }
+
+    pub fn join_faction(
+        &mut self,
+        faction: u8,
+        player: Pubkey,
+        player_data: &mut PlayerData,
+        avatar: Pubkey,
+        game_actions: &mut GameActionHistory,
+    ) -> Result<()> {
+        if faction != FACTION_GOOD && faction != FACTION_EVIL {
+            return err!(GameErrorCode::UnknownFaction);
+        }
+        if self.faction_of(player_data) != FACTION_NONE {
+            return err!(GameErrorCode::AlreadyInFaction);
+        }
+        if self.evil_won || self.good_won {
+            return err!(GameErrorCode::GameAlreadyOver);
+        }
+
+        // Joining the next game overwrites the membership, so a reward of the last game which
+        // was not claimed yet is paid out first and the war score of the last game is kept
+        if self.has_faction_reward(player_data) {
+            self.claim_faction_reward(player, player_data, avatar, game_actions)?;
+        }
+        self.sync_war_score(player_data);
+
+        player_data.faction = faction;
+        player_data.faction_game = self.game_number;
+        player_data.faction_reward_claimed = false;
+        if faction == FACTION_GOOD {
+            self.good_members += 1;
+        } else {
+            self.evil_members += 1;
+        }
+
+        let new_game_action = GameAction {
+            action_id: self.action_id,
+            action_type: ACTION_TYPE_JOIN_FACTION,
+            x: 0,
+            y: 0,
+            player: player.key(),
+            avatar: avatar.key(),
+            tile: TileData::default(),
+            amount: faction as u64,
+        };
+        self.add_new_game_action(game_actions, new_game_action);
+        Ok(())
+    }
+
+    fn has_faction_reward(&self, player_data: &PlayerData) -> bool {
+        player_data.faction != FACTION_NONE
+            && !player_data.faction_reward_claimed
+            && player_data.faction_game == self.reward_game
+            && player_data.faction == self.reward_faction
+            && self.reward_members > 0
+    }
+
+    /// Pays the share of the reward pool to a member of the faction which won the last game
+    pub fn claim_faction_reward(
+        &mut self,
+        player: Pubkey,
+        player_data: &mut PlayerData,
+        avatar: Pubkey,
+        game_actions: &mut GameActionHistory,
+    ) -> Result<()> {
+        if !self.has_faction_reward(player_data) {
+            return err!(GameErrorCode::NoFactionReward);
+        }
+
+        let wood_share = self.reward_wood / self.reward_members as u64;
+        let stone_share = self.reward_stone / self.reward_members as u64;
+        let wood_share = player_data.add_resource(RESOURCE_WOOD, wood_share);
+        let stone_share = player_data.add_resource(RESOURCE_STONE, stone_share);
+        player_data.faction_reward_claimed = true;
+
+        let new_game_action = GameAction {
+            action_id: self.action_id,
+            action_type: ACTION_TYPE_CLAIM_FACTION_REWARD,
+            x: 0,
+            y: 0,
+            player: player.key(),
+            avatar: avatar.key(),
+            tile: TileData::default(),
+            amount: wood_share + stone_share,
+        };
+        self.add_new_game_action(game_actions, new_game_action);
+        Ok(())
+    }
+
+    // The treasury left when the fight is decided becomes the reward pool of the winners. It
+    // stays claimable until the next game is decided.
+    fn close_faction_reward(&mut self, faction: u8) {
+        self.reward_game = self.game_number;
+        self.reward_faction = faction;
+        let war_score = if faction == FACTION_GOOD {
+            self.good_war_score
+        } else {
+            self.evil_war_score
+        };
+        // Without a score among the winners the pool stays for the next game and the war
+        // reward of the game before stays claimable. Otherwise what its winners did not claim
+        // goes back into the pool.
+        if war_score > 0 {
+            self.war_pool += self.reward_war_pool.saturating_sub(self.reward_war_claimed);
+            self.reward_war_game = self.game_number;
+            self.reward_war_faction = faction;
+            self.reward_war_score = war_score;
+            self.reward_war_pool = self.war_pool;
+            self.reward_war_claimed = 0;
+            self.war_pool = 0;
+        }
+        self.reward_members = if faction == FACTION_GOOD {
+            self.good_members
+        } else {
+            self.evil_members
+        };
+        if self.reward_members == 0 {
+            return;
+        }
+        self.reward_wood = self.wood;
+        self.reward_stone = self.stone;
+        self.wood = 0;
+        self.stone = 0;
+    }
+
     fn add_new_game_action(
         &mut self,
         game_actions: &mut GameActionHistory,
@@ -311,36 +1323,55 @@ impl BoardAccount {
         &mut self,
         game_actions: &mut GameActionHistory,
         player: Pubkey,
+        player_data: &mut PlayerData,
         avatar: Pubkey,
     ) {
         if self.evil_won || self.good_won {
             return;
         }
 
-        let good_damage: u32 = (self.data[GOOD_POSITION_X][GOOD_POSITION_Y].building_level + 1) * 2;
-        let evil_damage = (self.data[EVIL_POSITION_X][EVIL_POSITION_Y].building_level + 1) * 2;
+        let (good_x, good_y) = self.good_position();
+        let (evil_x, evil_y) = self.evil_position();
 
-        self.data[GOOD_POSITION_X][GOOD_POSITION_Y].building_health -= evil_damage as i64;
-        self.data[EVIL_POSITION_X][EVIL_POSITION_Y].building_health -= good_damage as i64;
+        let good_damage: u32 = (self.data[good_x][good_y].building_level + 1) * 2;
+        let evil_damage = (self.data[evil_x][evil_y].building_level + 1) * 2;
+
+        self.data[good_x][good_y].building_health -= evil_damage as i64;
+        self.data[evil_x][evil_y].building_health -= good_damage as i64;
+
+        // The player who started the fight gets the action and the damage of their faction
+        self.sync_war_score(player_data);
+        let damage = match self.faction_of(player_data) {
+            FACTION_GOOD => good_damage as u64,
+            FACTION_EVIL => evil_damage as u64,
+            _ => 0,
+        };
+        player_data.actions_taken += 1;
+        player_data.damage_dealt += damage;
+        self.add_war_score(player_data, WAR_SCORE_PER_ACTION + damage);
 
-        if self.data[EVIL_POSITION_X][EVIL_POSITION_Y].building_health <= 0 {
+        if self.data[evil_x][evil_y].building_health <= 0 {
             self.good_won = true;
+            self.close_faction_reward(FACTION_GOOD);
             msg!("Good wins");
         }
 
-        if self.data[GOOD_POSITION_X][GOOD_POSITION_Y].building_health <= 0 {
+        if self.data[good_x][good_y].building_health <= 0 {
             self.evil_won = true;
+            if !self.good_won {
+                self.close_faction_reward(FACTION_EVIL);
+            }
             msg!("Evil wins");
         }
 
         let new_game_action = GameAction {
             action_id: self.action_id,
             action_type: ACTION_TYPE_FIGHT,
-            x: EVIL_POSITION_X as u8,
-            y: EVIL_POSITION_Y as u8,
+            x: evil_x as u8,
+            y: evil_y as u8,
             player: player.key(),
             avatar: avatar.key(),
-            tile: self.data[EVIL_POSITION_X][EVIL_POSITION_Y],
+            tile: self.data[evil_x][evil_y],
             amount: good_damage as u64,
         };
 
@@ -349,107 +1380,50 @@ impl BoardAccount {
         let new_game_action = GameAction {
             action_id: self.action_id,
             action_type: ACTION_TYPE_FIGHT,
-            x: GOOD_POSITION_X as u8,
-            y: GOOD_POSITION_Y as u8,
+            x: good_x as u8,
+            y: good_y as u8,
             player: player.key(),
             avatar: avatar.key(),
-            tile: self.data[GOOD_POSITION_X][GOOD_POSITION_Y],
+            tile: self.data[good_x][good_y],
             amount: evil_damage as u64,
         };
         self.add_new_game_action(game_actions, new_game_action);
     }
 }
 
-// Function to calculate the wood upgrade cost of the sawmill based on the building level
-fn calculate_sawmill_wood_upgrade_cost(building_level: u32) -> u64 {
-    const BASE_SAWMILL_WOOD_COST: u32 = 10;
-    const SAWMILL_WOOD_COST_MULTIPLIER: f64 = 1.1;
-
-    let sawmill_wood_cost = (BASE_SAWMILL_WOOD_COST as f64
-        * f64::powf(SAWMILL_WOOD_COST_MULTIPLIER, building_level as f64))
-        as u64;
-    sawmill_wood_cost
-}
-
-// Function to calculate the stone upgrade cost of the sawmill based on the building level
-fn calculate_sawmill_stone_upgrade_cost(building_level: u32) -> u64 {
-    const BASE_SAWMILL_STONE_COST: u32 = 5;
-    const SAWMILL_STONE_COST_MULTIPLIER: f64 = 1.05;
-
-    let sawmill_stone_cost = (BASE_SAWMILL_STONE_COST as f64
-        * f64::powf(SAWMILL_STONE_COST_MULTIPLIER, building_level as f64))
-        as u64;
-    sawmill_stone_cost
-}
-
-fn calculate_stone_mine_wood_upgrade_cost(building_level: u32) -> u64 {
-    const BASE_STONE_MINE_WOOD_COST: u32 = 5;
-    const STONE_MINE_WOOD_COST_MULTIPLIER: f64 = 1.05;
-
-    let stone_mine_wood_cost = (BASE_STONE_MINE_WOOD_COST as f64
-        * f64::powf(STONE_MINE_WOOD_COST_MULTIPLIER, building_level as f64))
-        as u64;
-    stone_mine_wood_cost
-}
-
-fn calculate_stone_mine_stone_upgrade_cost(building_level: u32) -> u64 {
-    const BASE_STONE_MINE_STONE_COST: u32 = 10;
-    const STONE_MINE_STONE_COST_MULTIPLIER: f64 = 1.1;
-
-    let stone_mine_stone_cost = (BASE_STONE_MINE_STONE_COST as f64
-        * f64::powf(STONE_MINE_STONE_COST_MULTIPLIER, building_level as f64))
-        as u64;
-    stone_mine_stone_cost
-}
-
-fn calculate_evil_wood_upgrade_cost(building_level: u32) -> u64 {
-    const BASE_COST: u32 = 15;
-    const COST_MULTIPLIER: f64 = 1.1;
-
-    let final_cost = (BASE_COST as f64 * f64::powf(COST_MULTIPLIER, building_level as f64)) as u64;
-    final_cost
-}
-
-fn calculate_evil_stone_upgrade_cost(building_level: u32) -> u64 {
-    const BASE_COST: u32 = 15;
-    const COST_MULTIPLIER: f64 = 1.1;
-
-    (BASE_COST as f64 * f64::powf(COST_MULTIPLIER, building_level as f64)) as u64
-}
-
-fn calculate_good_wood_upgrade_cost(building_level: u32) -> u64 {
-    const BASE_COST: u32 = 15;
-    const COST_MULTIPLIER: f64 = 1.1;
-
-    (BASE_COST as f64 * f64::powf(COST_MULTIPLIER, building_level as f64)) as u64
-}
-
-fn calculate_good_stone_upgrade_cost(building_level: u32) -> u64 {
-    const BASE_COST: u32 = 15;
-    const COST_MULTIPLIER: f64 = 1.1;
-
-    (BASE_COST as f64 * f64::powf(COST_MULTIPLIER, building_level as f64)) as u64
-}
-
-fn calculate_building_collection(building_level: u32) -> u64 {
-    const BASE_COLLECTION: u32 = 5;
-    const COLLECITON_MULTIPLIER: f64 = 1.1;
-
-    (BASE_COLLECTION as f64 * f64::powf(COLLECITON_MULTIPLIER, building_level as f64)) as u64
-}
-
 #[account(zero_copy(unsafe))]
 #[repr(packed)]
 #[derive(Default)]
 pub struct BoardAccount {
     pub data: [[TileData; BOARD_SIZE_X]; BOARD_SIZE_Y],
     pub action_id: u64,
-    pub wood: u64,         // Global resources, let see how it goes :D
-    pub stone: u64,        // Global resources, let see how it goes :D
+    pub wood: u64,         // Shared treasury, pays the good and evil upgrades
+    pub stone: u64,        // Shared treasury, pays the good and evil upgrades
     pub damm_level: u64,   // Global building level of the mein goal
     pub initialized: bool, // Global building level of the mein goal
     pub evil_won: bool,
     pub good_won: bool,
+    pub game_number: u64, // Increased on every restart, faction memberships are per game
+    pub good_members: u32,
+    pub evil_members: u32,
+    pub reward_game: u64, // Last decided game, its winners can claim the reward pool
+    pub reward_faction: u8,
+    pub reward_members: u32,
+    pub reward_wood: u64,
+    pub reward_stone: u64,
+    pub size_x: u8, // Playable part of the tile array, zero for boards created before
+    pub size_y: u8,
+    pub seed: u64,                                    // Seed of the generated map
+    pub top_contributors: [Pubkey; TOP_CONTRIBUTORS], // Sorted by contribution to the treasury
+    pub top_contributions: [u64; TOP_CONTRIBUTORS],
+    pub good_war_score: u64, // Sum of the war scores of the members
+    pub evil_war_score: u64,
+    pub war_pool: u64, // Lamports in the reward vault for the winners of this game
+    pub reward_war_score: u64, // War score of the winners of the reward game
+    pub reward_war_pool: u64, // Lamports the winners of the reward game share
+    pub reward_war_claimed: u64, // Lamports of the reward war pool paid out so far
+    pub reward_war_game: u64, // Last decided game whose winners earned a war score
+    pub reward_war_faction: u8,
 }
 
 #[zero_copy(unsafe)]
@@ -458,7 +1432,7 @@ pub struct BoardAccount {
 pub struct TileData {
     pub building_type: u8,
     pub building_level: u32,
-    pub building_owner: Pubkey, // Could maybe be the avatar of the player building it? :thinking:
+    pub building_owner: Pubkey, // Player account of the builder, default for unowned buildings
     pub building_start_time: i64,
     pub building_start_upgrade_time: i64,
     pub building_start_collect_time: i64,
@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+
+use crate::state::board::{
+    BUILDING_TYPE_EVIL, BUILDING_TYPE_FARM, BUILDING_TYPE_GOOD, BUILDING_TYPE_HOUSE,
+    BUILDING_TYPE_MINE, BUILDING_TYPE_SAWMILL, BUILDING_TYPE_TOWER, BUILDING_TYPE_WALL,
+    BUILDING_TYPE_WAREHOUSE,
+};
+use crate::state::cost_curve::{CostCurve, MULTIPLIER_ONE};
+
+pub const RESOURCE_NONE: u8 = 0;
+pub const RESOURCE_WOOD: u8 = 1;
+pub const RESOURCE_STONE: u8 = 2;
+pub const RESOURCE_FOOD: u8 = 3;
+
+// Production of the definitions is given per this many seconds
+pub const PRODUCTION_TIME: i64 = 60;
+
+pub struct BuildingDefinition {
+    pub buildable: bool, // the good and evil buildings are placed by the game
+    pub build_cost_wood: u64,
+    pub build_cost_stone: u64,
+    pub upgrade_cost_wood: CostCurve,
+    pub upgrade_cost_stone: CostCurve,
+    pub upgrade_time: CostCurve, // seconds until the next level is reached, zero is instant
+    pub production_resource: u8,
+    pub production: CostCurve,  // produced every PRODUCTION_TIME
+    pub storage: CostCurve,     // most a building holds before it has to be collected
+    pub energy_per_level: u64,  // max energy added to the owner for every level
+    pub storage_per_level: u64, // resource storage added to the owner for every level
+    pub health: CostCurve,      // raids destroy the building once this much damage is dealt
+    pub defense: CostCurve,     // percent less raid damage for buildings of the owner in range
+    pub defense_range: u8,      // tiles in every direction, zero for buildings without defense
+}
+
+const ZERO: CostCurve = CostCurve::new(0, MULTIPLIER_ONE);
+
+const SAWMILL: BuildingDefinition = BuildingDefinition {
+    buildable: true,
+    build_cost_wood: 0,
+    build_cost_stone: 15,
+    upgrade_cost_wood: CostCurve::new(10, 11_000),
+    upgrade_cost_stone: CostCurve::new(5, 10_500),
+    upgrade_time: CostCurve::new(30, 15_000),
+    production_resource: RESOURCE_WOOD,
+    production: CostCurve::new(5, 11_000),
+    storage: CostCurve::new(100, 12_000),
+    energy_per_level: 0,
+    storage_per_level: 0,
+    health: CostCurve::new(100, 11_000),
+    defense: ZERO,
+    defense_range: 0,
+};
+
+const MINE: BuildingDefinition = BuildingDefinition {
+    buildable: true,
+    build_cost_wood: 15,
+    build_cost_stone: 0,
+    upgrade_cost_wood: CostCurve::new(5, 10_500),
+    upgrade_cost_stone: CostCurve::new(10, 11_000),
+    upgrade_time: CostCurve::new(30, 15_000),
+    production_resource: RESOURCE_STONE,
+    production: CostCurve::new(5, 11_000),
+    storage: CostCurve::new(100, 12_000),
+    energy_per_level: 0,
+    storage_per_level: 0,
+    health: CostCurve::new(100, 11_000),
+    defense: ZERO,
+    defense_range: 0,
+};
+
+const FACTION_BUILDING: BuildingDefinition = BuildingDefinition {
+    buildable: false,
+    build_cost_wood: 0,
+    build_cost_stone: 0,
+    upgrade_cost_wood: CostCurve::new(15, 11_000),
+    upgrade_cost_stone: CostCurve::new(15, 11_000),
+    upgrade_time: ZERO,
+    production_resource: RESOURCE_NONE,
+    production: ZERO,
+    storage: ZERO,
+    energy_per_level: 0,
+    storage_per_level: 0,
+    health: ZERO,
+    defense: ZERO,
+    defense_range: 0,
+};
+
+const HOUSE: BuildingDefinition = BuildingDefinition {
+    buildable: true,
+    build_cost_wood: 20,
+    build_cost_stone: 10,
+    upgrade_cost_wood: CostCurve::new(15, 12_000),
+    upgrade_cost_stone: CostCurve::new(10, 12_000),
+    upgrade_time: CostCurve::new(60, 15_000),
+    production_resource: RESOURCE_NONE,
+    production: ZERO,
+    storage: ZERO,
+    energy_per_level: 2,
+    storage_per_level: 0,
+    health: CostCurve::new(150, 11_000),
+    defense: ZERO,
+    defense_range: 0,
+};
+
+const FARM: BuildingDefinition = BuildingDefinition {
+    buildable: true,
+    build_cost_wood: 15,
+    build_cost_stone: 5,
+    upgrade_cost_wood: CostCurve::new(10, 11_000),
+    upgrade_cost_stone: CostCurve::new(5, 11_000),
+    upgrade_time: CostCurve::new(30, 15_000),
+    production_resource: RESOURCE_FOOD,
+    production: CostCurve::new(5, 11_000),
+    storage: CostCurve::new(100, 12_000),
+    energy_per_level: 0,
+    storage_per_level: 0,
+    health: CostCurve::new(100, 11_000),
+    defense: ZERO,
+    defense_range: 0,
+};
+
+const WAREHOUSE: BuildingDefinition = BuildingDefinition {
+    buildable: true,
+    build_cost_wood: 25,
+    build_cost_stone: 25,
+    upgrade_cost_wood: CostCurve::new(20, 11_500),
+    upgrade_cost_stone: CostCurve::new(20, 11_500),
+    upgrade_time: CostCurve::new(60, 15_000),
+    production_resource: RESOURCE_NONE,
+    production: ZERO,
+    storage: ZERO,
+    energy_per_level: 0,
+    storage_per_level: 250,
+    health: CostCurve::new(150, 11_000),
+    defense: ZERO,
+    defense_range: 0,
+};
+
+const WALL: BuildingDefinition = BuildingDefinition {
+    buildable: true,
+    build_cost_wood: 10,
+    build_cost_stone: 20,
+    upgrade_cost_wood: CostCurve::new(10, 11_500),
+    upgrade_cost_stone: CostCurve::new(15, 11_500),
+    upgrade_time: CostCurve::new(30, 15_000),
+    production_resource: RESOURCE_NONE,
+    production: ZERO,
+    storage: ZERO,
+    energy_per_level: 0,
+    storage_per_level: 0,
+    health: CostCurve::new(300, 12_000),
+    defense: CostCurve::new(10, 11_000),
+    defense_range: 1,
+};
+
+const TOWER: BuildingDefinition = BuildingDefinition {
+    buildable: true,
+    build_cost_wood: 20,
+    build_cost_stone: 30,
+    upgrade_cost_wood: CostCurve::new(15, 11_500),
+    upgrade_cost_stone: CostCurve::new(20, 11_500),
+    upgrade_time: CostCurve::new(60, 15_000),
+    production_resource: RESOURCE_NONE,
+    production: ZERO,
+    storage: ZERO,
+    energy_per_level: 0,
+    storage_per_level: 0,
+    health: CostCurve::new(200, 12_000),
+    defense: CostCurve::new(15, 11_000),
+    defense_range: 2,
+};
+
+impl BuildingDefinition {
+    /// Trees, empty tiles and the other map tiles have no definition
+    pub fn get(building_type: u8) -> Option<&'static BuildingDefinition> {
+        match building_type {
+            BUILDING_TYPE_SAWMILL => Some(&SAWMILL),
+            BUILDING_TYPE_MINE => Some(&MINE),
+            BUILDING_TYPE_GOOD | BUILDING_TYPE_EVIL => Some(&FACTION_BUILDING),
+            BUILDING_TYPE_HOUSE => Some(&HOUSE),
+            BUILDING_TYPE_FARM => Some(&FARM),
+            BUILDING_TYPE_WAREHOUSE => Some(&WAREHOUSE),
+            BUILDING_TYPE_WALL => Some(&WALL),
+            BUILDING_TYPE_TOWER => Some(&TOWER),
+            _ => None,
+        }
+    }
+
+    /// Wood and stone needed to upgrade from the given level
+    pub fn upgrade_cost(&self, building_level: u32) -> Result<(u64, u64)> {
+        Ok((
+            self.upgrade_cost_wood.at(building_level)?,
+            self.upgrade_cost_stone.at(building_level)?,
+        ))
+    }
+
+    /// Production accrued over the given seconds, capped by the storage of the level
+    pub fn accrued(&self, building_level: u32, seconds: i64) -> Result<u64> {
+        let produced = self.production.at(building_level)? as u128 * seconds.max(0) as u128
+            / PRODUCTION_TIME as u128;
+        Ok(produced.min(self.storage.at(building_level)? as u128) as u64)
+    }
+
+    /// Seconds of production it takes to accrue the amount, the inverse of accrued
+    pub fn production_seconds(&self, building_level: u32, amount: u64) -> Result<i64> {
+        let production = self.production.at(building_level)?;
+        if production == 0 {
+            return Ok(0);
+        }
+        Ok((amount as u128 * PRODUCTION_TIME as u128 / production as u128) as i64)
+    }
+}
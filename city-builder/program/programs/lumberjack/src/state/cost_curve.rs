@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+
+use crate::GameErrorCode;
+
+/// Fixed-point scale of the multipliers, 10_000 is a multiplier of 1.0 and 11_000 one of 1.1
+pub const MULTIPLIER_ONE: u64 = 10_000;
+
+/// Levels above this are never reached in a game, `checked_at` treats them like an overflow
+pub const MAX_CURVE_LEVEL: u32 = 1_000;
+
+/// base * (multiplier / MULTIPLIER_ONE) ^ level
+///
+/// Evaluated with integer math only, so the program and all clients get exactly the same value.
+/// The power is taken by squaring, so the cost does not grow with the level. Clients reproduce
+/// it like this, all divisions rounded down:
+///
+/// ```text
+/// value = base * MULTIPLIER_ONE
+/// factor = multiplier
+/// for every bit of level, lowest first:
+///     if the bit is set: value = value * factor / MULTIPLIER_ONE
+///     if higher bits are left: factor = factor * factor / MULTIPLIER_ONE
+/// result = value / MULTIPLIER_ONE
+/// ```
+///
+/// The intermediate values need 128 bits, the result has to fit into a u64.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CostCurve {
+    pub base: u64,
+    pub multiplier: u64,
+}
+
+impl CostCurve {
+    pub const fn new(base: u64, multiplier: u64) -> Self {
+        Self { base, multiplier }
+    }
+
+    /// None if the value does not fit into a u64 or the level is above MAX_CURVE_LEVEL
+    pub fn checked_at(&self, level: u32) -> Option<u64> {
+        if level > MAX_CURVE_LEVEL {
+            return None;
+        }
+        if self.base == 0 {
+            return Some(0);
+        }
+        let one = MULTIPLIER_ONE as u128;
+        let max = u64::MAX as u128 * one;
+        let mut value = self.base as u128 * one;
+        let mut factor = self.multiplier as u128;
+        let mut exponent = level;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                value = value.checked_mul(factor)? / one;
+                if value > max {
+                    return None;
+                }
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                // The factor is still applied to the value, which is at least MULTIPLIER_ONE
+                // while the factor grows, so if the factor no longer fits neither does the result
+                factor = factor.checked_mul(factor)? / one;
+            }
+        }
+        Some((value / one) as u64)
+    }
+
+    pub fn at(&self, level: u32) -> Result<u64> {
+        match self.checked_at(level) {
+            Some(value) => Ok(value),
+            None => err!(GameErrorCode::CostOverflow),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::board::{
+        BUILDING_TYPE_FARM, BUILDING_TYPE_GOOD, BUILDING_TYPE_HOUSE, BUILDING_TYPE_MINE,
+        BUILDING_TYPE_SAWMILL, BUILDING_TYPE_TOWER, BUILDING_TYPE_WALL, BUILDING_TYPE_WAREHOUSE,
+    };
+    use crate::state::building::BuildingDefinition;
+
+    // Levels at which the curves are pinned, 25 is past the point where rounding
+    // differs from multiplying level by level
+    const LEVELS: [u32; 5] = [0, 1, 2, 10, 25];
+
+    fn curves(definition: &BuildingDefinition) -> [[u64; 5]; 7] {
+        [
+            definition.upgrade_cost_wood,
+            definition.upgrade_cost_stone,
+            definition.upgrade_time,
+            definition.production,
+            definition.storage,
+            definition.health,
+            definition.defense,
+        ]
+        .map(|curve| LEVELS.map(|level| curve.at(level).unwrap()))
+    }
+
+    #[test]
+    fn shipped_definitions() {
+        let expected = [
+            (
+                BUILDING_TYPE_SAWMILL,
+                [
+                    [10, 11, 12, 25, 108],
+                    [5, 5, 5, 8, 16],
+                    [30, 45, 67, 1729, 757534],
+                    [5, 5, 6, 12, 54],
+                    [100, 120, 144, 619, 9539],
+                    [100, 110, 121, 259, 1083],
+                    [0, 0, 0, 0, 0],
+                ],
+            ),
+            (
+                BUILDING_TYPE_MINE,
+                [
+                    [5, 5, 5, 8, 16],
+                    [10, 11, 12, 25, 108],
+                    [30, 45, 67, 1729, 757534],
+                    [5, 5, 6, 12, 54],
+                    [100, 120, 144, 619, 9539],
+                    [100, 110, 121, 259, 1083],
+                    [0, 0, 0, 0, 0],
+                ],
+            ),
+            (
+                BUILDING_TYPE_GOOD,
+                [
+                    [15, 16, 18, 38, 162],
+                    [15, 16, 18, 38, 162],
+                    [0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0],
+                ],
+            ),
+            (
+                BUILDING_TYPE_HOUSE,
+                [
+                    [15, 18, 21, 92, 1430],
+                    [10, 12, 14, 61, 953],
+                    [60, 90, 135, 3459, 1515068],
+                    [0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0],
+                    [150, 165, 181, 389, 1624],
+                    [0, 0, 0, 0, 0],
+                ],
+            ),
+            (
+                BUILDING_TYPE_FARM,
+                [
+                    [10, 11, 12, 25, 108],
+                    [5, 5, 6, 12, 54],
+                    [30, 45, 67, 1729, 757534],
+                    [5, 5, 6, 12, 54],
+                    [100, 120, 144, 619, 9539],
+                    [100, 110, 121, 259, 1083],
+                    [0, 0, 0, 0, 0],
+                ],
+            ),
+            (
+                BUILDING_TYPE_WAREHOUSE,
+                [
+                    [20, 23, 26, 80, 658],
+                    [20, 23, 26, 80, 658],
+                    [60, 90, 135, 3459, 1515068],
+                    [0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0],
+                    [150, 165, 181, 389, 1624],
+                    [0, 0, 0, 0, 0],
+                ],
+            ),
+            (
+                BUILDING_TYPE_WALL,
+                [
+                    [10, 11, 13, 40, 329],
+                    [15, 17, 19, 60, 493],
+                    [30, 45, 67, 1729, 757534],
+                    [0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0],
+                    [300, 360, 432, 1857, 28618],
+                    [10, 11, 12, 25, 108],
+                ],
+            ),
+            (
+                BUILDING_TYPE_TOWER,
+                [
+                    [15, 17, 19, 60, 493],
+                    [20, 23, 26, 80, 658],
+                    [60, 90, 135, 3459, 1515068],
+                    [0, 0, 0, 0, 0],
+                    [0, 0, 0, 0, 0],
+                    [200, 240, 288, 1238, 19078],
+                    [15, 16, 18, 38, 162],
+                ],
+            ),
+        ];
+        for (building_type, curves_at_levels) in expected {
+            let definition = BuildingDefinition::get(building_type).unwrap();
+            assert_eq!(
+                curves(definition),
+                curves_at_levels,
+                "building {}",
+                building_type
+            );
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        let curve = CostCurve::new(u64::MAX, 11_000);
+        assert_eq!(curve.checked_at(0), Some(u64::MAX));
+        assert_eq!(curve.checked_at(1), None);
+        assert!(curve.at(1).is_err());
+
+        // 30 * 1.5^101 still fits, one more level does not
+        let curve = CostCurve::new(30, 15_000);
+        assert_eq!(curve.checked_at(101), Some(18_295_196_903_211_002_395));
+        assert_eq!(curve.checked_at(102), None);
+        assert_eq!(curve.checked_at(MAX_CURVE_LEVEL), None);
+
+        // The factor overflows while squaring before the value does
+        assert_eq!(CostCurve::new(1, u64::MAX).checked_at(3), None);
+    }
+
+    #[test]
+    fn max_curve_level() {
+        let flat = CostCurve::new(7, MULTIPLIER_ONE);
+        assert_eq!(flat.checked_at(MAX_CURVE_LEVEL), Some(7));
+        assert_eq!(flat.checked_at(MAX_CURVE_LEVEL + 1), None);
+        assert_eq!(ZERO_CURVE.checked_at(MAX_CURVE_LEVEL), Some(0));
+        assert_eq!(ZERO_CURVE.checked_at(u32::MAX), None);
+
+        let decay = CostCurve::new(1_000_000, 5_000);
+        assert_eq!(decay.checked_at(3), Some(125_000));
+        assert_eq!(decay.checked_at(MAX_CURVE_LEVEL), Some(0));
+    }
+
+    const ZERO_CURVE: CostCurve = CostCurve::new(0, 20_000);
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{ENERGY_REFILL_FEE, ENERGY_REFILL_STONE, MAX_ENERGY, TREASURY_PUBKEY, WAR_POOL_SHARE};
+
+/// Settings of the game and the current season. Created with the defaults from lib.rs by
+/// `init_player` or `restart_game`, the admin tunes the settings with `update_game_config`.
+#[account]
+pub struct GameConfig {
+    pub treasury: Pubkey,         // receives the SOL paid for energy refills
+    pub energy_refill_fee: u64,   // lamports per refilled energy point
+    pub energy_refill_stone: u64, // stone per refilled energy point, zero disables stone refills
+    pub max_energy: u64,          // max energy of every player before the bonus of houses
+    pub season: u64,              // the board of the season is seeded with this number
+    pub war_pool_share: u64,      // basis points of every SOL refill which go to the war pool
+}
+
+impl GameConfig {
+    pub const SPACE: usize = 8 + 32 + 5 * 8;
+
+    /// The treasury is never the default key once the config has been written
+    pub fn is_initialized(&self) -> bool {
+        self.treasury != Pubkey::default()
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            treasury: TREASURY_PUBKEY,
+            energy_refill_fee: ENERGY_REFILL_FEE,
+            energy_refill_stone: ENERGY_REFILL_STONE,
+            max_energy: MAX_ENERGY,
+            season: 0,
+            war_pool_share: WAR_POOL_SHARE,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::state::board::{
+    BUILDING_TYPE_EMPTY, BUILDING_TYPE_STONE_DEPOSIT, BUILDING_TYPE_TREE, BUILDING_TYPE_WATER,
+};
+
+// Chances in percent for each generated tile, the rest of the tiles stay empty
+const TREE_CHANCE: u64 = 45;
+const STONE_DEPOSIT_CHANCE: u64 = 15;
+const WATER_CHANCE: u64 = 10;
+
+/// Deterministic map generator, the same seed always results in the same board layout so
+/// clients can verify the map of a round.
+pub struct MapGenerator {
+    state: u64,
+}
+
+impl MapGenerator {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on zero
+        let state = if seed == 0 {
+            0x9E37_79B9_7F4A_7C15
+        } else {
+            seed
+        };
+        Self { state }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    pub fn next_tile(&mut self) -> u8 {
+        let roll = self.next() % 100;
+        if roll < TREE_CHANCE {
+            BUILDING_TYPE_TREE
+        } else if roll < TREE_CHANCE + STONE_DEPOSIT_CHANCE {
+            BUILDING_TYPE_STONE_DEPOSIT
+        } else if roll < TREE_CHANCE + STONE_DEPOSIT_CHANCE + WATER_CHANCE {
+            BUILDING_TYPE_WATER
+        } else {
+            BUILDING_TYPE_EMPTY
+        }
+    }
+}
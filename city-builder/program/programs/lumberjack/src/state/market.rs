@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::state::building::{RESOURCE_STONE, RESOURCE_WOOD};
+use crate::GameErrorCode;
+
+/// Orders can ask for lamports, they are paid to the wallet of the maker
+pub const RESOURCE_SOL: u8 = 4;
+
+/// An offer of wood or stone on the market. The offered resources are taken from the maker
+/// when the order is placed and held by the order until it is filled or cancelled.
+#[account]
+pub struct MarketOrder {
+    pub maker: Pubkey,     // PlayerData account of the player who placed the order
+    pub authority: Pubkey, // wallet of the maker, receives SOL payments and the rent
+    pub order_id: u64,
+    pub sell_resource: u8,
+    pub sell_amount: u64, // left to be filled
+    pub buy_resource: u8,
+    pub buy_amount: u64, // still to be paid for the rest of the order
+    pub created_at: i64,
+}
+
+impl MarketOrder {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1 + 8 + 1 + 8 + 8;
+
+    pub fn is_tradable(resource: u8) -> bool {
+        resource == RESOURCE_WOOD || resource == RESOURCE_STONE
+    }
+
+    pub fn check(
+        sell_resource: u8,
+        sell_amount: u64,
+        buy_resource: u8,
+        buy_amount: u64,
+    ) -> Result<()> {
+        if !Self::is_tradable(sell_resource)
+            || !(Self::is_tradable(buy_resource) || buy_resource == RESOURCE_SOL)
+            || sell_resource == buy_resource
+            || sell_amount == 0
+            || buy_amount == 0
+        {
+            return err!(GameErrorCode::InvalidOrder);
+        }
+        Ok(())
+    }
+
+    /// Price of part of the order at the price of the whole order, rounded up so filling an
+    /// order in many small parts never costs less than filling it at once
+    pub fn fill_cost(&self, amount: u64) -> Result<u64> {
+        if amount == 0 || amount > self.sell_amount {
+            return err!(GameErrorCode::InvalidFillAmount);
+        }
+        if amount == self.sell_amount {
+            return Ok(self.buy_amount);
+        }
+        let cost = (amount as u128 * self.buy_amount as u128 + self.sell_amount as u128 - 1)
+            / self.sell_amount as u128;
+        // Nothing would be left to pay for the rest, so only the whole order can be filled
+        if cost >= self.buy_amount as u128 {
+            return err!(GameErrorCode::InvalidFillAmount);
+        }
+        Ok(cost as u64)
+    }
+
+    /// Takes the amount out of the order and returns what the taker pays for it
+    pub fn fill(&mut self, amount: u64) -> Result<u64> {
+        let cost = self.fill_cost(amount)?;
+        self.sell_amount -= amount;
+        self.buy_amount -= cost;
+        Ok(cost)
+    }
+
+    /// Takes the amount out of the order for the maker. The rest keeps the price of the order,
+    /// rounded up like a partial fill.
+    pub fn cancel(&mut self, amount: u64) {
+        let left = self.sell_amount - amount.min(self.sell_amount);
+        self.buy_amount = ((left as u128 * self.buy_amount as u128 + self.sell_amount as u128 - 1)
+            / self.sell_amount as u128) as u64;
+        self.sell_amount = left;
+    }
+
+    pub fn is_filled(&self) -> bool {
+        self.sell_amount == 0
+    }
+}
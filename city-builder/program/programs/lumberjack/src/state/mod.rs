@@ -1,3 +1,17 @@
 pub use board::*;
+pub use building::*;
+pub use cost_curve::*;
+pub use game_config::*;
+pub use map::*;
+pub use market::*;
+pub use reward_vault::*;
+pub use season::*;
 
 pub mod board;
+pub mod building;
+pub mod cost_curve;
+pub mod game_config;
+pub mod map;
+pub mod market;
+pub mod reward_vault;
+pub mod season;
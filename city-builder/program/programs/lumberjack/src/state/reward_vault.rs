@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+/// Holds the lamports of the war pools. Its share of the SOL energy refills is added by
+/// `refill_energy`, the winners of a game take it out with `claim_war_reward`.
+#[account]
+pub struct RewardVault {}
+
+impl RewardVault {
+    pub const SPACE: usize = 8;
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::state::board::{BoardAccount, TOP_CONTRIBUTORS};
+
+/// Outcome of a finished season. Written by `restart_game` before the board of the next season
+/// is created, the board of the finished season is kept as it was.
+#[account]
+pub struct SeasonResult {
+    pub season: u64,
+    pub board: Pubkey, // Board account of the season
+    pub game_number: u64,
+    pub winner: u8,                                   // FACTION_GOOD or FACTION_EVIL
+    pub good_level: u32,                              // Final level of the good building
+    pub evil_level: u32,                              // Final level of the evil building
+    pub good_members: u32,                            // Players which joined the good faction
+    pub evil_members: u32,                            // Players which joined the evil faction
+    pub top_contributors: [Pubkey; TOP_CONTRIBUTORS], // Largest contributors to the treasury
+    pub top_contributions: [u64; TOP_CONTRIBUTORS],   // Wood and stone of each top contributor
+    pub archived_at: i64,
+}
+
+impl SeasonResult {
+    pub const SPACE: usize = 8 + 8 + 32 + 8 + 1 + 4 * 4 + (32 + 8) * TOP_CONTRIBUTORS + 8;
+
+    pub fn new(season: u64, board_key: Pubkey, board: &BoardAccount, archived_at: i64) -> Self {
+        let (good_level, evil_level) = board.faction_building_levels();
+        SeasonResult {
+            season,
+            board: board_key,
+            game_number: board.game_number,
+            winner: board.winner(),
+            good_level,
+            evil_level,
+            good_members: board.good_members,
+            evil_members: board.evil_members,
+            top_contributors: board.top_contributors,
+            top_contributions: board.top_contributions,
+            archived_at,
+        }
+    }
+}
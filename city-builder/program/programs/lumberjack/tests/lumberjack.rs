@@ -0,0 +1,1889 @@
+//! In process integration tests using solana-program-test. They run offline, no
+//! validator or devnet wallet needed: `cargo test`
+//!
+//! The wallet of the player signs every action, session keys are not used. Boards and player
+//! accounts are written directly, so each test starts from the state it needs.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, sysvar::clock::Clock};
+use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator, InstructionData};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use lumberjack::{
+    BoardAccount, BuildingDefinition, GameConfig, GameErrorCode, MapGenerator, MarketOrder,
+    PlayerData, SeasonResult, BUILDING_TYPE_EMPTY, BUILDING_TYPE_EVIL, BUILDING_TYPE_FARM,
+    BUILDING_TYPE_GOOD, BUILDING_TYPE_HOUSE, BUILDING_TYPE_SAPLING, BUILDING_TYPE_SAWMILL,
+    BUILDING_TYPE_TREE, BUILDING_TYPE_WALL, BUILDING_TYPE_WAREHOUSE, BUILDING_TYPE_WATER,
+    FACTION_EVIL, FACTION_GOOD, RESOURCE_FOOD, RESOURCE_SOL, RESOURCE_STONE, RESOURCE_WOOD,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use std::time::Duration;
+
+// Anchor's entry wants the same lifetime for the slice and the accounts, program-test
+// hands out different ones. Leaking the accounts is fine for a test process.
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    lumberjack::entry(program_id, accounts, data)
+}
+
+struct TestGame {
+    context: ProgramTestContext,
+    season: u64, // The board actions are sent to the board of this season
+}
+
+// BUILDING_START_HEALTH of the good and evil buildings
+const FACTION_BUILDING_HEALTH: i64 = 9000;
+
+fn player_pda(wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"player", wallet.as_ref()], &lumberjack::ID).0
+}
+
+fn board_pda(season: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"board", &season.to_le_bytes()], &lumberjack::ID).0
+}
+
+fn game_config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"gameConfig"], &lumberjack::ID).0
+}
+
+fn game_actions_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"gameActions"], &lumberjack::ID).0
+}
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: lumberjack::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn board_action(game: &TestGame, wallet: &Keypair, data: impl InstructionData) -> Instruction {
+    instruction(
+        lumberjack::accounts::BoardAction {
+            session_token: None,
+            board: board_pda(game.season),
+            game_actions: game_actions_pda(),
+            avatar: wallet.pubkey(),
+            player: player_pda(&wallet.pubkey()),
+            game_config: game_config_pda(),
+            signer: wallet.pubkey(),
+        },
+        data,
+    )
+}
+
+// Buildings of other players need the player account of the owner as remaining account
+fn on_building_of(mut instruction: Instruction, owner: &Keypair) -> Instruction {
+    instruction
+        .accounts
+        .push(AccountMeta::new(player_pda(&owner.pubkey()), false));
+    instruction
+}
+
+async fn setup() -> TestGame {
+    let mut program_test = ProgramTest::new(
+        "lumberjack",
+        lumberjack::ID,
+        processor!(process_instruction),
+    );
+    // The bundled token program shrinks closed accounts, which native CPIs can't copy back.
+    // The native build of spl-token only zeroes them.
+    program_test.add_program(
+        "spl_token",
+        spl_token::ID,
+        processor!(spl_token::processor::Processor::process),
+    );
+    TestGame {
+        context: program_test.start_with_context().await,
+        season: 0,
+    }
+}
+
+// solana 1.14 has no get_new_latest_blockhash. The bank registers a new blockhash every slot,
+// waiting for it keeps identical transactions in a row apart.
+async fn new_blockhash(context: &mut ProgramTestContext) -> Hash {
+    loop {
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        if blockhash != context.last_blockhash {
+            context.last_blockhash = blockhash;
+            return blockhash;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+async fn send(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> std::result::Result<(), BanksClientError> {
+    let blockhash = new_blockhash(context).await;
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Sends a board action signed by the wallet of the player
+async fn act(
+    game: &mut TestGame,
+    wallet: &Keypair,
+    instruction: Instruction,
+) -> std::result::Result<(), BanksClientError> {
+    send(&mut game.context, &[instruction], &[wallet]).await
+}
+
+/// Game errors and anchor's own errors, e.g. of the account constraints
+fn assert_error(
+    result: std::result::Result<(), BanksClientError>,
+    error: impl Into<u32> + std::fmt::Debug,
+) {
+    let expected = format!("{:?}", error);
+    match result.expect_err("the transaction should fail").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, error.into(), "expected {}", expected)
+        }
+        other => panic!("expected {}, got {:?}", expected, other),
+    }
+}
+
+async fn account(context: &mut ProgramTestContext, address: Pubkey) -> Account {
+    context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("account not found")
+}
+
+fn funded_keypair(context: &mut ProgramTestContext, keypair: Keypair) -> Keypair {
+    context.set_account(
+        &keypair.pubkey(),
+        &AccountSharedData::new(LAMPORTS_PER_SOL, 0, &solana_sdk::system_program::ID),
+    );
+    keypair
+}
+
+/// Creates a funded wallet and its player account. The first player also creates the game
+/// config, the board of the season and the game actions.
+async fn new_player(game: &mut TestGame) -> Keypair {
+    let wallet = funded_keypair(&mut game.context, Keypair::new());
+    let init_player = instruction(
+        lumberjack::accounts::InitPlayer {
+            player: player_pda(&wallet.pubkey()),
+            game_config: game_config_pda(),
+            board: board_pda(game.season),
+            game_actions: game_actions_pda(),
+            signer: wallet.pubkey(),
+            system_program: solana_sdk::system_program::ID,
+        },
+        lumberjack::instruction::InitPlayer {},
+    );
+    act(game, &wallet, init_player).await.unwrap();
+    wallet
+}
+
+async fn player(game: &mut TestGame, wallet: &Keypair) -> PlayerData {
+    let data = account(&mut game.context, player_pda(&wallet.pubkey()))
+        .await
+        .data;
+    PlayerData::try_deserialize(&mut data.as_slice()).unwrap()
+}
+
+/// Changes the player account of the wallet, e.g. to hand out resources
+async fn edit_player(game: &mut TestGame, wallet: &Keypair, edit: impl FnOnce(&mut PlayerData)) {
+    let address = player_pda(&wallet.pubkey());
+    let mut player_account = account(&mut game.context, address).await;
+    let mut player_data = PlayerData::try_deserialize(&mut player_account.data.as_slice()).unwrap();
+    edit(&mut player_data);
+    let mut writer: &mut [u8] = &mut player_account.data;
+    player_data.try_serialize(&mut writer).unwrap();
+    game.context.set_account(&address, &player_account.into());
+}
+
+async fn board(game: &mut TestGame) -> BoardAccount {
+    let data = account(&mut game.context, board_pda(game.season))
+        .await
+        .data;
+    *bytemuck::from_bytes(&data[8..8 + std::mem::size_of::<BoardAccount>()])
+}
+
+async fn set_board(game: &mut TestGame, board: &BoardAccount) {
+    let address = board_pda(game.season);
+    let mut board_account = account(&mut game.context, address).await;
+    board_account.data[8..8 + std::mem::size_of::<BoardAccount>()]
+        .copy_from_slice(bytemuck::bytes_of(board));
+    game.context.set_account(&address, &board_account.into());
+}
+
+/// Started 10x10 board of the first game with only the good and evil buildings on it
+fn empty_board() -> BoardAccount {
+    let mut board = BoardAccount::default();
+    for column in board.data.iter_mut() {
+        for tile in column.iter_mut() {
+            tile.building_type = BUILDING_TYPE_EMPTY;
+        }
+    }
+    board.initialized = true;
+    board.game_number = 1;
+    board.size_x = 10;
+    board.size_y = 10;
+    let (good_x, good_y) = board.good_position();
+    board.data[good_x][good_y].building_type = BUILDING_TYPE_GOOD;
+    board.data[good_x][good_y].building_health = FACTION_BUILDING_HEALTH;
+    let (evil_x, evil_y) = board.evil_position();
+    board.data[evil_x][evil_y].building_type = BUILDING_TYPE_EVIL;
+    board.data[evil_x][evil_y].building_health = FACTION_BUILDING_HEALTH;
+    board
+}
+
+/// A game with an empty board and a player
+async fn started_game() -> (TestGame, Keypair) {
+    let mut game = setup().await;
+    let wallet = new_player(&mut game).await;
+    set_board(&mut game, &empty_board()).await;
+    (game, wallet)
+}
+
+/// The admin key checked in next to Anchor.toml, see ADMIN_PUBKEY
+fn admin(context: &mut ProgramTestContext) -> Keypair {
+    let keypair = read_keypair_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../YNxhutfSA9JqhA2HEaPmGZ7LErJMMquE9Twbr3x3Hrr.json"
+    ))
+    .unwrap();
+    assert_eq!(keypair.pubkey(), lumberjack::ADMIN_PUBKEY);
+    funded_keypair(context, keypair)
+}
+
+fn update_game_config(
+    signer: &Keypair,
+    treasury: Pubkey,
+    energy_refill_fee: u64,
+    energy_refill_stone: u64,
+    max_energy: u64,
+    war_pool_share: u64,
+) -> Instruction {
+    instruction(
+        lumberjack::accounts::UpdateGameConfig {
+            signer: signer.pubkey(),
+            game_config: game_config_pda(),
+            system_program: solana_sdk::system_program::ID,
+        },
+        lumberjack::instruction::UpdateGameConfig {
+            treasury,
+            energy_refill_fee,
+            energy_refill_stone,
+            max_energy,
+            war_pool_share,
+        },
+    )
+}
+
+fn refill_energy(game: &TestGame, wallet: &Keypair, treasury: Pubkey, amount: u64) -> Instruction {
+    instruction(
+        lumberjack::accounts::RefillEnergyAccounts {
+            player: player_pda(&wallet.pubkey()),
+            game_config: game_config_pda(),
+            signer: wallet.pubkey(),
+            treasury,
+            board: board_pda(game.season),
+            reward_vault: reward_vault_pda(),
+            system_program: solana_sdk::system_program::ID,
+        },
+        lumberjack::instruction::RefillEnergy { amount },
+    )
+}
+
+fn claim_war_reward(game: &TestGame, wallet: &Keypair) -> Instruction {
+    instruction(
+        lumberjack::accounts::ClaimWarReward {
+            session_token: None,
+            board: board_pda(game.season),
+            game_actions: game_actions_pda(),
+            avatar: wallet.pubkey(),
+            player: player_pda(&wallet.pubkey()),
+            game_config: game_config_pda(),
+            reward_vault: reward_vault_pda(),
+            authority: wallet.pubkey(),
+            signer: wallet.pubkey(),
+        },
+        lumberjack::instruction::ClaimWarReward {},
+    )
+}
+
+/// Pays the refill of the missing energy, half of it goes to the war pool
+async fn refill_missing_energy(game: &mut TestGame, wallet: &Keypair, missing: u64) {
+    edit_player(game, wallet, |player| {
+        player.energy = lumberjack::MAX_ENERGY - missing
+    })
+    .await;
+    let refill = refill_energy(game, wallet, lumberjack::TREASURY_PUBKEY, missing);
+    act(game, wallet, refill).await.unwrap();
+}
+
+async fn join(game: &mut TestGame, wallet: &Keypair, faction: u8) {
+    let join = board_action(
+        game,
+        wallet,
+        lumberjack::instruction::JoinFaction { faction },
+    );
+    act(game, wallet, join).await.unwrap();
+}
+
+async fn contribute(game: &mut TestGame, wallet: &Keypair, wood: u64, stone: u64) {
+    edit_player(game, wallet, |player| {
+        player.wood += wood;
+        player.stone += stone;
+    })
+    .await;
+    let contribute = board_action(
+        game,
+        wallet,
+        lumberjack::instruction::Contribute { wood, stone },
+    );
+    act(game, wallet, contribute).await.unwrap();
+}
+
+/// Plants a tree at 0, 0 and leaves the building of the faction so little health that the
+/// next fight, started by chopping the tree, ends the game
+async fn prepare_last_fight(game: &mut TestGame, losing_faction: u8, health: i64) {
+    let mut fixture = board(game).await;
+    fixture.data[0][0].building_type = BUILDING_TYPE_TREE;
+    let (x, y) = if losing_faction == FACTION_GOOD {
+        fixture.good_position()
+    } else {
+        fixture.evil_position()
+    };
+    fixture.data[x][y].building_health = health;
+    set_board(game, &fixture).await;
+}
+
+async fn lamports(game: &mut TestGame, address: Pubkey) -> u64 {
+    game.context
+        .banks_client
+        .get_balance(address)
+        .await
+        .unwrap()
+}
+
+fn order_pda(maker: &Keypair, order_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"order",
+            player_pda(&maker.pubkey()).as_ref(),
+            &order_id.to_le_bytes(),
+        ],
+        &lumberjack::ID,
+    )
+    .0
+}
+
+/// Places the next order of the maker, the order ids count up from 0 per player
+fn place_order(maker: &Keypair, order_id: u64, sell: (u8, u64), buy: (u8, u64)) -> Instruction {
+    instruction(
+        lumberjack::accounts::PlaceOrder {
+            session_token: None,
+            player: player_pda(&maker.pubkey()),
+            order: order_pda(maker, order_id),
+            signer: maker.pubkey(),
+            system_program: solana_sdk::system_program::ID,
+        },
+        lumberjack::instruction::PlaceOrder {
+            sell_resource: sell.0,
+            sell_amount: sell.1,
+            buy_resource: buy.0,
+            buy_amount: buy.1,
+        },
+    )
+}
+
+fn fill_order(taker: &Keypair, maker: &Keypair, order_id: u64, amount: u64) -> Instruction {
+    instruction(
+        lumberjack::accounts::FillOrder {
+            session_token: None,
+            player: player_pda(&taker.pubkey()),
+            order: order_pda(maker, order_id),
+            maker: player_pda(&maker.pubkey()),
+            maker_authority: maker.pubkey(),
+            signer: taker.pubkey(),
+            system_program: solana_sdk::system_program::ID,
+        },
+        lumberjack::instruction::FillOrder { amount },
+    )
+}
+
+fn cancel_order(maker: &Keypair, order_id: u64) -> Instruction {
+    instruction(
+        lumberjack::accounts::CancelOrder {
+            session_token: None,
+            player: player_pda(&maker.pubkey()),
+            order: order_pda(maker, order_id),
+            authority: maker.pubkey(),
+            signer: maker.pubkey(),
+        },
+        lumberjack::instruction::CancelOrder {},
+    )
+}
+
+async fn order(game: &mut TestGame, maker: &Keypair, order_id: u64) -> Option<MarketOrder> {
+    let order_account = game
+        .context
+        .banks_client
+        .get_account(order_pda(maker, order_id))
+        .await
+        .unwrap()?;
+    Some(MarketOrder::try_deserialize(&mut order_account.data.as_slice()).unwrap())
+}
+
+fn resource_mint_pda(resource: u8) -> Pubkey {
+    Pubkey::find_program_address(&[b"resourceMint", &[resource]], &lumberjack::ID).0
+}
+
+fn create_resource_mint(signer: &Keypair, resource: u8) -> Instruction {
+    instruction(
+        lumberjack::accounts::CreateResourceMint {
+            signer: signer.pubkey(),
+            resource_mint: resource_mint_pda(resource),
+            token_program: spl_token::ID,
+            system_program: solana_sdk::system_program::ID,
+            rent: solana_sdk::sysvar::rent::ID,
+        },
+        lumberjack::instruction::CreateResourceMint { resource },
+    )
+}
+
+/// Accounts of withdraw_resources and deposit_resources, the tokens are in the ATA of the wallet
+fn resource_bridge(wallet: &Keypair, resource: u8) -> lumberjack::accounts::ResourceBridge {
+    lumberjack::accounts::ResourceBridge {
+        player: player_pda(&wallet.pubkey()),
+        resource_mint: resource_mint_pda(resource),
+        player_token_account: get_associated_token_address(
+            &wallet.pubkey(),
+            &resource_mint_pda(resource),
+        ),
+        signer: wallet.pubkey(),
+        token_program: spl_token::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        system_program: solana_sdk::system_program::ID,
+    }
+}
+
+async fn token_balance(game: &mut TestGame, wallet: &Keypair, resource: u8) -> u64 {
+    let address = get_associated_token_address(&wallet.pubkey(), &resource_mint_pda(resource));
+    let data = account(&mut game.context, address).await.data;
+    spl_token::state::Account::unpack(&data).unwrap().amount
+}
+
+fn reward_vault_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"rewardVault"], &lumberjack::ID).0
+}
+
+fn season_result_pda(season: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"seasonResult", &season.to_le_bytes()], &lumberjack::ID).0
+}
+
+/// Ends the season of the game on a new board, the next board actions go to that board
+async fn restart_game(
+    game: &mut TestGame,
+    signer: &Keypair,
+    size_x: u8,
+    size_y: u8,
+    seed: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let restart = instruction(
+        lumberjack::accounts::RestartGame {
+            game_config: game_config_pda(),
+            board: board_pda(game.season),
+            next_board: board_pda(game.season + 1),
+            season_result: season_result_pda(game.season),
+            game_actions: game_actions_pda(),
+            signer: signer.pubkey(),
+            system_program: solana_sdk::system_program::ID,
+        },
+        lumberjack::instruction::RestartGame {
+            size_x,
+            size_y,
+            seed,
+        },
+    );
+    send(&mut game.context, &[restart], &[signer]).await?;
+    game.season += 1;
+    Ok(())
+}
+
+/// Tiles the map generator creates for the seed, column by column like the program
+fn generated_map(seed: u64, size_x: usize, size_y: usize) -> Vec<Vec<u8>> {
+    let mut generator = MapGenerator::new(seed);
+    (0..size_x)
+        .map(|_| (0..size_y).map(|_| generator.next_tile()).collect())
+        .collect()
+}
+
+/// Building types of all tiles of the board, column by column like the map generator
+fn building_types(board: &BoardAccount) -> Vec<Vec<u8>> {
+    let data = board.data;
+    data.iter()
+        .map(|column| column.iter().map(|tile| tile.building_type).collect())
+        .collect()
+}
+
+async fn advance_clock(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.slot += 1;
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+#[tokio::test]
+async fn chop_tree_fills_the_inventory_of_the_player() {
+    let (mut game, chopper) = started_game().await;
+    let other = new_player(&mut game).await;
+    let mut fixture = empty_board();
+    fixture.data[0][0].building_type = BUILDING_TYPE_TREE;
+    set_board(&mut game, &fixture).await;
+
+    let chop = board_action(
+        &game,
+        &chopper,
+        lumberjack::instruction::ChopTree { x: 0, y: 0 },
+    );
+    act(&mut game, &chopper, chop).await.unwrap();
+
+    let chopper_data = player(&mut game, &chopper).await;
+    assert_eq!(chopper_data.wood, 5);
+    assert_eq!(chopper_data.energy, lumberjack::MAX_ENERGY - 3);
+    assert_eq!(player(&mut game, &other).await.wood, 0);
+    let board = board(&mut game).await;
+    assert_eq!(board.data[0][0].building_type, BUILDING_TYPE_EMPTY);
+    assert_eq!({ board.wood }, 0);
+}
+
+#[tokio::test]
+async fn contribute_moves_resources_of_the_player_into_the_treasury() {
+    let (mut game, contributor) = started_game().await;
+    let other = new_player(&mut game).await;
+    for wallet in [&contributor, &other] {
+        edit_player(&mut game, wallet, |player| {
+            player.wood = 30;
+            player.stone = 20;
+        })
+        .await;
+    }
+
+    let contribute = board_action(
+        &game,
+        &contributor,
+        lumberjack::instruction::Contribute { wood: 10, stone: 5 },
+    );
+    act(&mut game, &contributor, contribute).await.unwrap();
+
+    let contributor_data = player(&mut game, &contributor).await;
+    assert_eq!((contributor_data.wood, contributor_data.stone), (20, 15));
+    let other_data = player(&mut game, &other).await;
+    assert_eq!((other_data.wood, other_data.stone), (30, 20));
+    let board = board(&mut game).await;
+    assert_eq!(({ board.wood }, { board.stone }), (10, 5));
+
+    let too_much = board_action(
+        &game,
+        &contributor,
+        lumberjack::instruction::Contribute { wood: 21, stone: 0 },
+    );
+    assert_error(
+        act(&mut game, &contributor, too_much).await,
+        GameErrorCode::NotEnoughWood,
+    );
+}
+
+#[tokio::test]
+async fn build_and_upgrade_are_paid_by_the_player() {
+    let (mut game, builder) = started_game().await;
+    let other = new_player(&mut game).await;
+    for wallet in [&builder, &other] {
+        edit_player(&mut game, wallet, |player| {
+            player.wood = 50;
+            player.stone = 50;
+        })
+        .await;
+    }
+
+    let build = board_action(
+        &game,
+        &builder,
+        lumberjack::instruction::Build {
+            x: 0,
+            y: 0,
+            building_type: BUILDING_TYPE_SAWMILL,
+        },
+    );
+    act(&mut game, &builder, build).await.unwrap();
+    let builder_data = player(&mut game, &builder).await;
+    assert_eq!((builder_data.wood, builder_data.stone), (50, 35));
+
+    let upgrade = board_action(
+        &game,
+        &builder,
+        lumberjack::instruction::Upgrade { x: 0, y: 0 },
+    );
+    act(&mut game, &builder, upgrade).await.unwrap();
+    let builder_data = player(&mut game, &builder).await;
+    assert_eq!((builder_data.wood, builder_data.stone), (40, 30));
+
+    let other_data = player(&mut game, &other).await;
+    assert_eq!((other_data.wood, other_data.stone), (50, 50));
+    let board = board(&mut game).await;
+    assert_eq!(({ board.wood }, { board.stone }), (0, 0));
+    assert_eq!(
+        board.data[0][0].building_owner,
+        player_pda(&builder.pubkey())
+    );
+}
+
+#[tokio::test]
+async fn members_upgrade_their_faction_building_from_the_treasury() {
+    let (mut game, member) = started_game().await;
+    edit_player(&mut game, &member, |player| {
+        player.wood = 50;
+        player.stone = 50;
+    })
+    .await;
+    let contribute = board_action(
+        &game,
+        &member,
+        lumberjack::instruction::Contribute {
+            wood: 30,
+            stone: 30,
+        },
+    );
+    act(&mut game, &member, contribute).await.unwrap();
+
+    let (good_x, good_y) = empty_board().good_position();
+    let upgrade = board_action(
+        &game,
+        &member,
+        lumberjack::instruction::Upgrade {
+            x: good_x as u8,
+            y: good_y as u8,
+        },
+    );
+    let not_a_member = upgrade.clone();
+    assert_error(
+        act(&mut game, &member, not_a_member).await,
+        GameErrorCode::NotInFaction,
+    );
+
+    let unknown = board_action(
+        &game,
+        &member,
+        lumberjack::instruction::JoinFaction { faction: 3 },
+    );
+    assert_error(
+        act(&mut game, &member, unknown).await,
+        GameErrorCode::UnknownFaction,
+    );
+    let join = board_action(
+        &game,
+        &member,
+        lumberjack::instruction::JoinFaction {
+            faction: FACTION_GOOD,
+        },
+    );
+    act(&mut game, &member, join).await.unwrap();
+    let join_again = board_action(
+        &game,
+        &member,
+        lumberjack::instruction::JoinFaction {
+            faction: FACTION_EVIL,
+        },
+    );
+    assert_error(
+        act(&mut game, &member, join_again).await,
+        GameErrorCode::AlreadyInFaction,
+    );
+
+    act(&mut game, &member, upgrade).await.unwrap();
+    let board = board(&mut game).await;
+    assert_eq!({ board.data[good_x][good_y].building_level }, 1);
+    assert_eq!(({ board.wood }, { board.stone }), (15, 15));
+    assert_eq!({ board.good_members }, 1);
+    let member_data = player(&mut game, &member).await;
+    assert_eq!((member_data.wood, member_data.stone), (20, 20));
+}
+
+#[tokio::test]
+async fn winners_claim_the_treasury_as_faction_reward() {
+    let (mut game, winner) = started_game().await;
+    let loser = new_player(&mut game).await;
+    let mut fixture = empty_board();
+    fixture.data[0][0].building_type = BUILDING_TYPE_TREE;
+    fixture.wood = 40;
+    fixture.stone = 20;
+    set_board(&mut game, &fixture).await;
+    for (wallet, faction) in [(&winner, FACTION_GOOD), (&loser, FACTION_EVIL)] {
+        let join = board_action(
+            &game,
+            wallet,
+            lumberjack::instruction::JoinFaction { faction },
+        );
+        act(&mut game, wallet, join).await.unwrap();
+    }
+
+    // The next fight takes the last health of the evil building
+    let mut fixture = board(&mut game).await;
+    let (evil_x, evil_y) = fixture.evil_position();
+    fixture.data[evil_x][evil_y].building_health = 2;
+    set_board(&mut game, &fixture).await;
+    let chop = board_action(
+        &game,
+        &winner,
+        lumberjack::instruction::ChopTree { x: 0, y: 0 },
+    );
+    act(&mut game, &winner, chop).await.unwrap();
+    let board_after_win = board(&mut game).await;
+    assert!(board_after_win.good_won);
+    assert_eq!(
+        ({ board_after_win.reward_wood }, {
+            board_after_win.reward_stone
+        }),
+        (40, 20)
+    );
+    assert_eq!(
+        ({ board_after_win.wood }, { board_after_win.stone }),
+        (0, 0)
+    );
+
+    let late = new_player(&mut game).await;
+    let join_late = board_action(
+        &game,
+        &late,
+        lumberjack::instruction::JoinFaction {
+            faction: FACTION_GOOD,
+        },
+    );
+    assert_error(
+        act(&mut game, &late, join_late).await,
+        GameErrorCode::GameAlreadyOver,
+    );
+
+    let claim = board_action(
+        &game,
+        &winner,
+        lumberjack::instruction::ClaimFactionReward {},
+    );
+    act(&mut game, &winner, claim).await.unwrap();
+    let winner_data = player(&mut game, &winner).await;
+    // 5 wood from the chop
+    assert_eq!((winner_data.wood, winner_data.stone), (45, 20));
+    let claim_again = board_action(
+        &game,
+        &winner,
+        lumberjack::instruction::ClaimFactionReward {},
+    );
+    assert_error(
+        act(&mut game, &winner, claim_again).await,
+        GameErrorCode::NoFactionReward,
+    );
+    let loser_claim = board_action(
+        &game,
+        &loser,
+        lumberjack::instruction::ClaimFactionReward {},
+    );
+    assert_error(
+        act(&mut game, &loser, loser_claim).await,
+        GameErrorCode::NoFactionReward,
+    );
+}
+
+#[tokio::test]
+async fn buildings_are_used_by_their_owner_and_authorised_players() {
+    let (mut game, owner) = started_game().await;
+    let other = new_player(&mut game).await;
+    for wallet in [&owner, &other] {
+        edit_player(&mut game, wallet, |player| {
+            player.wood = 50;
+            player.stone = 50;
+        })
+        .await;
+    }
+    let build = board_action(
+        &game,
+        &owner,
+        lumberjack::instruction::Build {
+            x: 0,
+            y: 0,
+            building_type: BUILDING_TYPE_SAWMILL,
+        },
+    );
+    act(&mut game, &owner, build).await.unwrap();
+    advance_clock(&mut game.context, 60).await;
+
+    let upgrade = board_action(
+        &game,
+        &other,
+        lumberjack::instruction::Upgrade { x: 0, y: 0 },
+    );
+    assert_error(
+        act(&mut game, &other, upgrade.clone()).await,
+        GameErrorCode::NotBuildingOwner,
+    );
+    let collect = on_building_of(
+        board_action(
+            &game,
+            &other,
+            lumberjack::instruction::Collect { x: 0, y: 0 },
+        ),
+        &owner,
+    );
+    assert_error(
+        act(&mut game, &other, collect.clone()).await,
+        GameErrorCode::NotBuildingOwner,
+    );
+
+    let authorize = board_action(
+        &game,
+        &owner,
+        lumberjack::instruction::AuthorizePlayer {
+            authorized_player: player_pda(&other.pubkey()),
+        },
+    );
+    act(&mut game, &owner, authorize).await.unwrap();
+    // The owner account shows that the player is authorised
+    let without_owner = board_action(
+        &game,
+        &other,
+        lumberjack::instruction::Collect { x: 0, y: 0 },
+    );
+    assert_error(
+        act(&mut game, &other, without_owner).await,
+        GameErrorCode::NotBuildingOwner,
+    );
+    act(&mut game, &other, collect.clone()).await.unwrap();
+    assert_eq!(player(&mut game, &other).await.wood, 55);
+    assert_eq!(player(&mut game, &owner).await.wood, 50);
+
+    let revoke = board_action(
+        &game,
+        &owner,
+        lumberjack::instruction::RevokePlayer {
+            authorized_player: player_pda(&other.pubkey()),
+        },
+    );
+    act(&mut game, &owner, revoke).await.unwrap();
+    advance_clock(&mut game.context, 60).await;
+    assert_error(
+        act(&mut game, &other, collect).await,
+        GameErrorCode::NotBuildingOwner,
+    );
+
+    let transfer = board_action(
+        &game,
+        &owner,
+        lumberjack::instruction::TransferBuilding {
+            x: 0,
+            y: 0,
+            new_owner: player_pda(&other.pubkey()),
+        },
+    );
+    act(&mut game, &owner, transfer).await.unwrap();
+    act(&mut game, &other, upgrade).await.unwrap();
+    let board = board(&mut game).await;
+    assert_eq!(board.data[0][0].building_owner, player_pda(&other.pubkey()));
+    let other_data = player(&mut game, &other).await;
+    assert_eq!((other_data.wood, other_data.stone), (45, 45));
+}
+
+#[tokio::test]
+async fn first_chop_generates_the_board_from_the_clock() {
+    let mut game = setup().await;
+    let wallet = new_player(&mut game).await;
+    assert!(!board(&mut game).await.initialized);
+
+    // The seed is the time of the first chop, so the tree to chop is known up front
+    let clock: Clock = game.context.banks_client.get_sysvar().await.unwrap();
+    let map = generated_map(clock.unix_timestamp as u64, 10, 10);
+    let layout = empty_board();
+    let (x, y) = (0..10)
+        .flat_map(|x| (0..10).map(move |y| (x, y)))
+        .find(|&(x, y)| {
+            map[x][y] == BUILDING_TYPE_TREE
+                && (x, y) != layout.good_position()
+                && (x, y) != layout.evil_position()
+        })
+        .unwrap();
+    let chop = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::ChopTree {
+            x: x as u8,
+            y: y as u8,
+        },
+    );
+    act(&mut game, &wallet, chop).await.unwrap();
+
+    let board = board(&mut game).await;
+    assert!(board.initialized);
+    assert_eq!(({ board.size_x }, { board.size_y }), (10, 10));
+    assert_eq!({ board.game_number }, 1);
+    let (good_x, good_y) = board.good_position();
+    let (evil_x, evil_y) = board.evil_position();
+    let mut expected = map;
+    expected[x][y] = BUILDING_TYPE_EMPTY;
+    expected[good_x][good_y] = BUILDING_TYPE_GOOD;
+    expected[evil_x][evil_y] = BUILDING_TYPE_EVIL;
+    assert_eq!(building_types(&board), expected);
+}
+
+#[tokio::test]
+async fn restart_generates_a_smaller_board_from_the_seed() {
+    let (mut game, wallet) = started_game().await;
+    let mut fixture = empty_board();
+    fixture.good_won = true;
+    set_board(&mut game, &fixture).await;
+
+    assert_error(
+        restart_game(&mut game, &wallet, 3, 6, 42).await,
+        GameErrorCode::InvalidBoardSize,
+    );
+    restart_game(&mut game, &wallet, 6, 5, 42).await.unwrap();
+
+    let board = board(&mut game).await;
+    assert_eq!(
+        ({ board.size_x }, { board.size_y }, { board.seed }),
+        (6, 5, 42)
+    );
+    assert_eq!(board.good_position(), (3, 1));
+    assert_eq!(board.evil_position(), (2, 1));
+    let mut expected = vec![vec![BUILDING_TYPE_WATER; 10]; 10];
+    for (x, column) in generated_map(42, 6, 5).into_iter().enumerate() {
+        expected[x][..5].copy_from_slice(&column);
+    }
+    expected[3][1] = BUILDING_TYPE_GOOD;
+    expected[2][1] = BUILDING_TYPE_EVIL;
+    assert_eq!(building_types(&board), expected);
+
+    let outside = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::ChopTree { x: 6, y: 0 },
+    );
+    assert_error(
+        act(&mut game, &wallet, outside).await,
+        GameErrorCode::TileOutOfBounds,
+    );
+}
+
+#[tokio::test]
+async fn houses_warehouses_and_farms_work_from_the_building_table() {
+    let (mut game, wallet) = started_game().await;
+    edit_player(&mut game, &wallet, |player| {
+        player.wood = 100;
+        player.stone = 100;
+    })
+    .await;
+    for (x, building_type) in [
+        (0, BUILDING_TYPE_HOUSE),
+        (1, BUILDING_TYPE_WAREHOUSE),
+        (2, BUILDING_TYPE_FARM),
+    ] {
+        let build = board_action(
+            &game,
+            &wallet,
+            lumberjack::instruction::Build {
+                x,
+                y: 0,
+                building_type,
+            },
+        );
+        act(&mut game, &wallet, build).await.unwrap();
+    }
+    let wallet_data = player(&mut game, &wallet).await;
+    assert_eq!((wallet_data.wood, wallet_data.stone), (40, 60));
+    assert_eq!(wallet_data.energy_bonus, 2);
+    assert_eq!(wallet_data.storage_bonus, 250);
+
+    // The house lets the energy regenerate above the max energy of the config and the
+    // warehouse stores food above the base storage
+    edit_player(&mut game, &wallet, |player| player.food = 748).await;
+    advance_clock(&mut game.context, 600).await;
+    let collect = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::Collect { x: 2, y: 0 },
+    );
+    act(&mut game, &wallet, collect).await.unwrap();
+    let wallet_data = player(&mut game, &wallet).await;
+    assert_eq!(wallet_data.energy, lumberjack::MAX_ENERGY + 2 - 1);
+    assert_eq!(wallet_data.food, 750);
+
+    let house = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::Collect { x: 0, y: 0 },
+    );
+    assert_error(
+        act(&mut game, &wallet, house).await,
+        GameErrorCode::BuildingTypeNotCollectable,
+    );
+}
+
+#[tokio::test]
+async fn collect_pays_the_production_since_the_last_collection_up_to_the_storage() {
+    let (mut game, wallet) = started_game().await;
+    edit_player(&mut game, &wallet, |player| player.stone = 15).await;
+    let build = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::Build {
+            x: 0,
+            y: 0,
+            building_type: BUILDING_TYPE_SAWMILL,
+        },
+    );
+    act(&mut game, &wallet, build).await.unwrap();
+    let collect = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::Collect { x: 0, y: 0 },
+    );
+    assert_error(
+        act(&mut game, &wallet, collect.clone()).await,
+        GameErrorCode::ProductionNotReadyYet,
+    );
+
+    // 5 wood per minute
+    advance_clock(&mut game.context, 30).await;
+    act(&mut game, &wallet, collect.clone()).await.unwrap();
+    assert_eq!(player(&mut game, &wallet).await.wood, 2);
+    assert_error(
+        act(&mut game, &wallet, collect.clone()).await,
+        GameErrorCode::ProductionNotReadyYet,
+    );
+
+    // An hour away fills the storage of 100 wood of the sawmill
+    advance_clock(&mut game.context, 3600).await;
+    act(&mut game, &wallet, collect).await.unwrap();
+    assert_eq!(player(&mut game, &wallet).await.wood, 102);
+}
+
+#[tokio::test]
+async fn upgrades_take_time_or_food_to_finish() {
+    let (mut game, wallet) = started_game().await;
+    edit_player(&mut game, &wallet, |player| {
+        player.wood = 50;
+        player.stone = 50;
+    })
+    .await;
+    let build = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::Build {
+            x: 0,
+            y: 0,
+            building_type: BUILDING_TYPE_SAWMILL,
+        },
+    );
+    act(&mut game, &wallet, build).await.unwrap();
+    let finish = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::FinishUpgrade { x: 0, y: 0 },
+    );
+    assert_error(
+        act(&mut game, &wallet, finish.clone()).await,
+        GameErrorCode::NoUpgradeInProgress,
+    );
+
+    let upgrade = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::Upgrade { x: 0, y: 0 },
+    );
+    act(&mut game, &wallet, upgrade.clone()).await.unwrap();
+    assert_eq!({ board(&mut game).await.data[0][0].building_level }, 0);
+    assert_error(
+        act(&mut game, &wallet, finish.clone()).await,
+        GameErrorCode::UpgradeNotFinished,
+    );
+    let collect = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::Collect { x: 0, y: 0 },
+    );
+    assert_error(
+        act(&mut game, &wallet, collect.clone()).await,
+        GameErrorCode::BuildingIsUpgrading,
+    );
+
+    // The first upgrade takes 30 seconds, in which the sawmill produces nothing
+    advance_clock(&mut game.context, 30).await;
+    act(&mut game, &wallet, finish.clone()).await.unwrap();
+    assert_eq!({ board(&mut game).await.data[0][0].building_level }, 1);
+    assert_error(
+        act(&mut game, &wallet, collect).await,
+        GameErrorCode::ProductionNotReadyYet,
+    );
+
+    // The second one takes 45 seconds, one food for every started 10 seconds
+    act(&mut game, &wallet, upgrade).await.unwrap();
+    let speed_up = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::SpeedUpUpgrade { x: 0, y: 0 },
+    );
+    assert_error(
+        act(&mut game, &wallet, speed_up.clone()).await,
+        GameErrorCode::NotEnoughFood,
+    );
+    edit_player(&mut game, &wallet, |player| player.food = 10).await;
+    act(&mut game, &wallet, speed_up.clone()).await.unwrap();
+    assert_eq!(player(&mut game, &wallet).await.food, 5);
+    assert_eq!({ board(&mut game).await.data[0][0].building_level }, 2);
+    assert_error(
+        act(&mut game, &wallet, speed_up).await,
+        GameErrorCode::NoUpgradeInProgress,
+    );
+}
+
+#[tokio::test]
+async fn trees_grow_back_next_to_trees_and_where_they_are_planted() {
+    let (mut game, wallet) = started_game().await;
+    let mut fixture = empty_board();
+    fixture.data[0][0].building_type = BUILDING_TYPE_TREE;
+    fixture.data[0][1].building_type = BUILDING_TYPE_TREE;
+    set_board(&mut game, &fixture).await;
+    let chop = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::ChopTree { x: 0, y: 0 },
+    );
+    act(&mut game, &wallet, chop.clone()).await.unwrap();
+    let plant = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::PlantTree { x: 5, y: 0 },
+    );
+    act(&mut game, &wallet, plant).await.unwrap();
+    assert_eq!(player(&mut game, &wallet).await.wood, 3);
+    assert_eq!(
+        board(&mut game).await.data[5][0].building_type,
+        BUILDING_TYPE_SAPLING
+    );
+
+    // A sapling sprouts next to the tree after 5 minutes and is grown 10 minutes later
+    advance_clock(&mut game.context, 300).await;
+    assert_error(
+        act(&mut game, &wallet, chop.clone()).await,
+        GameErrorCode::TileHasNoTree,
+    );
+    advance_clock(&mut game.context, 600).await;
+    act(&mut game, &wallet, chop).await.unwrap();
+    let planted = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::ChopTree { x: 5, y: 0 },
+    );
+    act(&mut game, &wallet, planted).await.unwrap();
+    assert_eq!(player(&mut game, &wallet).await.wood, 13);
+
+    // Only what fits into the storage is chopped
+    edit_player(&mut game, &wallet, |player| player.wood = 498).await;
+    let full = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::ChopTree { x: 0, y: 1 },
+    );
+    act(&mut game, &wallet, full).await.unwrap();
+    assert_eq!(player(&mut game, &wallet).await.wood, 500);
+    assert_eq!(
+        board(&mut game).await.data[0][1].building_type,
+        BUILDING_TYPE_EMPTY
+    );
+}
+
+#[tokio::test]
+async fn upgrades_cost_what_the_exported_cost_curves_say() {
+    let (mut game, wallet) = started_game().await;
+    edit_player(&mut game, &wallet, |player| {
+        player.wood = 100;
+        player.stone = 100;
+        player.food = 20;
+    })
+    .await;
+    let build = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::Build {
+            x: 0,
+            y: 0,
+            building_type: BUILDING_TYPE_SAWMILL,
+        },
+    );
+    act(&mut game, &wallet, build).await.unwrap();
+    let upgrade = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::Upgrade { x: 0, y: 0 },
+    );
+    act(&mut game, &wallet, upgrade.clone()).await.unwrap();
+    let speed_up = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::SpeedUpUpgrade { x: 0, y: 0 },
+    );
+    act(&mut game, &wallet, speed_up).await.unwrap();
+    act(&mut game, &wallet, upgrade).await.unwrap();
+
+    // 10 wood * 1.1 and 5 stone * 1.05 rounded down
+    let sawmill = BuildingDefinition::get(BUILDING_TYPE_SAWMILL).unwrap();
+    assert_eq!(sawmill.upgrade_cost(0).unwrap(), (10, 5));
+    assert_eq!(sawmill.upgrade_cost(1).unwrap(), (11, 5));
+    let wallet_data = player(&mut game, &wallet).await;
+    assert_eq!(
+        (wallet_data.wood, wallet_data.stone),
+        (100 - 10 - 11, 100 - 15 - 5 - 5)
+    );
+
+    let mut fixture = board(&mut game).await;
+    fixture.data[1][0] = fixture.data[0][0];
+    fixture.data[1][0].building_level = lumberjack::MAX_CURVE_LEVEL + 1;
+    fixture.data[1][0].building_start_upgrade_time = 0;
+    set_board(&mut game, &fixture).await;
+    let too_high = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::Upgrade { x: 1, y: 0 },
+    );
+    assert_error(
+        act(&mut game, &wallet, too_high).await,
+        GameErrorCode::CostOverflow,
+    );
+}
+
+#[tokio::test]
+async fn energy_is_refilled_per_point_with_sol_or_stone() {
+    let (mut game, wallet) = started_game().await;
+    edit_player(&mut game, &wallet, |player| player.energy = 6).await;
+    let treasury = lumberjack::TREASURY_PUBKEY;
+    let wallet_lamports = game
+        .context
+        .banks_client
+        .get_balance(wallet.pubkey())
+        .await
+        .unwrap();
+
+    // Only the 4 missing points are paid, half of the fee goes to the war pool
+    let refill = refill_energy(&game, &wallet, treasury, 10);
+    act(&mut game, &wallet, refill.clone()).await.unwrap();
+    let fee = 4 * lumberjack::ENERGY_REFILL_FEE;
+    assert_eq!(
+        player(&mut game, &wallet).await.energy,
+        lumberjack::MAX_ENERGY
+    );
+    let vault = account(&mut game.context, reward_vault_pda()).await;
+    let rent = game.context.banks_client.get_rent().await.unwrap();
+    assert_eq!(
+        vault.lamports,
+        rent.minimum_balance(vault.data.len()) + fee / 2
+    );
+    assert_eq!(account(&mut game.context, treasury).await.lamports, fee / 2);
+    assert_eq!({ board(&mut game).await.war_pool }, fee / 2);
+    assert_eq!(
+        game.context
+            .banks_client
+            .get_balance(wallet.pubkey())
+            .await
+            .unwrap(),
+        wallet_lamports - fee - rent.minimum_balance(vault.data.len())
+    );
+    assert_error(
+        act(&mut game, &wallet, refill).await,
+        GameErrorCode::EnergyAlreadyFull,
+    );
+
+    // The game config is only changed by the admin
+    let admin = admin(&mut game.context);
+    let new_treasury = funded_keypair(&mut game.context, Keypair::new()).pubkey();
+    let not_admin = update_game_config(&wallet, new_treasury, 1000, 3, 12, 0);
+    assert_error(
+        send(&mut game.context, &[not_admin], &[&wallet]).await,
+        anchor_lang::error::ErrorCode::ConstraintAddress,
+    );
+    let invalid = update_game_config(&admin, new_treasury, 1000, 3, 0, 0);
+    assert_error(
+        send(&mut game.context, &[invalid], &[&admin]).await,
+        GameErrorCode::InvalidGameConfig,
+    );
+    let update = update_game_config(&admin, new_treasury, 1000, 3, 12, 0);
+    send(&mut game.context, &[update], &[&admin]).await.unwrap();
+
+    // 10 stone pay for 3 of the 6 points missing to the new max energy
+    edit_player(&mut game, &wallet, |player| {
+        player.energy = 6;
+        player.stone = 10;
+    })
+    .await;
+    let with_stone = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::RefillEnergyWithStone { amount: 6 },
+    );
+    act(&mut game, &wallet, with_stone).await.unwrap();
+    let wallet_data = player(&mut game, &wallet).await;
+    assert_eq!((wallet_data.energy, wallet_data.stone), (9, 1));
+
+    let old_treasury = refill_energy(&game, &wallet, treasury, 1);
+    assert_error(
+        act(&mut game, &wallet, old_treasury).await,
+        anchor_lang::error::ErrorCode::ConstraintAddress,
+    );
+    let refill = refill_energy(&game, &wallet, new_treasury, 1);
+    act(&mut game, &wallet, refill).await.unwrap();
+    assert_eq!(
+        account(&mut game.context, new_treasury).await.lamports,
+        LAMPORTS_PER_SOL + 1000
+    );
+    assert_eq!(player(&mut game, &wallet).await.energy, 10);
+}
+
+#[tokio::test]
+async fn restart_archives_the_season_and_starts_a_new_board() {
+    let (mut game, wallet) = started_game().await;
+    let mut fixture = empty_board();
+    fixture.data[0][0].building_type = BUILDING_TYPE_TREE;
+    set_board(&mut game, &fixture).await;
+    edit_player(&mut game, &wallet, |player| {
+        player.wood = 10;
+        player.stone = 5;
+    })
+    .await;
+    let join = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::JoinFaction {
+            faction: FACTION_GOOD,
+        },
+    );
+    act(&mut game, &wallet, join).await.unwrap();
+    let contribute = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::Contribute { wood: 10, stone: 5 },
+    );
+    act(&mut game, &wallet, contribute).await.unwrap();
+    assert_error(
+        restart_game(&mut game, &wallet, 10, 10, 7).await,
+        GameErrorCode::SeasonNotOver,
+    );
+
+    let mut fixture = board(&mut game).await;
+    let (evil_x, evil_y) = fixture.evil_position();
+    fixture.data[evil_x][evil_y].building_health = 2;
+    set_board(&mut game, &fixture).await;
+    let chop = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::ChopTree { x: 0, y: 0 },
+    );
+    act(&mut game, &wallet, chop).await.unwrap();
+    restart_game(&mut game, &wallet, 10, 10, 7).await.unwrap();
+
+    let data = account(&mut game.context, season_result_pda(0)).await.data;
+    let result = SeasonResult::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(result.season, 0);
+    assert_eq!(result.board, board_pda(0));
+    assert_eq!(result.game_number, 1);
+    assert_eq!(result.winner, FACTION_GOOD);
+    assert_eq!((result.good_members, result.evil_members), (1, 0));
+    assert_eq!(result.top_contributors[0], player_pda(&wallet.pubkey()));
+    assert_eq!(result.top_contributions[0], 15);
+    let data = account(&mut game.context, game_config_pda()).await.data;
+    assert_eq!(
+        GameConfig::try_deserialize(&mut data.as_slice())
+            .unwrap()
+            .season,
+        1
+    );
+
+    // The finished board stays as it was, the reward of its winners moves to the new board
+    game.season = 0;
+    assert!(board(&mut game).await.good_won);
+    game.season = 1;
+    let new_board = board(&mut game).await;
+    assert!(!new_board.good_won && new_board.initialized);
+    assert_eq!({ new_board.game_number }, 2);
+    assert_eq!(
+        ({ new_board.reward_wood }, { new_board.reward_stone }),
+        (10, 5)
+    );
+    let claim = board_action(
+        &game,
+        &wallet,
+        lumberjack::instruction::ClaimFactionReward {},
+    );
+    act(&mut game, &wallet, claim).await.unwrap();
+    let wallet_data = player(&mut game, &wallet).await;
+    assert_eq!((wallet_data.wood, wallet_data.stone), (15, 5));
+}
+
+#[tokio::test]
+async fn admin_moves_the_legacy_board_to_the_current_season() {
+    let mut game = setup().await;
+    let wallet = new_player(&mut game).await;
+    let legacy_board_address = Pubkey::find_program_address(&[b"board"], &lumberjack::ID).0;
+    let mut legacy = empty_board();
+    legacy.game_number = 4;
+    legacy.wood = 33;
+    let set_legacy_board = |context: &mut ProgramTestContext| {
+        let mut data = vec![0; 10024];
+        data[..8].copy_from_slice(&BoardAccount::DISCRIMINATOR);
+        data[8..8 + std::mem::size_of::<BoardAccount>()]
+            .copy_from_slice(bytemuck::bytes_of(&legacy));
+        context.set_account(
+            &legacy_board_address,
+            &Account {
+                lamports: LAMPORTS_PER_SOL,
+                data,
+                owner: lumberjack::ID,
+                executable: false,
+                rent_epoch: 0,
+            }
+            .into(),
+        );
+    };
+    set_legacy_board(&mut game.context);
+    let migrate = |signer: &Keypair| {
+        instruction(
+            lumberjack::accounts::MigrateLegacyBoard {
+                signer: signer.pubkey(),
+                game_config: game_config_pda(),
+                legacy_board: legacy_board_address,
+                board: board_pda(0),
+                system_program: solana_sdk::system_program::ID,
+            },
+            lumberjack::instruction::MigrateLegacyBoard {},
+        )
+    };
+
+    assert_error(
+        send(&mut game.context, &[migrate(&wallet)], &[&wallet]).await,
+        anchor_lang::error::ErrorCode::ConstraintAddress,
+    );
+    let admin = admin(&mut game.context);
+    send(&mut game.context, &[migrate(&admin)], &[&admin])
+        .await
+        .unwrap();
+    let board = board(&mut game).await;
+    assert_eq!(bytemuck::bytes_of(&board), bytemuck::bytes_of(&legacy));
+    assert!(game
+        .context
+        .banks_client
+        .get_account(legacy_board_address)
+        .await
+        .unwrap()
+        .is_none());
+
+    // The board of the season is in use now
+    set_legacy_board(&mut game.context);
+    assert_error(
+        send(&mut game.context, &[migrate(&admin)], &[&admin]).await,
+        GameErrorCode::BoardAlreadyInUse,
+    );
+}
+
+#[tokio::test]
+async fn winners_share_the_war_pool_and_unclaimed_shares_go_back_into_it() {
+    let (mut game, first) = started_game().await;
+    let second = new_player(&mut game).await;
+    let enemy = new_player(&mut game).await;
+    refill_missing_energy(&mut game, &enemy, 4).await;
+    let pool = 2 * lumberjack::ENERGY_REFILL_FEE;
+    assert_eq!({ board(&mut game).await.war_pool }, pool);
+    join(&mut game, &first, FACTION_GOOD).await;
+    join(&mut game, &second, FACTION_GOOD).await;
+    join(&mut game, &enemy, FACTION_EVIL).await;
+    contribute(&mut game, &first, 10, 0).await;
+    contribute(&mut game, &second, 30, 0).await;
+    let early = claim_war_reward(&game, &first);
+    assert_error(
+        act(&mut game, &first, early).await,
+        GameErrorCode::NoWarReward,
+    );
+
+    // Chopping scores 5 for the action and 2 for the damage of the good building
+    prepare_last_fight(&mut game, FACTION_EVIL, 2).await;
+    let chop = board_action(
+        &game,
+        &first,
+        lumberjack::instruction::ChopTree { x: 0, y: 0 },
+    );
+    act(&mut game, &first, chop).await.unwrap();
+    let won = board(&mut game).await;
+    assert!(won.good_won);
+    assert_eq!(
+        ({ won.reward_war_score }, { won.reward_war_pool }),
+        (47, pool)
+    );
+    assert_eq!({ won.war_pool }, 0);
+
+    // The reward is claimed in the next season
+    restart_game(&mut game, &enemy, 10, 10, 7).await.unwrap();
+    let first_share = pool * 17 / 47;
+    let before = lamports(&mut game, first.pubkey()).await;
+    let claim = claim_war_reward(&game, &first);
+    act(&mut game, &first, claim.clone()).await.unwrap();
+    assert_eq!(
+        lamports(&mut game, first.pubkey()).await,
+        before + first_share
+    );
+    assert_error(
+        act(&mut game, &first, claim).await,
+        GameErrorCode::NoWarReward,
+    );
+    let losing = claim_war_reward(&game, &enemy);
+    assert_error(
+        act(&mut game, &enemy, losing).await,
+        GameErrorCode::NoWarReward,
+    );
+
+    // The second player misses the claim until the next game is decided with a score
+    join(&mut game, &enemy, FACTION_EVIL).await;
+    contribute(&mut game, &enemy, 5, 0).await;
+    prepare_last_fight(&mut game, FACTION_GOOD, 2).await;
+    let chop = board_action(
+        &game,
+        &enemy,
+        lumberjack::instruction::ChopTree { x: 0, y: 0 },
+    );
+    act(&mut game, &enemy, chop).await.unwrap();
+    let won = board(&mut game).await;
+    assert!(won.evil_won);
+    assert_eq!(
+        ({ won.reward_war_score }, { won.reward_war_pool }),
+        (12, pool - first_share)
+    );
+    let missed = claim_war_reward(&game, &second);
+    assert_error(
+        act(&mut game, &second, missed).await,
+        GameErrorCode::NoWarReward,
+    );
+    let before = lamports(&mut game, enemy.pubkey()).await;
+    let claim = claim_war_reward(&game, &enemy);
+    act(&mut game, &enemy, claim).await.unwrap();
+    assert_eq!(
+        lamports(&mut game, enemy.pubkey()).await,
+        before + pool - first_share
+    );
+}
+
+#[tokio::test]
+async fn treasury_upgrades_and_wins_without_score_keep_the_war_reward() {
+    let (mut game, member) = started_game().await;
+    let outsider = new_player(&mut game).await;
+    refill_missing_energy(&mut game, &outsider, 4).await;
+    let pool = 2 * lumberjack::ENERGY_REFILL_FEE;
+    join(&mut game, &member, FACTION_GOOD).await;
+    contribute(&mut game, &member, 30, 30).await;
+
+    // The treasury resources scored when they were contributed, the upgrade only scores the
+    // fight, which the upgraded building already fights with 4 damage
+    let (good_x, good_y) = empty_board().good_position();
+    let upgrade = board_action(
+        &game,
+        &member,
+        lumberjack::instruction::Upgrade {
+            x: good_x as u8,
+            y: good_y as u8,
+        },
+    );
+    act(&mut game, &member, upgrade).await.unwrap();
+    assert_eq!(player(&mut game, &member).await.war_score, 60 + 5 + 4);
+    prepare_last_fight(&mut game, FACTION_EVIL, 4).await;
+    let chop = board_action(
+        &game,
+        &member,
+        lumberjack::instruction::ChopTree { x: 0, y: 0 },
+    );
+    act(&mut game, &member, chop).await.unwrap();
+    assert_eq!({ board(&mut game).await.reward_war_score }, 69 + 5 + 4);
+
+    // Nobody joined the next game, so its pool stays and the last reward can still be claimed
+    restart_game(&mut game, &outsider, 10, 10, 7).await.unwrap();
+    refill_missing_energy(&mut game, &outsider, 2).await;
+    prepare_last_fight(&mut game, FACTION_EVIL, 2).await;
+    let chop = board_action(
+        &game,
+        &outsider,
+        lumberjack::instruction::ChopTree { x: 0, y: 0 },
+    );
+    act(&mut game, &outsider, chop).await.unwrap();
+    let won = board(&mut game).await;
+    assert!(won.good_won);
+    assert_eq!({ won.reward_war_game }, 1);
+    assert_eq!({ won.reward_war_pool }, pool);
+    assert_eq!({ won.war_pool }, lumberjack::ENERGY_REFILL_FEE);
+    let before = lamports(&mut game, member.pubkey()).await;
+    let claim = claim_war_reward(&game, &member);
+    act(&mut game, &member, claim).await.unwrap();
+    assert_eq!(lamports(&mut game, member.pubkey()).await, before + pool);
+}
+
+#[tokio::test]
+async fn raids_damage_and_rob_buildings_which_walls_protect() {
+    let (mut game, owner) = started_game().await;
+    let raider = new_player(&mut game).await;
+    let clock: Clock = game.context.banks_client.get_sysvar().await.unwrap();
+    let owner_player = player_pda(&owner.pubkey());
+    let mut fixture = empty_board();
+    // 10 wood are waiting in the sawmill
+    fixture.data[0][0].building_type = BUILDING_TYPE_SAWMILL;
+    fixture.data[0][0].building_start_collect_time = clock.unix_timestamp - 120;
+    fixture.data[1][0].building_type = BUILDING_TYPE_WALL;
+    fixture.data[9][9].building_type = BUILDING_TYPE_HOUSE;
+    fixture.data[9][9].building_health = 10;
+    for (x, y) in [(0, 0), (1, 0), (9, 9)] {
+        fixture.data[x][y].building_owner = owner_player;
+    }
+    set_board(&mut game, &fixture).await;
+    edit_player(&mut game, &owner, |player| {
+        player.stone = 5;
+        player.bonus_game = 1;
+        player.energy_bonus = 4;
+    })
+    .await;
+
+    let own = board_action(&game, &owner, lumberjack::instruction::Raid { x: 0, y: 0 });
+    assert_error(
+        act(&mut game, &owner, own).await,
+        GameErrorCode::CantRaidBuilding,
+    );
+    let (good_x, good_y) = fixture.good_position();
+    let faction_building = board_action(
+        &game,
+        &raider,
+        lumberjack::instruction::Raid {
+            x: good_x as u8,
+            y: good_y as u8,
+        },
+    );
+    assert_error(
+        act(&mut game, &raider, faction_building).await,
+        GameErrorCode::TileHasNoBuilding,
+    );
+
+    // The wall next to the sawmill takes 10% off the 40 damage of a raid
+    let raid = board_action(&game, &raider, lumberjack::instruction::Raid { x: 0, y: 0 });
+    act(&mut game, &raider, raid).await.unwrap();
+    assert_eq!(
+        { board(&mut game).await.data[0][0].building_health },
+        100 - 36
+    );
+    let raider_data = player(&mut game, &raider).await;
+    assert_eq!(raider_data.wood, 2);
+    assert_eq!(raider_data.energy, lumberjack::MAX_ENERGY - 2);
+    let collect = board_action(
+        &game,
+        &owner,
+        lumberjack::instruction::Collect { x: 0, y: 0 },
+    );
+    act(&mut game, &owner, collect).await.unwrap();
+    assert_eq!(player(&mut game, &owner).await.wood, 8);
+
+    let foreign = on_building_of(
+        board_action(
+            &game,
+            &raider,
+            lumberjack::instruction::Defend { x: 0, y: 0 },
+        ),
+        &owner,
+    );
+    assert_error(
+        act(&mut game, &raider, foreign).await,
+        GameErrorCode::NotBuildingOwner,
+    );
+    let defend = board_action(
+        &game,
+        &owner,
+        lumberjack::instruction::Defend { x: 0, y: 0 },
+    );
+    act(&mut game, &owner, defend.clone()).await.unwrap();
+    assert_eq!({ board(&mut game).await.data[0][0].building_health }, 0);
+    assert_eq!(player(&mut game, &owner).await.stone, 0);
+    assert_error(
+        act(&mut game, &owner, defend).await,
+        GameErrorCode::BuildingAtFullHealth,
+    );
+
+    // Destroying a house takes its energy bonus from the owner, which needs the owner account
+    let raid_house = board_action(&game, &raider, lumberjack::instruction::Raid { x: 9, y: 9 });
+    assert_error(
+        act(&mut game, &raider, raid_house.clone()).await,
+        GameErrorCode::BuildingOwnerMissing,
+    );
+    act(&mut game, &raider, on_building_of(raid_house, &owner))
+        .await
+        .unwrap();
+    assert_eq!(
+        board(&mut game).await.data[9][9].building_type,
+        BUILDING_TYPE_EMPTY
+    );
+    assert_eq!(player(&mut game, &owner).await.energy_bonus, 2);
+}
+
+#[tokio::test]
+async fn orders_are_filled_in_parts_with_resources_or_sol() {
+    let (mut game, maker) = started_game().await;
+    let taker = new_player(&mut game).await;
+    edit_player(&mut game, &maker, |player| player.wood = 100).await;
+    edit_player(&mut game, &taker, |player| player.stone = 20).await;
+
+    let invalid = place_order(&maker, 0, (RESOURCE_WOOD, 30), (RESOURCE_WOOD, 12));
+    assert_error(
+        act(&mut game, &maker, invalid).await,
+        GameErrorCode::InvalidOrder,
+    );
+    let place = place_order(&maker, 0, (RESOURCE_WOOD, 30), (RESOURCE_STONE, 12));
+    act(&mut game, &maker, place).await.unwrap();
+    assert_eq!(player(&mut game, &maker).await.wood, 70);
+    let own = fill_order(&maker, &maker, 0, 10);
+    assert_error(act(&mut game, &maker, own).await, GameErrorCode::OwnOrder);
+
+    // A third of the order costs a third of the price
+    let fill = fill_order(&taker, &maker, 0, 10);
+    act(&mut game, &taker, fill).await.unwrap();
+    let taker_data = player(&mut game, &taker).await;
+    assert_eq!((taker_data.wood, taker_data.stone), (10, 16));
+    assert_eq!(player(&mut game, &maker).await.stone, 4);
+    let rest = order(&mut game, &maker, 0).await.unwrap();
+    assert_eq!((rest.sell_amount, rest.buy_amount), (20, 8));
+
+    // The rent of a filled order goes back to the maker
+    let maker_lamports = lamports(&mut game, maker.pubkey()).await;
+    let order_lamports = account(&mut game.context, order_pda(&maker, 0))
+        .await
+        .lamports;
+    let fill = fill_order(&taker, &maker, 0, 20);
+    act(&mut game, &taker, fill).await.unwrap();
+    assert!(order(&mut game, &maker, 0).await.is_none());
+    assert_eq!(player(&mut game, &maker).await.stone, 12);
+    assert_eq!(
+        lamports(&mut game, maker.pubkey()).await,
+        maker_lamports + order_lamports
+    );
+
+    let place = place_order(&maker, 1, (RESOURCE_STONE, 10), (RESOURCE_SOL, 1_000_000));
+    act(&mut game, &maker, place).await.unwrap();
+    let maker_lamports = lamports(&mut game, maker.pubkey()).await;
+    let fill = fill_order(&taker, &maker, 1, 5);
+    act(&mut game, &taker, fill).await.unwrap();
+    assert_eq!(
+        lamports(&mut game, maker.pubkey()).await,
+        maker_lamports + 500_000
+    );
+    assert_eq!(player(&mut game, &taker).await.stone, 20 - 4 - 8 + 5);
+}
+
+#[tokio::test]
+async fn cancel_returns_what_fits_and_keeps_the_rest_on_the_market() {
+    let (mut game, maker) = started_game().await;
+    edit_player(&mut game, &maker, |player| player.wood = 100).await;
+    let place = place_order(&maker, 0, (RESOURCE_WOOD, 90), (RESOURCE_STONE, 30));
+    act(&mut game, &maker, place).await.unwrap();
+
+    // Only 20 wood fit into the storage, the other 70 keep the price of the order
+    edit_player(&mut game, &maker, |player| player.wood = 480).await;
+    let cancel = cancel_order(&maker, 0);
+    act(&mut game, &maker, cancel.clone()).await.unwrap();
+    assert_eq!(player(&mut game, &maker).await.wood, 500);
+    let rest = order(&mut game, &maker, 0).await.unwrap();
+    assert_eq!((rest.sell_amount, rest.buy_amount), (70, 24));
+    assert_error(
+        act(&mut game, &maker, cancel.clone()).await,
+        GameErrorCode::StorageFull,
+    );
+
+    edit_player(&mut game, &maker, |player| player.wood = 0).await;
+    act(&mut game, &maker, cancel).await.unwrap();
+    assert_eq!(player(&mut game, &maker).await.wood, 70);
+    assert!(order(&mut game, &maker, 0).await.is_none());
+}
+
+#[tokio::test]
+async fn resources_are_withdrawn_as_tokens_and_deposited_back() {
+    let (mut game, wallet) = started_game().await;
+    let admin = admin(&mut game.context);
+    let not_admin = create_resource_mint(&wallet, RESOURCE_WOOD);
+    assert_error(
+        send(&mut game.context, &[not_admin], &[&wallet]).await,
+        anchor_lang::error::ErrorCode::ConstraintAddress,
+    );
+    let food = create_resource_mint(&admin, RESOURCE_FOOD);
+    assert_error(
+        send(&mut game.context, &[food], &[&admin]).await,
+        GameErrorCode::ResourceNotTradable,
+    );
+    let create = create_resource_mint(&admin, RESOURCE_WOOD);
+    send(&mut game.context, &[create], &[&admin]).await.unwrap();
+
+    edit_player(&mut game, &wallet, |player| player.wood = 50).await;
+    let withdraw = |amount| {
+        instruction(
+            resource_bridge(&wallet, RESOURCE_WOOD),
+            lumberjack::instruction::WithdrawResources {
+                resource: RESOURCE_WOOD,
+                amount,
+            },
+        )
+    };
+    let deposit = |amount| {
+        instruction(
+            resource_bridge(&wallet, RESOURCE_WOOD),
+            lumberjack::instruction::DepositResources {
+                resource: RESOURCE_WOOD,
+                amount,
+            },
+        )
+    };
+    act(&mut game, &wallet, withdraw(20)).await.unwrap();
+    assert_eq!(player(&mut game, &wallet).await.wood, 30);
+    assert_eq!(token_balance(&mut game, &wallet, RESOURCE_WOOD).await, 20);
+    assert_error(
+        act(&mut game, &wallet, withdraw(40)).await,
+        GameErrorCode::NotEnoughWood,
+    );
+
+    act(&mut game, &wallet, deposit(15)).await.unwrap();
+    assert_eq!(player(&mut game, &wallet).await.wood, 45);
+    assert_eq!(token_balance(&mut game, &wallet, RESOURCE_WOOD).await, 5);
+    let mint_data = account(&mut game.context, resource_mint_pda(RESOURCE_WOOD))
+        .await
+        .data;
+    assert_eq!(
+        spl_token::state::Mint::unpack(&mint_data).unwrap().supply,
+        5
+    );
+
+    // Tokens are only burned for wood which fits into the storage
+    edit_player(&mut game, &wallet, |player| player.wood = 498).await;
+    assert_error(
+        act(&mut game, &wallet, deposit(5)).await,
+        GameErrorCode::StorageFull,
+    );
+    assert_eq!(token_balance(&mut game, &wallet, RESOURCE_WOOD).await, 5);
+}
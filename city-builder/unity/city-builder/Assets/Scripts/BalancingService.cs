@@ -1,5 +1,8 @@
+using System;
+using System.Collections.Generic;
+using System.Numerics;
+using Lumberjack.Accounts;
 using Lumberjack.Types;
-using UnityEngine;
 
 namespace DefaultNamespace
 {
@@ -11,126 +14,329 @@ public class Cost
             public ulong Wood;
             public ulong Stone;
         }
-        
-        private const int BASE_SAWMILL_WOOD_COST = 10;
-        private const int BASE_SAWMILL_STONE_COST = 5;
-        private const int BASE_STONE_MINE_WOOD_COST = 5;
-        private const int BASE_STONE_MINE_STONE_COST = 10;
-
-        // Define the cost increase multiplier per level for the sawmill and stone mine
-        private const float SAWMILL_WOOD_COST_MULTIPLIER = 1.1f; // Increase by 10% per level
-        private const float SAWMILL_STONE_COST_MULTIPLIER = 1.05f; // Increase by 5% per level
-        private const float STONE_MINE_WOOD_COST_MULTIPLIER = 1.05f; // Increase by 5% per level
-        private const float STONE_MINE_STONE_COST_MULTIPLIER = 1.1f; // Increase by 10% per level
 
-        public static ulong RefillEnergyCost = 3;
-
-        public static Cost GetUpgradeCost(TileData tileData)
+        // Mirrors the building definition table of the program in state/building.rs
+        public class CostCurve
         {
-            Cost newCost = new Cost();
-            if (tileData.BuildingType == LumberjackService.BUILDING_TYPE_SAWMILL)
-            {
-                newCost.Wood = CalculateSawmillWoodUpgradeCost(tileData.BuildingLevel);
-                newCost.Stone = CalculateSawmillStoneUpgradeCost(tileData.BuildingLevel);
-            } else if (tileData.BuildingType == LumberjackService.BUILDING_TYPE_MINE)
-            {
-                newCost.Wood = CalculateStoneMineWoodUpgradeCost(tileData.BuildingLevel);
-                newCost.Stone = CalculateStoneMineStoneUpgradeCost(tileData.BuildingLevel);
-            } else if (tileData.BuildingType == LumberjackService.BUILDING_TYPE_GOOD)
-            {
-                newCost.Wood = CalculateGoodWoodUpgradeCost(tileData.BuildingLevel);
-                newCost.Stone = CalculateGoodStoneUpgradeCost(tileData.BuildingLevel);
-            } else if (tileData.BuildingType == LumberjackService.BUILDING_TYPE_EVIL)
+            // Fixed-point scale of the multipliers, 10000 is a multiplier of 1.0
+            public const ulong MULTIPLIER_ONE = 10000;
+            public const uint MAX_CURVE_LEVEL = 1000;
+
+            public ulong Base;
+            public ulong Multiplier;
+
+            public CostCurve(ulong baseCost, ulong multiplier)
             {
-                newCost.Wood = CalculateEvilWoodUpgradeCost(tileData.BuildingLevel);
-                newCost.Stone = CalculateEvilStoneUpgradeCost(tileData.BuildingLevel);
+                Base = baseCost;
+                Multiplier = multiplier;
             }
 
-            return newCost;
-        }
-        
-        public static Cost GetBuildCost(TileConfig tileConfig)
-        {
-            Cost newCost = new Cost();
-            if (tileConfig.building_type == LumberjackService.BUILDING_TYPE_SAWMILL)
-            {
-                newCost.Wood = CalculateSawmillWoodBuildCost();
-                newCost.Stone = CalculateSawmillStoneBuildCost();
-            } else if (tileConfig.building_type == LumberjackService.BUILDING_TYPE_MINE)
+            // Same integer math as state/cost_curve.rs, the power is taken by squaring
+            // and every step is rounded down
+            public ulong At(uint buildingLevel)
             {
-                newCost.Wood = CalculateStoneMineWoodBuildCost();
-                newCost.Stone = CalculateStoneMineStoneBuildCost();
-            }
+                if (buildingLevel > MAX_CURVE_LEVEL)
+                {
+                    return ulong.MaxValue;
+                }
+                if (Base == 0)
+                {
+                    return 0;
+                }
+                BigInteger one = MULTIPLIER_ONE;
+                BigInteger max = ulong.MaxValue * one;
+                BigInteger value = Base * one;
+                BigInteger factor = Multiplier;
+                for (var exponent = buildingLevel; exponent > 0; exponent >>= 1)
+                {
+                    if ((exponent & 1) == 1)
+                    {
+                        value = value * factor / one;
+                        if (value > max)
+                        {
+                            return ulong.MaxValue;
+                        }
+                    }
+                    if (exponent > 1)
+                    {
+                        factor = factor * factor / one;
+                    }
+                }
 
-            return newCost;
+                return (ulong) (value / one);
+            }
         }
 
-        public static ulong CalculateSawmillWoodUpgradeCost(uint buildingLevel)
+        public class BuildingDefinition
         {
-            int sawmillWoodCost = Mathf.RoundToInt(BASE_SAWMILL_WOOD_COST * Mathf.Pow(SAWMILL_WOOD_COST_MULTIPLIER, buildingLevel));
-            return (ulong ) sawmillWoodCost;
+            public ulong BuildCostWood;
+            public ulong BuildCostStone;
+            public CostCurve UpgradeCostWood;
+            public CostCurve UpgradeCostStone;
+            public CostCurve UpgradeTime = new CostCurve(0, CostCurve.MULTIPLIER_ONE);
+            public CostCurve Production;
+            public CostCurve Storage = new CostCurve(0, CostCurve.MULTIPLIER_ONE);
+            public ulong EnergyPerLevel;
+            public ulong StoragePerLevel;
+            public CostCurve Health = new CostCurve(0, CostCurve.MULTIPLIER_ONE);
+            public CostCurve Defense = new CostCurve(0, CostCurve.MULTIPLIER_ONE);
+            public byte DefenseRange;
         }
 
-        public static ulong CalculateSawmillStoneUpgradeCost(uint buildingLevel)
+        private static readonly BuildingDefinition FactionBuilding = new BuildingDefinition
         {
-            int sawmillStoneCost = Mathf.RoundToInt(BASE_SAWMILL_STONE_COST * Mathf.Pow(SAWMILL_STONE_COST_MULTIPLIER, buildingLevel));
-            return (ulong )sawmillStoneCost;
-        }
+            UpgradeCostWood = new CostCurve(15, 11000),
+            UpgradeCostStone = new CostCurve(15, 11000),
+            Production = new CostCurve(0, CostCurve.MULTIPLIER_ONE)
+        };
 
-        public static ulong CalculateStoneMineWoodUpgradeCost(uint buildingLevel)
+        public static readonly Dictionary<byte, BuildingDefinition> Definitions = new Dictionary<byte, BuildingDefinition>
         {
-            int stoneMineWoodCost = Mathf.RoundToInt(BASE_STONE_MINE_WOOD_COST * Mathf.Pow(STONE_MINE_WOOD_COST_MULTIPLIER, buildingLevel));
-            return (ulong )stoneMineWoodCost;
+            {
+                LumberjackService.BUILDING_TYPE_SAWMILL, new BuildingDefinition
+                {
+                    BuildCostStone = 15,
+                    UpgradeCostWood = new CostCurve(10, 11000),
+                    UpgradeCostStone = new CostCurve(5, 10500),
+                    UpgradeTime = new CostCurve(30, 15000),
+                    Production = new CostCurve(5, 11000),
+                    Storage = new CostCurve(100, 12000),
+                    Health = new CostCurve(100, 11000)
+                }
+            },
+            {
+                LumberjackService.BUILDING_TYPE_MINE, new BuildingDefinition
+                {
+                    BuildCostWood = 15,
+                    UpgradeCostWood = new CostCurve(5, 10500),
+                    UpgradeCostStone = new CostCurve(10, 11000),
+                    UpgradeTime = new CostCurve(30, 15000),
+                    Production = new CostCurve(5, 11000),
+                    Storage = new CostCurve(100, 12000),
+                    Health = new CostCurve(100, 11000)
+                }
+            },
+            { LumberjackService.BUILDING_TYPE_GOOD, FactionBuilding },
+            { LumberjackService.BUILDING_TYPE_EVIL, FactionBuilding },
+            {
+                LumberjackService.BUILDING_TYPE_HOUSE, new BuildingDefinition
+                {
+                    BuildCostWood = 20,
+                    BuildCostStone = 10,
+                    UpgradeCostWood = new CostCurve(15, 12000),
+                    UpgradeCostStone = new CostCurve(10, 12000),
+                    UpgradeTime = new CostCurve(60, 15000),
+                    Production = new CostCurve(0, CostCurve.MULTIPLIER_ONE),
+                    EnergyPerLevel = 2,
+                    Health = new CostCurve(150, 11000)
+                }
+            },
+            {
+                LumberjackService.BUILDING_TYPE_FARM, new BuildingDefinition
+                {
+                    BuildCostWood = 15,
+                    BuildCostStone = 5,
+                    UpgradeCostWood = new CostCurve(10, 11000),
+                    UpgradeCostStone = new CostCurve(5, 11000),
+                    UpgradeTime = new CostCurve(30, 15000),
+                    Production = new CostCurve(5, 11000),
+                    Storage = new CostCurve(100, 12000),
+                    Health = new CostCurve(100, 11000)
+                }
+            },
+            {
+                LumberjackService.BUILDING_TYPE_WAREHOUSE, new BuildingDefinition
+                {
+                    BuildCostWood = 25,
+                    BuildCostStone = 25,
+                    UpgradeCostWood = new CostCurve(20, 11500),
+                    UpgradeCostStone = new CostCurve(20, 11500),
+                    UpgradeTime = new CostCurve(60, 15000),
+                    Production = new CostCurve(0, CostCurve.MULTIPLIER_ONE),
+                    StoragePerLevel = 250,
+                    Health = new CostCurve(150, 11000)
+                }
+            },
+            {
+                LumberjackService.BUILDING_TYPE_WALL, new BuildingDefinition
+                {
+                    BuildCostWood = 10,
+                    BuildCostStone = 20,
+                    UpgradeCostWood = new CostCurve(10, 11500),
+                    UpgradeCostStone = new CostCurve(15, 11500),
+                    UpgradeTime = new CostCurve(30, 15000),
+                    Production = new CostCurve(0, CostCurve.MULTIPLIER_ONE),
+                    Health = new CostCurve(300, 12000),
+                    Defense = new CostCurve(10, 11000),
+                    DefenseRange = 1
+                }
+            },
+            {
+                LumberjackService.BUILDING_TYPE_TOWER, new BuildingDefinition
+                {
+                    BuildCostWood = 20,
+                    BuildCostStone = 30,
+                    UpgradeCostWood = new CostCurve(15, 11500),
+                    UpgradeCostStone = new CostCurve(20, 11500),
+                    UpgradeTime = new CostCurve(60, 15000),
+                    Production = new CostCurve(0, CostCurve.MULTIPLIER_ONE),
+                    Health = new CostCurve(200, 12000),
+                    Defense = new CostCurve(15, 11000),
+                    DefenseRange = 2
+                }
+            },
+        };
+
+        public static ulong RefillEnergyCost = 3;
+
+        // Production of the definitions is given per this many seconds
+        public const ulong PRODUCTION_TIME = 60;
+
+        // Tree regrowth, mirrors grown_tile of the program
+        public const long SPROUT_TIME = 300;
+        public const long GROW_TIME = 600;
+
+        private static bool IsGrownTree(TileData tileData, long unixTime)
+        {
+            return tileData.BuildingType == LumberjackService.BUILDING_TYPE_TREE ||
+                   (tileData.BuildingType == LumberjackService.BUILDING_TYPE_SAPLING &&
+                    tileData.BuildingStartTime + GROW_TIME <= unixTime);
         }
 
-        public static ulong CalculateStoneMineStoneUpgradeCost(uint buildingLevel)
+        public static byte GetGrownBuildingType(BoardAccount board, int x, int y, long unixTime)
         {
-            int stoneMineStoneCost = Mathf.RoundToInt(BASE_STONE_MINE_STONE_COST * Mathf.Pow(STONE_MINE_STONE_COST_MULTIPLIER, buildingLevel));
-            return (ulong )stoneMineStoneCost;
+            var sizeX = board.SizeX == 0 ? BoardManager.WIDTH : board.SizeX;
+            var sizeY = board.SizeY == 0 ? BoardManager.HEIGHT : board.SizeY;
+            var tileData = board.Data[x][y];
+            var buildingType = tileData.BuildingType;
+            var startTime = tileData.BuildingStartTime;
+
+            if (buildingType == LumberjackService.BUILDING_TYPE_EMPTY && startTime + SPROUT_TIME <= unixTime)
+            {
+                var hasTreeNeighbour =
+                    (x > 0 && IsGrownTree(board.Data[x - 1][y], unixTime)) ||
+                    (x + 1 < sizeX && IsGrownTree(board.Data[x + 1][y], unixTime)) ||
+                    (y > 0 && IsGrownTree(board.Data[x][y - 1], unixTime)) ||
+                    (y + 1 < sizeY && IsGrownTree(board.Data[x][y + 1], unixTime));
+                if (hasTreeNeighbour)
+                {
+                    buildingType = LumberjackService.BUILDING_TYPE_SAPLING;
+                    startTime += SPROUT_TIME;
+                }
+            }
+
+            if (buildingType == LumberjackService.BUILDING_TYPE_SAPLING && startTime + GROW_TIME <= unixTime)
+            {
+                buildingType = LumberjackService.BUILDING_TYPE_TREE;
+            }
+
+            return buildingType;
         }
 
-        public static ulong CalculateGoodStoneUpgradeCost(uint buildingLevel)
+        public static Cost GetUpgradeCost(TileData tileData)
         {
-            int stoneMineStoneCost = Mathf.RoundToInt(15 * Mathf.Pow(1.1f, buildingLevel));
-            return (ulong )stoneMineStoneCost;
+            Cost newCost = new Cost();
+            if (Definitions.TryGetValue(tileData.BuildingType, out var definition))
+            {
+                newCost.Wood = definition.UpgradeCostWood.At(tileData.BuildingLevel);
+                newCost.Stone = definition.UpgradeCostStone.At(tileData.BuildingLevel);
+            }
+
+            return newCost;
         }
 
-        public static ulong CalculateGoodWoodUpgradeCost(uint buildingLevel)
+        public static Cost GetBuildCost(TileConfig tileConfig)
         {
-            int stoneMineStoneCost = Mathf.RoundToInt(15 * Mathf.Pow(1.1f, buildingLevel));
-            return (ulong )stoneMineStoneCost;
+            Cost newCost = new Cost();
+            if (Definitions.TryGetValue(tileConfig.building_type, out var definition))
+            {
+                newCost.Wood = definition.BuildCostWood;
+                newCost.Stone = definition.BuildCostStone;
+            }
+
+            return newCost;
         }
 
-        public static ulong CalculateEvilStoneUpgradeCost(uint buildingLevel)
+        // Production accrued since the last collection, capped by the storage of the building
+        public static ulong GetCollection(TileData tileData, long unixTime)
         {
-            int stoneMineStoneCost = Mathf.RoundToInt(15 * Mathf.Pow(1.1f, buildingLevel));
-            return (ulong )stoneMineStoneCost;
+            if (!Definitions.TryGetValue(tileData.BuildingType, out var definition) || IsUpgrading(tileData))
+            {
+                return 0;
+            }
+
+            var seconds = (ulong) Math.Max(0, unixTime - tileData.BuildingStartCollectTime);
+            var produced = definition.Production.At(tileData.BuildingLevel) * seconds / PRODUCTION_TIME;
+            return Math.Min(produced, GetStorage(tileData));
         }
 
-        public static ulong CalculateEvilWoodUpgradeCost(uint buildingLevel)
+        public static bool IsUpgrading(TileData tileData)
         {
-            int stoneMineStoneCost = Mathf.RoundToInt(15 * Mathf.Pow(1.1f, buildingLevel));
-            return (ulong )stoneMineStoneCost;
+            return tileData.BuildingStartUpgradeTime != 0;
         }
 
-        public static ulong CalculateSawmillWoodBuildCost()
+        public static long GetUpgradeFinishTime(TileData tileData)
         {
-            return (ulong ) 0;
+            if (!Definitions.TryGetValue(tileData.BuildingType, out var definition))
+            {
+                return tileData.BuildingStartUpgradeTime;
+            }
+
+            return tileData.BuildingStartUpgradeTime + (long) definition.UpgradeTime.At(tileData.BuildingLevel);
         }
 
-        public static ulong CalculateSawmillStoneBuildCost()
+        // Raids, mirrors building_health and raid_defense of the program
+        public const long RAID_DAMAGE = 40;
+        public const ulong MAX_RAID_DEFENSE = 75;
+        public const ulong DEFEND_STONE_COST = 5;
+
+        // A stored health of zero means the building was never damaged
+        public static long GetHealth(TileData tileData)
         {
-            return (ulong ) 15;
+            if (tileData.BuildingHealth > 0 || !Definitions.TryGetValue(tileData.BuildingType, out var definition))
+            {
+                return tileData.BuildingHealth;
+            }
+
+            return (long) definition.Health.At(tileData.BuildingLevel);
         }
 
-        public static ulong CalculateStoneMineWoodBuildCost()
+        public static ulong GetRaidDefense(BoardAccount board, int x, int y)
         {
-            return (ulong )15;
+            var sizeX = board.SizeX == 0 ? BoardManager.WIDTH : board.SizeX;
+            var sizeY = board.SizeY == 0 ? BoardManager.HEIGHT : board.SizeY;
+            var owner = board.Data[x][y].BuildingOwner;
+            ulong defense = 0;
+            for (var defenderX = 0; defenderX < sizeX; defenderX++)
+            {
+                for (var defenderY = 0; defenderY < sizeY; defenderY++)
+                {
+                    var tileData = board.Data[defenderX][defenderY];
+                    if ((defenderX == x && defenderY == y) || tileData.BuildingOwner.Key != owner.Key ||
+                        !Definitions.TryGetValue(tileData.BuildingType, out var definition) || definition.DefenseRange == 0)
+                    {
+                        continue;
+                    }
+
+                    var distance = Math.Max(Math.Abs(defenderX - x), Math.Abs(defenderY - y));
+                    if (distance <= definition.DefenseRange)
+                    {
+                        defense += definition.Defense.At(tileData.BuildingLevel);
+                    }
+                }
+            }
+
+            return Math.Min(defense, MAX_RAID_DEFENSE);
         }
 
-        public static ulong CalculateStoneMineStoneBuildCost()
+        public static ulong GetStorage(TileData tileData)
         {
-            return (ulong )0;
+            if (!Definitions.TryGetValue(tileData.BuildingType, out var definition))
+            {
+                return 0;
+            }
+
+            return definition.Storage.At(tileData.BuildingLevel);
         }
     }
-}
\ No newline at end of file
+}
@@ -14,7 +14,7 @@ public void SetDataUpgradeCost(TileData tiledata)
         {
             BalancingService.Cost upgradeCost = BalancingService.GetUpgradeCost(tiledata);
 
-            SetCost(upgradeCost);
+            SetCost(upgradeCost, LumberjackService.IsPaidFromTreasury(tiledata));
         }
 
         public void SetDataBuildCost(TileConfig config)
@@ -23,17 +23,17 @@ public void SetDataBuildCost(TileConfig config)
             SetCost(cost);
         }
         
-        private void SetCost(BalancingService.Cost upgradeCost)
+        private void SetCost(BalancingService.Cost upgradeCost, bool fromTreasury = false)
         {
             WoodCost.text = upgradeCost.Wood.ToString();
             StoneCost.text = upgradeCost.Stone.ToString();
 
-            var hasEnoughWood = upgradeCost.Wood <= LumberjackService.Instance.CurrentBoardAccount.Wood;
+            var hasEnoughWood = LumberjackService.HasEnoughResources(upgradeCost.Wood, 0, fromTreasury);
             WoodCost.color = hasEnoughWood ? Color.white : Color.red;
-            var hasEnoughStone = upgradeCost.Stone <= LumberjackService.Instance.CurrentBoardAccount.Stone;
+            var hasEnoughStone = LumberjackService.HasEnoughResources(0, upgradeCost.Stone, fromTreasury);
             StoneCost.color = hasEnoughStone ? Color.white : Color.red;
 
-            NotEnoughResources.gameObject.SetActive(!LumberjackService.HasEnoughResources(upgradeCost));
+            NotEnoughResources.gameObject.SetActive(!LumberjackService.HasEnoughResources(upgradeCost, fromTreasury));
         }
     }
 }
\ No newline at end of file
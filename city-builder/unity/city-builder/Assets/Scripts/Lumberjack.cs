@@ -43,6 +43,48 @@ public partial class BoardAccount
 
             public bool GoodWon { get; set; }
 
+            public ulong GameNumber { get; set; }
+
+            public uint GoodMembers { get; set; }
+
+            public uint EvilMembers { get; set; }
+
+            public ulong RewardGame { get; set; }
+
+            public byte RewardFaction { get; set; }
+
+            public uint RewardMembers { get; set; }
+
+            public ulong RewardWood { get; set; }
+
+            public ulong RewardStone { get; set; }
+
+            public byte SizeX { get; set; }
+
+            public byte SizeY { get; set; }
+
+            public ulong Seed { get; set; }
+
+            public PublicKey[] TopContributors { get; set; }
+
+            public ulong[] TopContributions { get; set; }
+
+            public ulong GoodWarScore { get; set; }
+
+            public ulong EvilWarScore { get; set; }
+
+            public ulong WarPool { get; set; }
+
+            public ulong RewardWarScore { get; set; }
+
+            public ulong RewardWarPool { get; set; }
+
+            public ulong RewardWarClaimed { get; set; }
+
+            public ulong RewardWarGame { get; set; }
+
+            public byte RewardWarFaction { get; set; }
+
             public static BoardAccount Deserialize(ReadOnlySpan<byte> _data)
             {
                 int offset = 0;
@@ -79,6 +121,58 @@ public static BoardAccount Deserialize(ReadOnlySpan<byte> _data)
                 offset += 1;
                 result.GoodWon = _data.GetBool(offset);
                 offset += 1;
+                result.GameNumber = _data.GetU64(offset);
+                offset += 8;
+                result.GoodMembers = _data.GetU32(offset);
+                offset += 4;
+                result.EvilMembers = _data.GetU32(offset);
+                offset += 4;
+                result.RewardGame = _data.GetU64(offset);
+                offset += 8;
+                result.RewardFaction = _data.GetU8(offset);
+                offset += 1;
+                result.RewardMembers = _data.GetU32(offset);
+                offset += 4;
+                result.RewardWood = _data.GetU64(offset);
+                offset += 8;
+                result.RewardStone = _data.GetU64(offset);
+                offset += 8;
+                result.SizeX = _data.GetU8(offset);
+                offset += 1;
+                result.SizeY = _data.GetU8(offset);
+                offset += 1;
+                result.Seed = _data.GetU64(offset);
+                offset += 8;
+                result.TopContributors = new PublicKey[3];
+                for (uint resultTopContributorsIdx = 0; resultTopContributorsIdx < 3; resultTopContributorsIdx++)
+                {
+                    result.TopContributors[resultTopContributorsIdx] = _data.GetPubKey(offset);
+                    offset += 32;
+                }
+
+                result.TopContributions = new ulong[3];
+                for (uint resultTopContributionsIdx = 0; resultTopContributionsIdx < 3; resultTopContributionsIdx++)
+                {
+                    result.TopContributions[resultTopContributionsIdx] = _data.GetU64(offset);
+                    offset += 8;
+                }
+
+                result.GoodWarScore = _data.GetU64(offset);
+                offset += 8;
+                result.EvilWarScore = _data.GetU64(offset);
+                offset += 8;
+                result.WarPool = _data.GetU64(offset);
+                offset += 8;
+                result.RewardWarScore = _data.GetU64(offset);
+                offset += 8;
+                result.RewardWarPool = _data.GetU64(offset);
+                offset += 8;
+                result.RewardWarClaimed = _data.GetU64(offset);
+                offset += 8;
+                result.RewardWarGame = _data.GetU64(offset);
+                offset += 8;
+                result.RewardWarFaction = _data.GetU8(offset);
+                offset += 1;
                 return result;
             }
         }
@@ -139,6 +233,48 @@ public partial class PlayerData
 
             public long LastLogin { get; set; }
 
+            public ulong Wood { get; set; }
+
+            public ulong Stone { get; set; }
+
+            public byte Faction { get; set; }
+
+            public ulong FactionGame { get; set; }
+
+            public bool FactionRewardClaimed { get; set; }
+
+            public PublicKey[] AuthorizedPlayers { get; set; }
+
+            public ulong Food { get; set; }
+
+            public ulong EnergyBonus { get; set; }
+
+            public ulong StorageBonus { get; set; }
+
+            public ulong BonusGame { get; set; }
+
+            public ulong Contribution { get; set; }
+
+            public ulong ContributionGame { get; set; }
+
+            public ulong UpgradeSpent { get; set; }
+
+            public ulong ActionsTaken { get; set; }
+
+            public ulong DamageDealt { get; set; }
+
+            public ulong WarScore { get; set; }
+
+            public bool WarRewardClaimed { get; set; }
+
+            public ulong LastWarGame { get; set; }
+
+            public ulong LastWarScore { get; set; }
+
+            public byte LastWarFaction { get; set; }
+
+            public ulong NextOrderId { get; set; }
+
             public static PlayerData Deserialize(ReadOnlySpan<byte> _data)
             {
                 int offset = 0;
@@ -164,6 +300,223 @@ public static PlayerData Deserialize(ReadOnlySpan<byte> _data)
                 offset += 8;
                 result.LastLogin = _data.GetS64(offset);
                 offset += 8;
+                result.Wood = _data.GetU64(offset);
+                offset += 8;
+                result.Stone = _data.GetU64(offset);
+                offset += 8;
+                result.Faction = _data.GetU8(offset);
+                offset += 1;
+                result.FactionGame = _data.GetU64(offset);
+                offset += 8;
+                result.FactionRewardClaimed = _data.GetBool(offset);
+                offset += 1;
+                result.AuthorizedPlayers = new PublicKey[5];
+                for (uint resultAuthorizedPlayersIdx = 0; resultAuthorizedPlayersIdx < 5; resultAuthorizedPlayersIdx++)
+                {
+                    result.AuthorizedPlayers[resultAuthorizedPlayersIdx] = _data.GetPubKey(offset);
+                    offset += 32;
+                }
+
+                result.Food = _data.GetU64(offset);
+                offset += 8;
+                result.EnergyBonus = _data.GetU64(offset);
+                offset += 8;
+                result.StorageBonus = _data.GetU64(offset);
+                offset += 8;
+                result.BonusGame = _data.GetU64(offset);
+                offset += 8;
+                result.Contribution = _data.GetU64(offset);
+                offset += 8;
+                result.ContributionGame = _data.GetU64(offset);
+                offset += 8;
+                result.UpgradeSpent = _data.GetU64(offset);
+                offset += 8;
+                result.ActionsTaken = _data.GetU64(offset);
+                offset += 8;
+                result.DamageDealt = _data.GetU64(offset);
+                offset += 8;
+                result.WarScore = _data.GetU64(offset);
+                offset += 8;
+                result.WarRewardClaimed = _data.GetBool(offset);
+                offset += 1;
+                result.LastWarGame = _data.GetU64(offset);
+                offset += 8;
+                result.LastWarScore = _data.GetU64(offset);
+                offset += 8;
+                result.LastWarFaction = _data.GetU8(offset);
+                offset += 1;
+                result.NextOrderId = _data.GetU64(offset);
+                offset += 8;
+                return result;
+            }
+        }
+
+        public partial class GameConfig
+        {
+            public static ulong ACCOUNT_DISCRIMINATOR => 9610758201818649133UL;
+            public static ReadOnlySpan<byte> ACCOUNT_DISCRIMINATOR_BYTES => new byte[]{45, 146, 146, 33, 170, 69, 96, 133};
+            public static string ACCOUNT_DISCRIMINATOR_B58 => "8d7RZ1Z6Zhn";
+            public PublicKey Treasury { get; set; }
+
+            public ulong EnergyRefillFee { get; set; }
+
+            public ulong EnergyRefillStone { get; set; }
+
+            public ulong MaxEnergy { get; set; }
+
+            public ulong Season { get; set; }
+
+            public ulong WarPoolShare { get; set; }
+
+            public static GameConfig Deserialize(ReadOnlySpan<byte> _data)
+            {
+                int offset = 0;
+                ulong accountHashValue = _data.GetU64(offset);
+                offset += 8;
+                if (accountHashValue != ACCOUNT_DISCRIMINATOR)
+                {
+                    return null;
+                }
+
+                GameConfig result = new GameConfig();
+                result.Treasury = _data.GetPubKey(offset);
+                offset += 32;
+                result.EnergyRefillFee = _data.GetU64(offset);
+                offset += 8;
+                result.EnergyRefillStone = _data.GetU64(offset);
+                offset += 8;
+                result.MaxEnergy = _data.GetU64(offset);
+                offset += 8;
+                result.Season = _data.GetU64(offset);
+                offset += 8;
+                result.WarPoolShare = _data.GetU64(offset);
+                offset += 8;
+                return result;
+            }
+        }
+
+        public partial class MarketOrder
+        {
+            public static ulong ACCOUNT_DISCRIMINATOR => 14600862536240216179UL;
+            public static ReadOnlySpan<byte> ACCOUNT_DISCRIMINATOR_BYTES => new byte[]{115, 56, 232, 54, 30, 175, 160, 202};
+            public static string ACCOUNT_DISCRIMINATOR_B58 => "LGoS9NV9oWZ";
+            public PublicKey Maker { get; set; }
+
+            public PublicKey Authority { get; set; }
+
+            public ulong OrderId { get; set; }
+
+            public byte SellResource { get; set; }
+
+            public ulong SellAmount { get; set; }
+
+            public byte BuyResource { get; set; }
+
+            public ulong BuyAmount { get; set; }
+
+            public long CreatedAt { get; set; }
+
+            public static MarketOrder Deserialize(ReadOnlySpan<byte> _data)
+            {
+                int offset = 0;
+                ulong accountHashValue = _data.GetU64(offset);
+                offset += 8;
+                if (accountHashValue != ACCOUNT_DISCRIMINATOR)
+                {
+                    return null;
+                }
+
+                MarketOrder result = new MarketOrder();
+                result.Maker = _data.GetPubKey(offset);
+                offset += 32;
+                result.Authority = _data.GetPubKey(offset);
+                offset += 32;
+                result.OrderId = _data.GetU64(offset);
+                offset += 8;
+                result.SellResource = _data.GetU8(offset);
+                offset += 1;
+                result.SellAmount = _data.GetU64(offset);
+                offset += 8;
+                result.BuyResource = _data.GetU8(offset);
+                offset += 1;
+                result.BuyAmount = _data.GetU64(offset);
+                offset += 8;
+                result.CreatedAt = _data.GetS64(offset);
+                offset += 8;
+                return result;
+            }
+        }
+
+        public partial class SeasonResult
+        {
+            public static ulong ACCOUNT_DISCRIMINATOR => 12108946219334482681UL;
+            public static ReadOnlySpan<byte> ACCOUNT_DISCRIMINATOR_BYTES => new byte[]{249, 186, 189, 55, 110, 158, 11, 168};
+            public static string ACCOUNT_DISCRIMINATOR_B58 => "imgw4thMCkX";
+            public ulong Season { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public ulong GameNumber { get; set; }
+
+            public byte Winner { get; set; }
+
+            public uint GoodLevel { get; set; }
+
+            public uint EvilLevel { get; set; }
+
+            public uint GoodMembers { get; set; }
+
+            public uint EvilMembers { get; set; }
+
+            public PublicKey[] TopContributors { get; set; }
+
+            public ulong[] TopContributions { get; set; }
+
+            public long ArchivedAt { get; set; }
+
+            public static SeasonResult Deserialize(ReadOnlySpan<byte> _data)
+            {
+                int offset = 0;
+                ulong accountHashValue = _data.GetU64(offset);
+                offset += 8;
+                if (accountHashValue != ACCOUNT_DISCRIMINATOR)
+                {
+                    return null;
+                }
+
+                SeasonResult result = new SeasonResult();
+                result.Season = _data.GetU64(offset);
+                offset += 8;
+                result.Board = _data.GetPubKey(offset);
+                offset += 32;
+                result.GameNumber = _data.GetU64(offset);
+                offset += 8;
+                result.Winner = _data.GetU8(offset);
+                offset += 1;
+                result.GoodLevel = _data.GetU32(offset);
+                offset += 4;
+                result.EvilLevel = _data.GetU32(offset);
+                offset += 4;
+                result.GoodMembers = _data.GetU32(offset);
+                offset += 4;
+                result.EvilMembers = _data.GetU32(offset);
+                offset += 4;
+                result.TopContributors = new PublicKey[3];
+                for (uint resultTopContributorsIdx = 0; resultTopContributorsIdx < 3; resultTopContributorsIdx++)
+                {
+                    result.TopContributors[resultTopContributorsIdx] = _data.GetPubKey(offset);
+                    offset += 32;
+                }
+
+                result.TopContributions = new ulong[3];
+                for (uint resultTopContributionsIdx = 0; resultTopContributionsIdx < 3; resultTopContributionsIdx++)
+                {
+                    result.TopContributions[resultTopContributionsIdx] = _data.GetU64(offset);
+                    offset += 8;
+                }
+
+                result.ArchivedAt = _data.GetS64(offset);
+                offset += 8;
                 return result;
             }
         }
@@ -182,7 +535,39 @@ public enum LumberjackErrorKind : uint
             ProductionNotReadyYet = 6006U,
             BuildingTypeNotCollectable = 6007U,
             NotEnoughStone = 6008U,
-            NotEnoughWood = 6009U
+            NotEnoughWood = 6009U,
+            UnknownFaction = 6010U,
+            AlreadyInFaction = 6011U,
+            NotInFaction = 6012U,
+            GameAlreadyOver = 6013U,
+            NoFactionReward = 6014U,
+            NotBuildingOwner = 6015U,
+            TileHasNoBuilding = 6016U,
+            TooManyAuthorizedPlayers = 6017U,
+            PlayerNotAuthorized = 6018U,
+            InvalidBoardSize = 6019U,
+            TileOutOfBounds = 6020U,
+            BuildingTypeNotBuildable = 6021U,
+            BuildingCantBeTransferred = 6022U,
+            BuildingIsUpgrading = 6023U,
+            NoUpgradeInProgress = 6024U,
+            UpgradeNotFinished = 6025U,
+            NotEnoughFood = 6026U,
+            CostOverflow = 6027U,
+            EnergyAlreadyFull = 6028U,
+            StoneRefillDisabled = 6029U,
+            InvalidGameConfig = 6030U,
+            SeasonNotOver = 6031U,
+            NoWarReward = 6032U,
+            CantRaidBuilding = 6033U,
+            BuildingOwnerMissing = 6034U,
+            BuildingAtFullHealth = 6035U,
+            InvalidOrder = 6036U,
+            InvalidFillAmount = 6037U,
+            StorageFull = 6038U,
+            OwnOrder = 6039U,
+            ResourceNotTradable = 6040U,
+            BoardAlreadyInUse = 6041U
         }
     }
 
@@ -349,6 +734,17 @@ public async Task<Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List
             return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<PlayerData>>(res, resultingAccounts);
         }
 
+        public async Task<Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<MarketOrder>>> GetMarketOrdersAsync(string programAddress, Commitment commitment = Commitment.Finalized)
+        {
+            var list = new List<Solana.Unity.Rpc.Models.MemCmp>{new Solana.Unity.Rpc.Models.MemCmp{Bytes = MarketOrder.ACCOUNT_DISCRIMINATOR_B58, Offset = 0}};
+            var res = await RpcClient.GetProgramAccountsAsync(programAddress, commitment, memCmpList: list);
+            if (!res.WasSuccessful || !(res.Result?.Count > 0))
+                return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<MarketOrder>>(res);
+            List<MarketOrder> resultingAccounts = new List<MarketOrder>(res.Result.Count);
+            resultingAccounts.AddRange(res.Result.Select(result => MarketOrder.Deserialize(Convert.FromBase64String(result.Account.Data[0]))));
+            return new Solana.Unity.Programs.Models.ProgramAccountsResultWrapper<List<MarketOrder>>(res, resultingAccounts);
+        }
+
         public async Task<Solana.Unity.Programs.Models.AccountResultWrapper<BoardAccount>> GetBoardAccountAsync(string accountAddress, Commitment commitment = Commitment.Finalized)
         {
             var res = await RpcClient.GetAccountInfoAsync(accountAddress, commitment);
@@ -367,6 +763,33 @@ public async Task<Solana.Unity.Programs.Models.AccountResultWrapper<GameActionHi
             return new Solana.Unity.Programs.Models.AccountResultWrapper<GameActionHistory>(res, resultingAccount);
         }
 
+        public async Task<Solana.Unity.Programs.Models.AccountResultWrapper<GameConfig>> GetGameConfigAsync(string accountAddress, Commitment commitment = Commitment.Finalized)
+        {
+            var res = await RpcClient.GetAccountInfoAsync(accountAddress, commitment);
+            if (!res.WasSuccessful)
+                return new Solana.Unity.Programs.Models.AccountResultWrapper<GameConfig>(res);
+            var resultingAccount = GameConfig.Deserialize(Convert.FromBase64String(res.Result.Value.Data[0]));
+            return new Solana.Unity.Programs.Models.AccountResultWrapper<GameConfig>(res, resultingAccount);
+        }
+
+        public async Task<Solana.Unity.Programs.Models.AccountResultWrapper<SeasonResult>> GetSeasonResultAsync(string accountAddress, Commitment commitment = Commitment.Finalized)
+        {
+            var res = await RpcClient.GetAccountInfoAsync(accountAddress, commitment);
+            if (!res.WasSuccessful)
+                return new Solana.Unity.Programs.Models.AccountResultWrapper<SeasonResult>(res);
+            var resultingAccount = SeasonResult.Deserialize(Convert.FromBase64String(res.Result.Value.Data[0]));
+            return new Solana.Unity.Programs.Models.AccountResultWrapper<SeasonResult>(res, resultingAccount);
+        }
+
+        public async Task<Solana.Unity.Programs.Models.AccountResultWrapper<MarketOrder>> GetMarketOrderAsync(string accountAddress, Commitment commitment = Commitment.Finalized)
+        {
+            var res = await RpcClient.GetAccountInfoAsync(accountAddress, commitment);
+            if (!res.WasSuccessful)
+                return new Solana.Unity.Programs.Models.AccountResultWrapper<MarketOrder>(res);
+            var resultingAccount = MarketOrder.Deserialize(Convert.FromBase64String(res.Result.Value.Data[0]));
+            return new Solana.Unity.Programs.Models.AccountResultWrapper<MarketOrder>(res, resultingAccount);
+        }
+
         public async Task<Solana.Unity.Programs.Models.AccountResultWrapper<PlayerData>> GetPlayerDataAsync(string accountAddress, Commitment commitment = Commitment.Finalized)
         {
             var res = await RpcClient.GetAccountInfoAsync(accountAddress, commitment);
@@ -418,9 +841,9 @@ public async Task<RequestResult<string>> SendInitPlayerAsync(InitPlayerAccounts
             return await SignAndSendTransaction(instr, feePayer, signingCallback);
         }
 
-        public async Task<RequestResult<string>> SendRestartGameAsync(RestartGameAccounts accounts, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        public async Task<RequestResult<string>> SendRestartGameAsync(RestartGameAccounts accounts, byte sizeX, byte sizeY, ulong seed, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
         {
-            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.RestartGame(accounts, programId);
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.RestartGame(accounts, sizeX, sizeY, seed, programId);
             return await SignAndSendTransaction(instr, feePayer, signingCallback);
         }
 
@@ -454,49 +877,189 @@ public async Task<RequestResult<string>> SendUpdateAsync(UpdateAccounts accounts
             return await SignAndSendTransaction(instr, feePayer, signingCallback);
         }
 
-        public async Task<RequestResult<string>> SendRefillEnergyAsync(RefillEnergyAccounts accounts, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        public async Task<RequestResult<string>> SendRefillEnergyAsync(RefillEnergyAccounts accounts, ulong amount, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
         {
-            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.RefillEnergy(accounts, programId);
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.RefillEnergy(accounts, amount, programId);
             return await SignAndSendTransaction(instr, feePayer, signingCallback);
         }
 
-        protected override Dictionary<uint, ProgramError<LumberjackErrorKind>> BuildErrorsDictionary()
+        public async Task<RequestResult<string>> SendContributeAsync(ContributeAccounts accounts, ulong wood, ulong stone, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
         {
-            return new Dictionary<uint, ProgramError<LumberjackErrorKind>>{{6000U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.NotEnoughEnergy, "Not enough energy")}, {6001U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.TileAlreadyOccupied, "Tile Already Occupied")}, {6002U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.TileCantBeUpgraded, "Tile cant be upgraded")}, {6003U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.TileHasNoTree, "Tile has no tree")}, {6004U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.WrongAuthority, "Wrong Authority")}, {6005U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.TileCantBeCollected, "Tile cant be collected")}, {6006U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.ProductionNotReadyYet, "Production not ready yet")}, {6007U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.BuildingTypeNotCollectable, "Building type not collectable")}, {6008U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.NotEnoughStone, "Not enough stone")}, {6009U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.NotEnoughWood, "Not enough wood")}, };
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.Contribute(accounts, wood, stone, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
         }
-    }
 
-    namespace Program
-    {
-        public class InitPlayerAccounts
+        public async Task<RequestResult<string>> SendJoinFactionAsync(JoinFactionAccounts accounts, byte faction, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
         {
-            public PublicKey Player { get; set; }
-
-            public PublicKey Board { get; set; }
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.JoinFaction(accounts, faction, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
 
-            public PublicKey GameActions { get; set; }
+        public async Task<RequestResult<string>> SendClaimFactionRewardAsync(ClaimFactionRewardAccounts accounts, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.ClaimFactionReward(accounts, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
 
-            public PublicKey Signer { get; set; }
+        public async Task<RequestResult<string>> SendTransferBuildingAsync(TransferBuildingAccounts accounts, byte x, byte y, PublicKey newOwner, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.TransferBuilding(accounts, x, y, newOwner, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
 
-            public PublicKey SystemProgram { get; set; }
+        public async Task<RequestResult<string>> SendDemolishAsync(DemolishAccounts accounts, byte x, byte y, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.Demolish(accounts, x, y, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
         }
 
-        public class RestartGameAccounts
+        public async Task<RequestResult<string>> SendAuthorizePlayerAsync(AuthorizePlayerAccounts accounts, PublicKey authorizedPlayer, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
         {
-            public PublicKey Board { get; set; }
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.AuthorizePlayer(accounts, authorizedPlayer, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
 
-            public PublicKey GameActions { get; set; }
+        public async Task<RequestResult<string>> SendRevokePlayerAsync(RevokePlayerAccounts accounts, PublicKey authorizedPlayer, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.RevokePlayer(accounts, authorizedPlayer, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
 
-            public PublicKey Signer { get; set; }
+        public async Task<RequestResult<string>> SendFinishUpgradeAsync(FinishUpgradeAccounts accounts, byte x, byte y, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.FinishUpgrade(accounts, x, y, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
 
-            public PublicKey SystemProgram { get; set; }
+        public async Task<RequestResult<string>> SendSpeedUpUpgradeAsync(SpeedUpUpgradeAccounts accounts, byte x, byte y, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.SpeedUpUpgrade(accounts, x, y, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
         }
 
-        public class ChopTreeAccounts
+        public async Task<RequestResult<string>> SendPlantTreeAsync(PlantTreeAccounts accounts, byte x, byte y, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
         {
-            public PublicKey SessionToken { get; set; }
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.PlantTree(accounts, x, y, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
 
-            public PublicKey Board { get; set; }
+        public async Task<RequestResult<string>> SendUpdateGameConfigAsync(UpdateGameConfigAccounts accounts, PublicKey treasury, ulong energyRefillFee, ulong energyRefillStone, ulong maxEnergy, ulong warPoolShare, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.UpdateGameConfig(accounts, treasury, energyRefillFee, energyRefillStone, maxEnergy, warPoolShare, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
+
+        public async Task<RequestResult<string>> SendRefillEnergyWithStoneAsync(RefillEnergyWithStoneAccounts accounts, ulong amount, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.RefillEnergyWithStone(accounts, amount, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
+
+        public async Task<RequestResult<string>> SendClaimWarRewardAsync(ClaimWarRewardAccounts accounts, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.ClaimWarReward(accounts, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
+
+        public async Task<RequestResult<string>> SendRaidAsync(RaidAccounts accounts, byte x, byte y, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.Raid(accounts, x, y, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
+
+        public async Task<RequestResult<string>> SendDefendAsync(DefendAccounts accounts, byte x, byte y, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.Defend(accounts, x, y, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
+
+        public async Task<RequestResult<string>> SendPlaceOrderAsync(PlaceOrderAccounts accounts, byte sellResource, ulong sellAmount, byte buyResource, ulong buyAmount, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.PlaceOrder(accounts, sellResource, sellAmount, buyResource, buyAmount, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
+
+        public async Task<RequestResult<string>> SendCancelOrderAsync(CancelOrderAccounts accounts, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.CancelOrder(accounts, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
+
+        public async Task<RequestResult<string>> SendFillOrderAsync(FillOrderAccounts accounts, ulong amount, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.FillOrder(accounts, amount, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
+
+        public async Task<RequestResult<string>> SendCreateResourceMintAsync(CreateResourceMintAccounts accounts, byte resource, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.CreateResourceMint(accounts, resource, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
+
+        public async Task<RequestResult<string>> SendWithdrawResourcesAsync(WithdrawResourcesAccounts accounts, byte resource, ulong amount, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.WithdrawResources(accounts, resource, amount, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
+
+        public async Task<RequestResult<string>> SendDepositResourcesAsync(DepositResourcesAccounts accounts, byte resource, ulong amount, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.DepositResources(accounts, resource, amount, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
+
+        public async Task<RequestResult<string>> SendMigrateLegacyBoardAsync(MigrateLegacyBoardAccounts accounts, PublicKey feePayer, Func<byte[], PublicKey, byte[]> signingCallback, PublicKey programId)
+        {
+            Solana.Unity.Rpc.Models.TransactionInstruction instr = Program.LumberjackProgram.MigrateLegacyBoard(accounts, programId);
+            return await SignAndSendTransaction(instr, feePayer, signingCallback);
+        }
+
+        protected override Dictionary<uint, ProgramError<LumberjackErrorKind>> BuildErrorsDictionary()
+        {
+            return new Dictionary<uint, ProgramError<LumberjackErrorKind>>{{6000U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.NotEnoughEnergy, "Not enough energy")}, {6001U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.TileAlreadyOccupied, "Tile Already Occupied")}, {6002U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.TileCantBeUpgraded, "Tile cant be upgraded")}, {6003U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.TileHasNoTree, "Tile has no tree")}, {6004U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.WrongAuthority, "Wrong Authority")}, {6005U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.TileCantBeCollected, "Tile cant be collected")}, {6006U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.ProductionNotReadyYet, "Production not ready yet")}, {6007U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.BuildingTypeNotCollectable, "Building type not collectable")}, {6008U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.NotEnoughStone, "Not enough stone")}, {6009U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.NotEnoughWood, "Not enough wood")}, {6010U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.UnknownFaction, "Unknown faction")}, {6011U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.AlreadyInFaction, "Player already joined a faction in this game")}, {6012U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.NotInFaction, "Only members of the faction can upgrade its building")}, {6013U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.GameAlreadyOver, "Game is already over")}, {6014U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.NoFactionReward, "No faction reward to claim")}, {6015U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.NotBuildingOwner, "Only the owner or authorised players can use this building")}, {6016U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.TileHasNoBuilding, "Tile has no building")}, {6017U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.TooManyAuthorizedPlayers, "No room to authorise more players")}, {6018U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.PlayerNotAuthorized, "Player is not authorised")}, {6019U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.InvalidBoardSize, "Invalid board size")}, {6020U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.TileOutOfBounds, "Tile is outside of the board")}, {6021U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.BuildingTypeNotBuildable, "Building type can not be built")}, {6022U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.BuildingCantBeTransferred, "Houses and warehouses can not be transferred")}, {6023U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.BuildingIsUpgrading, "Building is being upgraded")}, {6024U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.NoUpgradeInProgress, "Building has no upgrade in progress")}, {6025U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.UpgradeNotFinished, "Upgrade not finished yet")}, {6026U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.NotEnoughFood, "Not enough food")}, {6027U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.CostOverflow, "Cost curve value out of range")}, {6028U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.EnergyAlreadyFull, "Energy is already full")}, {6029U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.StoneRefillDisabled, "Refilling energy with stone is disabled")}, {6030U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.InvalidGameConfig, "Invalid game config")}, {6031U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.SeasonNotOver, "Season is not over yet")}, {6032U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.NoWarReward, "No war reward to claim")}, {6033U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.CantRaidBuilding, "Only buildings of other players can be raided")}, {6034U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.BuildingOwnerMissing, "The player account of the building owner is missing")}, {6035U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.BuildingAtFullHealth, "Building is at full health")}, {6036U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.InvalidOrder, "Orders sell wood or stone for a different resource or SOL")}, {6037U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.InvalidFillAmount, "Amount is more than the order has left or too small to be paid")}, {6038U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.StorageFull, "Not enough storage for the resources")}, {6039U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.OwnOrder, "Orders can't be filled by their maker")}, {6040U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.ResourceNotTradable, "Only wood and stone can be traded")}, {6041U, new ProgramError<LumberjackErrorKind>(LumberjackErrorKind.BoardAlreadyInUse, "The board of the season is already in use")}, };
+        }
+    }
+
+    namespace Program
+    {
+        public class InitPlayerAccounts
+        {
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Signer { get; set; }
+
+            public PublicKey SystemProgram { get; set; }
+        }
+
+        public class RestartGameAccounts
+        {
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey NextBoard { get; set; }
+
+            public PublicKey SeasonResult { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Signer { get; set; }
+
+            public PublicKey SystemProgram { get; set; }
+        }
+
+        public class ChopTreeAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
 
             public PublicKey GameActions { get; set; }
 
@@ -504,6 +1067,8 @@ public class ChopTreeAccounts
 
             public PublicKey Player { get; set; }
 
+            public PublicKey GameConfig { get; set; }
+
             public PublicKey Signer { get; set; }
         }
 
@@ -519,6 +1084,8 @@ public class BuildAccounts
 
             public PublicKey Player { get; set; }
 
+            public PublicKey GameConfig { get; set; }
+
             public PublicKey Signer { get; set; }
         }
 
@@ -534,6 +1101,8 @@ public class UpgradeAccounts
 
             public PublicKey Player { get; set; }
 
+            public PublicKey GameConfig { get; set; }
+
             public PublicKey Signer { get; set; }
         }
 
@@ -549,6 +1118,8 @@ public class CollectAccounts
 
             public PublicKey Player { get; set; }
 
+            public PublicKey GameConfig { get; set; }
+
             public PublicKey Signer { get; set; }
         }
 
@@ -556,63 +1127,640 @@ public class UpdateAccounts
         {
             public PublicKey SessionToken { get; set; }
 
-            public PublicKey Board { get; set; }
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class RefillEnergyAccounts
+        {
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+
+            public PublicKey Treasury { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey RewardVault { get; set; }
+
+            public PublicKey SystemProgram { get; set; }
+        }
+
+        public class ContributeAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class JoinFactionAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class ClaimFactionRewardAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class TransferBuildingAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class DemolishAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class AuthorizePlayerAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class RevokePlayerAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class FinishUpgradeAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class SpeedUpUpgradeAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class PlantTreeAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class UpdateGameConfigAccounts
+        {
+            public PublicKey Signer { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey SystemProgram { get; set; }
+        }
+
+        public class RefillEnergyWithStoneAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class ClaimWarRewardAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey RewardVault { get; set; }
+
+            public PublicKey Authority { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class RaidAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class DefendAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey GameActions { get; set; }
+
+            public PublicKey Avatar { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class PlaceOrderAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey Order { get; set; }
+
+            public PublicKey Signer { get; set; }
+
+            public PublicKey SystemProgram { get; set; }
+        }
+
+        public class CancelOrderAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey Order { get; set; }
+
+            public PublicKey Authority { get; set; }
+
+            public PublicKey Signer { get; set; }
+        }
+
+        public class FillOrderAccounts
+        {
+            public PublicKey SessionToken { get; set; }
+
+            public PublicKey Player { get; set; }
+
+            public PublicKey Order { get; set; }
+
+            public PublicKey Maker { get; set; }
+
+            public PublicKey MakerAuthority { get; set; }
+
+            public PublicKey Signer { get; set; }
+
+            public PublicKey SystemProgram { get; set; }
+        }
+
+        public class CreateResourceMintAccounts
+        {
+            public PublicKey Signer { get; set; }
+
+            public PublicKey ResourceMint { get; set; }
+
+            public PublicKey TokenProgram { get; set; }
+
+            public PublicKey SystemProgram { get; set; }
+
+            public PublicKey Rent { get; set; }
+        }
+
+        public class WithdrawResourcesAccounts
+        {
+            public PublicKey Player { get; set; }
+
+            public PublicKey ResourceMint { get; set; }
+
+            public PublicKey PlayerTokenAccount { get; set; }
+
+            public PublicKey Signer { get; set; }
+
+            public PublicKey TokenProgram { get; set; }
+
+            public PublicKey AssociatedTokenProgram { get; set; }
+
+            public PublicKey SystemProgram { get; set; }
+        }
+
+        public class DepositResourcesAccounts
+        {
+            public PublicKey Player { get; set; }
+
+            public PublicKey ResourceMint { get; set; }
+
+            public PublicKey PlayerTokenAccount { get; set; }
+
+            public PublicKey Signer { get; set; }
+
+            public PublicKey TokenProgram { get; set; }
+
+            public PublicKey AssociatedTokenProgram { get; set; }
+
+            public PublicKey SystemProgram { get; set; }
+        }
+
+        public class MigrateLegacyBoardAccounts
+        {
+            public PublicKey Signer { get; set; }
+
+            public PublicKey GameConfig { get; set; }
+
+            public PublicKey LegacyBoard { get; set; }
+
+            public PublicKey Board { get; set; }
+
+            public PublicKey SystemProgram { get; set; }
+        }
+
+        public static class LumberjackProgram
+        {
+            public static Solana.Unity.Rpc.Models.TransactionInstruction InitPlayer(InitPlayerAccounts accounts, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(4819994211046333298UL, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction RestartGame(RestartGameAccounts accounts, byte sizeX, byte sizeY, ulong seed, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.NextBoard, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.SeasonResult, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(10140096924326872336UL, offset);
+                offset += 8;
+                _data.WriteU8(sizeX, offset);
+                offset += 1;
+                _data.WriteU8(sizeY, offset);
+                offset += 1;
+                _data.WriteU64(seed, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction ChopTree(ChopTreeAccounts accounts, byte x, byte y, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(2027946759707441272UL, offset);
+                offset += 8;
+                _data.WriteU8(x, offset);
+                offset += 1;
+                _data.WriteU8(y, offset);
+                offset += 1;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction Build(BuildAccounts accounts, byte x, byte y, byte buildingType, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(1817356094846029497UL, offset);
+                offset += 8;
+                _data.WriteU8(x, offset);
+                offset += 1;
+                _data.WriteU8(y, offset);
+                offset += 1;
+                _data.WriteU8(buildingType, offset);
+                offset += 1;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction Upgrade(UpgradeAccounts accounts, byte x, byte y, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(1920037355368607471UL, offset);
+                offset += 8;
+                _data.WriteU8(x, offset);
+                offset += 1;
+                _data.WriteU8(y, offset);
+                offset += 1;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
 
-            public PublicKey GameActions { get; set; }
+            public static Solana.Unity.Rpc.Models.TransactionInstruction Collect(CollectAccounts accounts, byte x, byte y, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(17028780968808427472UL, offset);
+                offset += 8;
+                _data.WriteU8(x, offset);
+                offset += 1;
+                _data.WriteU8(y, offset);
+                offset += 1;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
 
-            public PublicKey Avatar { get; set; }
+            public static Solana.Unity.Rpc.Models.TransactionInstruction Update(UpdateAccounts accounts, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(9222597562720635099UL, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
 
-            public PublicKey Player { get; set; }
+            public static Solana.Unity.Rpc.Models.TransactionInstruction RefillEnergy(RefillEnergyAccounts accounts, ulong amount, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Treasury, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.RewardVault, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(12683479030383825657UL, offset);
+                offset += 8;
+                _data.WriteU64(amount, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
 
-            public PublicKey Signer { get; set; }
-        }
+            public static Solana.Unity.Rpc.Models.TransactionInstruction Contribute(ContributeAccounts accounts, ulong wood, ulong stone, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(6903173943470072146UL, offset);
+                offset += 8;
+                _data.WriteU64(wood, offset);
+                offset += 8;
+                _data.WriteU64(stone, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
 
-        public class RefillEnergyAccounts
-        {
-            public PublicKey Player { get; set; }
+            public static Solana.Unity.Rpc.Models.TransactionInstruction JoinFaction(JoinFactionAccounts accounts, byte faction, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(10755544504998376406UL, offset);
+                offset += 8;
+                _data.WriteU8(faction, offset);
+                offset += 1;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
 
-            public PublicKey Signer { get; set; }
+            public static Solana.Unity.Rpc.Models.TransactionInstruction ClaimFactionReward(ClaimFactionRewardAccounts accounts, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(10637625454282954823UL, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
 
-            public PublicKey Treasury { get; set; }
+            public static Solana.Unity.Rpc.Models.TransactionInstruction TransferBuilding(TransferBuildingAccounts accounts, byte x, byte y, PublicKey newOwner, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(7213844343122715583UL, offset);
+                offset += 8;
+                _data.WriteU8(x, offset);
+                offset += 1;
+                _data.WriteU8(y, offset);
+                offset += 1;
+                _data.WritePubKey(newOwner, offset);
+                offset += 32;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
 
-            public PublicKey SystemProgram { get; set; }
-        }
+            public static Solana.Unity.Rpc.Models.TransactionInstruction Demolish(DemolishAccounts accounts, byte x, byte y, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(6467463098686221477UL, offset);
+                offset += 8;
+                _data.WriteU8(x, offset);
+                offset += 1;
+                _data.WriteU8(y, offset);
+                offset += 1;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
 
-        public static class LumberjackProgram
-        {
-            public static Solana.Unity.Rpc.Models.TransactionInstruction InitPlayer(InitPlayerAccounts accounts, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction AuthorizePlayer(AuthorizePlayerAccounts accounts, PublicKey authorizedPlayer, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
-                _data.WriteU64(4819994211046333298UL, offset);
+                _data.WriteU64(3403846816748911745UL, offset);
                 offset += 8;
+                _data.WritePubKey(authorizedPlayer, offset);
+                offset += 32;
                 byte[] resultData = new byte[offset];
                 Array.Copy(_data, resultData, offset);
                 return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
             }
 
-            public static Solana.Unity.Rpc.Models.TransactionInstruction RestartGame(RestartGameAccounts accounts, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction RevokePlayer(RevokePlayerAccounts accounts, PublicKey authorizedPlayer, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
-                _data.WriteU64(10140096924326872336UL, offset);
+                _data.WriteU64(7169127969771167069UL, offset);
                 offset += 8;
+                _data.WritePubKey(authorizedPlayer, offset);
+                offset += 32;
                 byte[] resultData = new byte[offset];
                 Array.Copy(_data, resultData, offset);
                 return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
             }
 
-            public static Solana.Unity.Rpc.Models.TransactionInstruction ChopTree(ChopTreeAccounts accounts, byte x, byte y, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction FinishUpgrade(FinishUpgradeAccounts accounts, byte x, byte y, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
-                _data.WriteU64(2027946759707441272UL, offset);
+                _data.WriteU64(7599337740711610409UL, offset);
                 offset += 8;
                 _data.WriteU8(x, offset);
                 offset += 1;
@@ -623,32 +1771,98 @@ public static Solana.Unity.Rpc.Models.TransactionInstruction ChopTree(ChopTreeAc
                 return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
             }
 
-            public static Solana.Unity.Rpc.Models.TransactionInstruction Build(BuildAccounts accounts, byte x, byte y, byte buildingType, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction SpeedUpUpgrade(SpeedUpUpgradeAccounts accounts, byte x, byte y, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
-                _data.WriteU64(1817356094846029497UL, offset);
+                _data.WriteU64(3410835724395820274UL, offset);
                 offset += 8;
                 _data.WriteU8(x, offset);
                 offset += 1;
                 _data.WriteU8(y, offset);
                 offset += 1;
-                _data.WriteU8(buildingType, offset);
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction PlantTree(PlantTreeAccounts accounts, byte x, byte y, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(4487227172208894396UL, offset);
+                offset += 8;
+                _data.WriteU8(x, offset);
+                offset += 1;
+                _data.WriteU8(y, offset);
                 offset += 1;
                 byte[] resultData = new byte[offset];
                 Array.Copy(_data, resultData, offset);
                 return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
             }
 
-            public static Solana.Unity.Rpc.Models.TransactionInstruction Upgrade(UpgradeAccounts accounts, byte x, byte y, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction UpdateGameConfig(UpdateGameConfigAccounts accounts, PublicKey treasury, ulong energyRefillFee, ulong energyRefillStone, ulong maxEnergy, ulong warPoolShare, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
-                _data.WriteU64(1920037355368607471UL, offset);
+                _data.WriteU64(9240742507242279604UL, offset);
+                offset += 8;
+                _data.WritePubKey(treasury, offset);
+                offset += 32;
+                _data.WriteU64(energyRefillFee, offset);
+                offset += 8;
+                _data.WriteU64(energyRefillStone, offset);
+                offset += 8;
+                _data.WriteU64(maxEnergy, offset);
+                offset += 8;
+                _data.WriteU64(warPoolShare, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction RefillEnergyWithStone(RefillEnergyWithStoneAccounts accounts, ulong amount, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(8056333375333300310UL, offset);
+                offset += 8;
+                _data.WriteU64(amount, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction ClaimWarReward(ClaimWarRewardAccounts accounts, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.RewardVault, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Authority, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(9874053927252340275UL, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction Raid(RaidAccounts accounts, byte x, byte y, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(1570192391570005224UL, offset);
                 offset += 8;
                 _data.WriteU8(x, offset);
                 offset += 1;
@@ -659,13 +1873,13 @@ public static Solana.Unity.Rpc.Models.TransactionInstruction Upgrade(UpgradeAcco
                 return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
             }
 
-            public static Solana.Unity.Rpc.Models.TransactionInstruction Collect(CollectAccounts accounts, byte x, byte y, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction Defend(DefendAccounts accounts, byte x, byte y, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
-                _data.WriteU64(17028780968808427472UL, offset);
+                _data.WriteU64(6314476194112694670UL, offset);
                 offset += 8;
                 _data.WriteU8(x, offset);
                 offset += 1;
@@ -676,26 +1890,111 @@ public static Solana.Unity.Rpc.Models.TransactionInstruction Collect(CollectAcco
                 return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
             }
 
-            public static Solana.Unity.Rpc.Models.TransactionInstruction Update(UpdateAccounts accounts, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction PlaceOrder(PlaceOrderAccounts accounts, byte sellResource, ulong sellAmount, byte buyResource, ulong buyAmount, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameActions, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Avatar, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Order, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
-                _data.WriteU64(9222597562720635099UL, offset);
+                _data.WriteU64(7665269973393850931UL, offset);
+                offset += 8;
+                _data.WriteU8(sellResource, offset);
+                offset += 1;
+                _data.WriteU64(sellAmount, offset);
+                offset += 8;
+                _data.WriteU8(buyResource, offset);
+                offset += 1;
+                _data.WriteU64(buyAmount, offset);
                 offset += 8;
                 byte[] resultData = new byte[offset];
                 Array.Copy(_data, resultData, offset);
                 return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
             }
 
-            public static Solana.Unity.Rpc.Models.TransactionInstruction RefillEnergy(RefillEnergyAccounts accounts, PublicKey programId)
+            public static Solana.Unity.Rpc.Models.TransactionInstruction CancelOrder(CancelOrderAccounts accounts, PublicKey programId)
             {
                 List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
-                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Treasury, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Order, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Authority, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true)};
                 byte[] _data = new byte[1200];
                 int offset = 0;
-                _data.WriteU64(12683479030383825657UL, offset);
+                _data.WriteU64(9574425247284560223UL, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction FillOrder(FillOrderAccounts accounts, ulong amount, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SessionToken == null ? programId : accounts.SessionToken, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Order, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Maker, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.MakerAuthority, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(11711768916265237224UL, offset);
+                offset += 8;
+                _data.WriteU64(amount, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction CreateResourceMint(CreateResourceMintAccounts accounts, byte resource, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.ResourceMint, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.TokenProgram, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.Rent, false)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(5301582015213358486UL, offset);
+                offset += 8;
+                _data.WriteU8(resource, offset);
+                offset += 1;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction WithdrawResources(WithdrawResourcesAccounts accounts, byte resource, ulong amount, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.ResourceMint, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.PlayerTokenAccount, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.TokenProgram, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.AssociatedTokenProgram, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(4526436423692450039UL, offset);
+                offset += 8;
+                _data.WriteU8(resource, offset);
+                offset += 1;
+                _data.WriteU64(amount, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction DepositResources(DepositResourcesAccounts accounts, byte resource, ulong amount, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Player, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.ResourceMint, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.PlayerTokenAccount, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.TokenProgram, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.AssociatedTokenProgram, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(16338788120519742806UL, offset);
+                offset += 8;
+                _data.WriteU8(resource, offset);
+                offset += 1;
+                _data.WriteU64(amount, offset);
+                offset += 8;
+                byte[] resultData = new byte[offset];
+                Array.Copy(_data, resultData, offset);
+                return new Solana.Unity.Rpc.Models.TransactionInstruction{Keys = keys, ProgramId = programId.KeyBytes, Data = resultData};
+            }
+
+            public static Solana.Unity.Rpc.Models.TransactionInstruction MigrateLegacyBoard(MigrateLegacyBoardAccounts accounts, PublicKey programId)
+            {
+                List<Solana.Unity.Rpc.Models.AccountMeta> keys = new()
+                {Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Signer, true), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.GameConfig, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.LegacyBoard, false), Solana.Unity.Rpc.Models.AccountMeta.Writable(accounts.Board, false), Solana.Unity.Rpc.Models.AccountMeta.ReadOnly(accounts.SystemProgram, false)};
+                byte[] _data = new byte[1200];
+                int offset = 0;
+                _data.WriteU64(1125185924516518633UL, offset);
                 offset += 8;
                 byte[] resultData = new byte[offset];
                 Array.Copy(_data, resultData, offset);
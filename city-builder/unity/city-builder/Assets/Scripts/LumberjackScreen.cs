@@ -139,7 +139,7 @@ private void UpdateContent()
         
         while (
             timePassed >= LumberjackService.TIME_TO_REFILL_ENERGY &&
-            LumberjackService.Instance.CurrentPlayerData.Energy < LumberjackService.MAX_ENERGY
+            LumberjackService.Instance.CurrentPlayerData.Energy < LumberjackService.GetMaxEnergy(LumberjackService.Instance.CurrentPlayerData)
         ) {
             LumberjackService.Instance.CurrentPlayerData.Energy += 1;
             LumberjackService.Instance.CurrentPlayerData.LastLogin += LumberjackService.TIME_TO_REFILL_ENERGY;
@@ -158,11 +158,8 @@ private void UpdateContent()
         }
         
         EnergyAmountText.text = LumberjackService.Instance.CurrentPlayerData.Energy.ToString();
-        if (LumberjackService.Instance.CurrentBoardAccount != null)
-        {
-            WoodAmountText.text = LumberjackService.Instance.CurrentBoardAccount.Wood.ToString();
-            StoneAmountText.text = LumberjackService.Instance.CurrentBoardAccount.Stone.ToString();
-        }
+        WoodAmountText.text = LumberjackService.Instance.CurrentPlayerData.Wood.ToString();
+        StoneAmountText.text = LumberjackService.Instance.CurrentPlayerData.Stone.ToString();
     }
 
     private void OnChuckWoodSessionButtonClicked()
@@ -31,6 +31,23 @@ public class LumberjackService : MonoBehaviour
     public const byte BUILDING_TYPE_MINE = 3;
     public const byte BUILDING_TYPE_GOOD = 4;
     public const byte BUILDING_TYPE_EVIL = 5;
+    public const byte BUILDING_TYPE_STONE_DEPOSIT = 6;
+    public const byte BUILDING_TYPE_WATER = 7;
+    public const byte BUILDING_TYPE_HOUSE = 8;
+    public const byte BUILDING_TYPE_FARM = 9;
+    public const byte BUILDING_TYPE_WAREHOUSE = 10;
+    public const byte BUILDING_TYPE_SAPLING = 11;
+    public const byte BUILDING_TYPE_WALL = 12;
+    public const byte BUILDING_TYPE_TOWER = 13;
+
+    // Resources of market orders, orders can ask for SOL but only sell wood or stone
+    public const byte RESOURCE_WOOD = 1;
+    public const byte RESOURCE_STONE = 2;
+    public const byte RESOURCE_SOL = 4;
+
+    public const byte FACTION_NONE = 0;
+    public const byte FACTION_GOOD = 1;
+    public const byte FACTION_EVIL = 2;
     
     public static LumberjackService Instance { get; private set; }
     public static Action<PlayerData> OnPlayerDataChanged;
@@ -41,11 +58,14 @@ public class LumberjackService : MonoBehaviour
     public PlayerData CurrentPlayerData;
     public BoardAccount CurrentBoardAccount;
     public GameActionHistory CurrentGameActionHistory;
+    public GameConfig CurrentGameConfig;
 
     private SessionWallet sessionWallet;
     private PublicKey PlayerDataPDA;
     private PublicKey BoardPDA;
     private PublicKey GameActionsPDA;
+    private PublicKey GameConfigPDA;
+    private PublicKey RewardVaultPDA;
     private bool _isInitialized;
     private LumberjackClient lumberjackClient;
     private int transactionsInProgress;
@@ -86,14 +106,18 @@ private async void OnLogin(Account account)
                 {Encoding.UTF8.GetBytes("player"), account.PublicKey.KeyBytes},
             LumberjackProgramIdPubKey, out PlayerDataPDA, out byte bump);
 
-        PublicKey.TryFindProgramAddress(new[]
-                {Encoding.UTF8.GetBytes("board")},
-            LumberjackProgramIdPubKey, out BoardPDA, out byte bump2);
-
         PublicKey.TryFindProgramAddress(new[]
                 {Encoding.UTF8.GetBytes("gameActions")},
             LumberjackProgramIdPubKey, out GameActionsPDA, out byte bump3);
 
+        PublicKey.TryFindProgramAddress(new[]
+                {Encoding.UTF8.GetBytes("gameConfig")},
+            LumberjackProgramIdPubKey, out GameConfigPDA, out byte bump4);
+
+        PublicKey.TryFindProgramAddress(new[]
+                {Encoding.UTF8.GetBytes("rewardVault")},
+            LumberjackProgramIdPubKey, out RewardVaultPDA, out byte bump5);
+
         lumberjackClient = new LumberjackClient(Web3.Rpc, Web3.WsRpc, LumberjackProgramIdPubKey);
         ServiceFactory.Resolve<SolPlayWebSocketService>().Connect(Web3.WsRpc.NodeAddress.AbsoluteUri);
         await SubscribeToPlayerDataUpdates();
@@ -107,8 +131,78 @@ public bool IsInitialized()
         return _isInitialized;
     }
 
+    // Every season has its own board, the current season is stored in the game config
+    private async Task LoadGameConfig()
+    {
+        try
+        {
+            var gameConfig = await lumberjackClient.GetGameConfigAsync(GameConfigPDA, Commitment.Confirmed);
+            if (gameConfig.ParsedResult != null)
+            {
+                CurrentGameConfig = gameConfig.ParsedResult;
+            }
+        }
+        catch (Exception e)
+        {
+            Debug.Log("gameConfig not available " + e.Message);
+        }
+
+        BoardPDA = GetBoardPDA(GetCurrentSeason());
+    }
+
+    public ulong GetCurrentSeason()
+    {
+        return CurrentGameConfig != null ? CurrentGameConfig.Season : 0;
+    }
+
+    public PublicKey GetBoardPDA(ulong season)
+    {
+        PublicKey.TryFindProgramAddress(new[]
+                {Encoding.UTF8.GetBytes("board"), GetLittleEndianBytes(season)},
+            LumberjackProgramIdPubKey, out PublicKey boardPDA, out byte bump);
+        return boardPDA;
+    }
+
+    public PublicKey GetSeasonResultPDA(ulong season)
+    {
+        PublicKey.TryFindProgramAddress(new[]
+                {Encoding.UTF8.GetBytes("seasonResult"), GetLittleEndianBytes(season)},
+            LumberjackProgramIdPubKey, out PublicKey seasonResultPDA, out byte bump);
+        return seasonResultPDA;
+    }
+
+    public PublicKey GetOrderPDA(PublicKey maker, ulong orderId)
+    {
+        PublicKey.TryFindProgramAddress(new[]
+                {Encoding.UTF8.GetBytes("order"), maker.KeyBytes, GetLittleEndianBytes(orderId)},
+            LumberjackProgramIdPubKey, out PublicKey orderPDA, out byte bump);
+        return orderPDA;
+    }
+
+    // SPL mint of wood or stone, created by the admin with create_resource_mint
+    public PublicKey GetResourceMintPDA(byte resource)
+    {
+        PublicKey.TryFindProgramAddress(new[]
+                {Encoding.UTF8.GetBytes("resourceMint"), new[] {resource}},
+            LumberjackProgramIdPubKey, out PublicKey resourceMintPDA, out byte bump);
+        return resourceMintPDA;
+    }
+
+    // u64 seeds of the program are little endian
+    private static byte[] GetLittleEndianBytes(ulong value)
+    {
+        var bytes = BitConverter.GetBytes(value);
+        if (!BitConverter.IsLittleEndian)
+        {
+            Array.Reverse(bytes);
+        }
+
+        return bytes;
+    }
+
     private async Task SubscribeToPlayerDataUpdates()
     {
+        await LoadGameConfig();
         AccountResultWrapper<PlayerData> playerData = null;
         
         try
@@ -161,6 +255,19 @@ private async Task SubscribeToPlayerDataUpdates()
             Debug.Log("gameActionHistroy not available " + e.Message);
         }
 
+        ServiceFactory.Resolve<SolPlayWebSocketService>().SubscribeToPubKeyData(GameConfigPDA, async result =>
+        {
+            var gameConfig = GameConfig.Deserialize(Convert.FromBase64String(result.result.value.data[0]));
+            var seasonChanged = gameConfig.Season != GetCurrentSeason();
+            CurrentGameConfig = gameConfig;
+            if (seasonChanged)
+            {
+                // Somebody started the next season, switch to its board
+                Debug.Log("Season " + gameConfig.Season + " started");
+                await SubscribeToPlayerDataUpdates();
+            }
+        });
+
         ServiceFactory.Resolve<SolPlayWebSocketService>().SubscribeToPubKeyData(PlayerDataPDA, result =>
         {
             var playerData = PlayerData.Deserialize(Convert.FromBase64String(result.result.value.data[0]));
@@ -172,7 +279,7 @@ private async Task SubscribeToPlayerDataUpdates()
         ServiceFactory.Resolve<SolPlayWebSocketService>().SubscribeToPubKeyData(BoardPDA, result =>
         {
             var boardAccount = BoardAccount.Deserialize(Convert.FromBase64String(result.result.value.data[0]));
-            Debug.Log("Player data socket " + boardAccount.Wood + " wood");
+            Debug.Log("Board data socket " + boardAccount.Wood + " wood in the treasury");
             CurrentBoardAccount = boardAccount;
             OnBoardDataChanged?.Invoke(boardAccount);
         });
@@ -199,6 +306,7 @@ public async Task<RequestResult<string>> InitGameDataAccount(bool useSession)
         accounts.Player = PlayerDataPDA;
         accounts.Board = BoardPDA;
         accounts.GameActions = GameActionsPDA;
+        accounts.GameConfig = GameConfigPDA;
         accounts.Signer = Web3.Account;
         accounts.SystemProgram = SystemProgram.ProgramIdKey;
 
@@ -227,7 +335,7 @@ public async Task<RequestResult<string>> InitGameDataAccount(bool useSession)
         return initResult;
     }
 
-    public async Task<RequestResult<string>> RestartGame()
+    public async Task<RequestResult<string>> RestartGame(byte sizeX = BoardManager.WIDTH, byte sizeY = BoardManager.HEIGHT)
     {
         var tx = new Transaction()
         {
@@ -236,13 +344,20 @@ public async Task<RequestResult<string>> RestartGame()
             RecentBlockHash = await Web3.BlockHash()
         };
 
+        // The finished season is archived and the next one is played on a new board
+        var season = GetCurrentSeason();
         RestartGameAccounts accounts = new RestartGameAccounts();
+        accounts.GameConfig = GameConfigPDA;
         accounts.Board = BoardPDA;
+        accounts.NextBoard = GetBoardPDA(season + 1);
+        accounts.SeasonResult = GetSeasonResultPDA(season);
         accounts.GameActions = GameActionsPDA;
         accounts.Signer = Web3.Account;
         accounts.SystemProgram = SystemProgram.ProgramIdKey;
 
-        var initTx = LumberjackProgram.RestartGame(accounts, LumberjackProgramIdPubKey);
+        // Every round gets a new map layout
+        var seed = (ulong) DateTimeOffset.UtcNow.ToUnixTimeMilliseconds();
+        var initTx = LumberjackProgram.RestartGame(accounts, sizeX, sizeY, seed, LumberjackProgramIdPubKey);
         tx.Add(initTx);
 
         var initResult =  await Web3.Wallet.SignAndSendTransaction(tx, commitment: Commitment.Confirmed);
@@ -272,6 +387,7 @@ public async void ChopTree(bool useSession, byte x, byte y)
         accounts.Player = PlayerDataPDA;
         accounts.Board = BoardPDA;
         accounts.GameActions = GameActionsPDA;
+        accounts.GameConfig = GameConfigPDA;
         accounts.Avatar = GetAvatar();
         if (useSession)
         {
@@ -324,6 +440,7 @@ public async void Build(bool useSession, byte x, byte y, byte buildingType)
         accounts.Player = PlayerDataPDA;
         accounts.Board = BoardPDA;
         accounts.GameActions = GameActionsPDA;
+        accounts.GameConfig = GameConfigPDA;
         accounts.Avatar = GetAvatar();
         if (useSession)
         {
@@ -376,6 +493,7 @@ public async void Upgrade(bool useSession, byte x, byte y)
         accounts.Player = PlayerDataPDA;
         accounts.Board = BoardPDA;
         accounts.GameActions = GameActionsPDA;
+        accounts.GameConfig = GameConfigPDA;
         accounts.Avatar = GetAvatar();
         if (useSession)
         {
@@ -387,7 +505,7 @@ public async void Upgrade(bool useSession, byte x, byte y)
                 var createSessionIX = sessionWallet.CreateSessionIX(topUp, validity);
                 accounts.Signer = Web3.Account.PublicKey;
                 tx.Add(createSessionIX);
-                var chopInstruction = LumberjackProgram.Upgrade(accounts, x, y, LumberjackProgramIdPubKey);
+                var chopInstruction = WithBuildingOwner(LumberjackProgram.Upgrade(accounts, x, y, LumberjackProgramIdPubKey), x, y);
                 tx.Add(chopInstruction);
                 Debug.Log("Has no session -> partial sign");
                 tx.PartialSign(new[] { Web3.Account, sessionWallet.Account });
@@ -399,7 +517,7 @@ public async void Upgrade(bool useSession, byte x, byte y)
                 accounts.SessionToken = sessionWallet.SessionTokenPDA;
                 accounts.Signer = sessionWallet.Account.PublicKey;
                 Debug.Log("Has session -> sign and send session wallet");
-                var chopInstruction = LumberjackProgram.Upgrade(accounts, x, y, LumberjackProgramIdPubKey);
+                var chopInstruction = WithBuildingOwner(LumberjackProgram.Upgrade(accounts, x, y, LumberjackProgramIdPubKey), x, y);
                 tx.Add(chopInstruction);
                 SendAndConfirmTransaction(sessionWallet, tx, "Upgrade");
             }
@@ -408,7 +526,7 @@ public async void Upgrade(bool useSession, byte x, byte y)
         {
             tx.FeePayer = Web3.Account.PublicKey;
             accounts.Signer = Web3.Account.PublicKey;
-            var chopInstruction = LumberjackProgram.Upgrade(accounts, x, y, LumberjackProgramIdPubKey);
+            var chopInstruction = WithBuildingOwner(LumberjackProgram.Upgrade(accounts, x, y, LumberjackProgramIdPubKey), x, y);
             tx.Add(chopInstruction);
             Debug.Log("Sign without session");
             SendAndConfirmTransaction(Web3.Wallet, tx, "Upgrade without session");
@@ -439,6 +557,7 @@ public async void Collect(bool useSession, byte x, byte y)
         accounts.Player = PlayerDataPDA;
         accounts.Board = BoardPDA;
         accounts.GameActions = GameActionsPDA;
+        accounts.GameConfig = GameConfigPDA;
         accounts.Avatar = GetAvatar();
         
         if (useSession)
@@ -451,7 +570,7 @@ public async void Collect(bool useSession, byte x, byte y)
                 var createSessionIX = sessionWallet.CreateSessionIX(topUp, validity);
                 accounts.Signer = Web3.Account.PublicKey;
                 tx.Add(createSessionIX);
-                var chopInstruction = LumberjackProgram.Collect(accounts, x, y, LumberjackProgramIdPubKey);
+                var chopInstruction = WithBuildingOwner(LumberjackProgram.Collect(accounts, x, y, LumberjackProgramIdPubKey), x, y);
                 tx.Add(chopInstruction);
                 Debug.Log("Has no session -> partial sign");
                 tx.PartialSign(new[] { Web3.Account, sessionWallet.Account });
@@ -463,7 +582,7 @@ public async void Collect(bool useSession, byte x, byte y)
                 accounts.SessionToken = sessionWallet.SessionTokenPDA;
                 accounts.Signer = sessionWallet.Account.PublicKey;
                 Debug.Log("Has session -> sign and send session wallet");
-                var chopInstruction = LumberjackProgram.Collect(accounts, x, y, LumberjackProgramIdPubKey);
+                var chopInstruction = WithBuildingOwner(LumberjackProgram.Collect(accounts, x, y, LumberjackProgramIdPubKey), x, y);
                 tx.Add(chopInstruction);
                 SendAndConfirmTransaction(sessionWallet, tx, "Collect");
             }
@@ -472,7 +591,7 @@ public async void Collect(bool useSession, byte x, byte y)
         {
             tx.FeePayer = Web3.Account.PublicKey;
             accounts.Signer = Web3.Account.PublicKey;
-            var chopInstruction = LumberjackProgram.Collect(accounts, x, y, LumberjackProgramIdPubKey);
+            var chopInstruction = WithBuildingOwner(LumberjackProgram.Collect(accounts, x, y, LumberjackProgramIdPubKey), x, y);
             tx.Add(chopInstruction);
             Debug.Log("Sign without session");
             SendAndConfirmTransaction(Web3.Wallet, tx, "Collect without session");
@@ -495,10 +614,21 @@ public static bool IsCollectable(TileData tileData)
     {
         long unixTime = ((DateTimeOffset)DateTime.UtcNow).ToUnixTimeSeconds();
 
-        var tileDataBuildingStartCollectTime = tileData.BuildingStartCollectTime + new TimeSpan(0,0,61).TotalSeconds;
-        //Debug.Log("Time: " + tileDataBuildingStartCollectTime + " current time " + unixTime + " diff " + (unixTime - tileDataBuildingStartCollectTime));
-        
-        return tileDataBuildingStartCollectTime < unixTime;
+        return BalancingService.GetCollection(tileData, unixTime) > 0;
+    }
+
+    // Houses raise the max energy of their owner, the base max energy is set in the game config
+    public static ulong GetMaxEnergy(PlayerData playerData)
+    {
+        var gameConfig = Instance != null ? Instance.CurrentGameConfig : null;
+        var maxEnergy = gameConfig != null ? gameConfig.MaxEnergy : MAX_ENERGY;
+        return maxEnergy + playerData.EnergyBonus;
+    }
+
+    public ulong GetMissingEnergy()
+    {
+        var maxEnergy = GetMaxEnergy(CurrentPlayerData);
+        return CurrentPlayerData.Energy < maxEnergy ? maxEnergy - CurrentPlayerData.Energy : 0;
     }
 
     public static string GetName(TileData tileData)
@@ -516,6 +646,30 @@ public static string GetName(TileData tileData)
 
             case BUILDING_TYPE_SAWMILL:
                 return "Sawmill";
+
+            case BUILDING_TYPE_STONE_DEPOSIT:
+                return "Stone deposit";
+
+            case BUILDING_TYPE_WATER:
+                return "Water";
+
+            case BUILDING_TYPE_HOUSE:
+                return "House";
+
+            case BUILDING_TYPE_FARM:
+                return "Farm";
+
+            case BUILDING_TYPE_WAREHOUSE:
+                return "Warehouse";
+
+            case BUILDING_TYPE_SAPLING:
+                return "Sapling";
+
+            case BUILDING_TYPE_WALL:
+                return "Wall";
+
+            case BUILDING_TYPE_TOWER:
+                return "Tower";
         }
 
         return "NaN";
@@ -525,14 +679,17 @@ public void OnCellClicked(byte x, byte y)
     {
         var cell = ServiceFactory.Resolve<BoardManager>().GetCell(x, y);
         var tileData = CurrentBoardAccount.Data[x][y];
-        if (tileData.BuildingType == BUILDING_TYPE_EVIL || tileData.BuildingType == BUILDING_TYPE_GOOD)
+        // Trees regrow lazily, so the stored type can be outdated
+        var buildingType = BalancingService.GetGrownBuildingType(CurrentBoardAccount, x, y, ((DateTimeOffset)DateTime.UtcNow).ToUnixTimeSeconds());
+        if (buildingType == BUILDING_TYPE_EVIL || buildingType == BUILDING_TYPE_GOOD ||
+            buildingType == BUILDING_TYPE_HOUSE || buildingType == BUILDING_TYPE_WAREHOUSE)
         {
             var uiData = new UpgradeBuildingPopupUiData(Web3.Wallet, () =>
             {
                 Upgrade(!Web3.Rpc.NodeAddress.AbsoluteUri.Contains("localhost"), x, y);    
             }, tileData);
             ServiceFactory.Resolve<UiService>().OpenPopup(UiService.ScreenType.UpgradeBuildingPopup, uiData);
-        }else if (tileData.BuildingType == BUILDING_TYPE_EMPTY)
+        }else if (buildingType == BUILDING_TYPE_EMPTY || buildingType == BUILDING_TYPE_SAPLING)
         {
             if (!CheckForEnergy(1))
             {
@@ -544,7 +701,7 @@ public void OnCellClicked(byte x, byte y)
                 Build(!Web3.Rpc.NodeAddress.AbsoluteUri.Contains("localhost"), x, y, config.building_type);
             }, tileData);
             ServiceFactory.Resolve<UiService>().OpenPopup(UiService.ScreenType.BuildBuildingPopup, uiData);
-        } else if (tileData.BuildingType == BUILDING_TYPE_TREE)
+        } else if (buildingType == BUILDING_TYPE_TREE || buildingType == BUILDING_TYPE_STONE_DEPOSIT)
         {
             if (!CheckForEnergy(3))
             {
@@ -555,8 +712,9 @@ public void OnCellClicked(byte x, byte y)
                 ChopTree(!Web3.Rpc.NodeAddress.AbsoluteUri.Contains("localhost"), x, y);
             });
             ServiceFactory.Resolve<UiService>().OpenPopup(UiService.ScreenType.ChopTreePopup, uiData);
-        } else if (tileData.BuildingType == BUILDING_TYPE_MINE ||
-                   tileData.BuildingType == BUILDING_TYPE_SAWMILL)
+        } else if (buildingType == BUILDING_TYPE_MINE ||
+                   buildingType == BUILDING_TYPE_SAWMILL ||
+                   buildingType == BUILDING_TYPE_FARM)
         {
             if (!CheckForEnergy(1))
             {
@@ -578,6 +736,30 @@ public void OnCellClicked(byte x, byte y)
         }
     }
 
+    // Buildings of other players need the account of the owner to check that we are authorised
+    private TransactionInstruction WithBuildingOwner(TransactionInstruction instruction, byte x, byte y)
+    {
+        var owner = CurrentBoardAccount.Data[x][y].BuildingOwner;
+        if (owner != null && owner.Key != SystemProgram.ProgramIdKey.Key && owner.Key != PlayerDataPDA.Key)
+        {
+            instruction.Keys.Add(AccountMeta.ReadOnly(owner, false));
+        }
+        return instruction;
+    }
+
+    // Raids which destroy a house or warehouse take its bonus from the owner, so the owner account
+    // is passed writable
+    private TransactionInstruction WithRaidedOwner(TransactionInstruction instruction, byte x, byte y)
+    {
+        var tileData = CurrentBoardAccount.Data[x][y];
+        if (BalancingService.Definitions.TryGetValue(tileData.BuildingType, out var definition) &&
+            (definition.EnergyPerLevel > 0 || definition.StoragePerLevel > 0))
+        {
+            instruction.Keys.Add(AccountMeta.Writable(tileData.BuildingOwner, false));
+        }
+        return instruction;
+    }
+
     private bool CheckForEnergy(ulong amountNeeded)
     {
         if (CurrentPlayerData.Energy < amountNeeded)
@@ -588,7 +770,8 @@ private bool CheckForEnergy(ulong amountNeeded)
         return true;
     }
 
-    public async Task RefillEnergy()
+    // Refills up to the given amount of energy, paid per point in SOL
+    public async Task RefillEnergy(ulong amount)
     {
         var tx = new Transaction()
         {
@@ -599,12 +782,15 @@ public async Task RefillEnergy()
         
         RefillEnergyAccounts accounts = new RefillEnergyAccounts();
         accounts.Player = PlayerDataPDA;
+        accounts.GameConfig = GameConfigPDA;
         accounts.SystemProgram = SystemProgram.ProgramIdKey;
-        accounts.Treasury = new PublicKey("CYg2vSJdujzEC1E7kHMzB9QhjiPLRdsAa4Js7MkuXfYq");
+        accounts.Treasury = CurrentGameConfig != null ? CurrentGameConfig.Treasury : new PublicKey("CYg2vSJdujzEC1E7kHMzB9QhjiPLRdsAa4Js7MkuXfYq");
+        accounts.Board = BoardPDA;
+        accounts.RewardVault = RewardVaultPDA;
         
         tx.FeePayer = Web3.Account.PublicKey;
         accounts.Signer = Web3.Account.PublicKey;
-        var ix = LumberjackProgram.RefillEnergy(accounts, LumberjackProgramIdPubKey);
+        var ix = LumberjackProgram.RefillEnergy(accounts, amount, LumberjackProgramIdPubKey);
         tx.Add(ix);
         SendAndConfirmTransaction(Web3.Wallet, tx, "Refill energy");
         
@@ -615,18 +801,321 @@ public async Task RefillEnergy()
         }
     }
 
-    public static bool HasEnoughResources(BalancingService.Cost cost)
+    // Pays the share of the war pool of the last decided game to the wallet of the player
+    public async void ClaimWarReward(bool useSession)
     {
-        var hasEnoughWood = cost.Wood <= Instance.CurrentBoardAccount.Wood;
-        var hasEnoughStone = cost.Stone <= Instance.CurrentBoardAccount.Stone;
+        var tx = new Transaction()
+        {
+            FeePayer = Web3.Account,
+            Instructions = new List<TransactionInstruction>(),
+            RecentBlockHash = await Web3.BlockHash(maxSeconds:1)
+        };
 
-        return hasEnoughWood && hasEnoughStone;
+        ClaimWarRewardAccounts accounts = new ClaimWarRewardAccounts();
+        accounts.Player = PlayerDataPDA;
+        accounts.Board = BoardPDA;
+        accounts.GameActions = GameActionsPDA;
+        accounts.GameConfig = GameConfigPDA;
+        accounts.RewardVault = RewardVaultPDA;
+        accounts.Authority = Web3.Account.PublicKey;
+        accounts.Avatar = GetAvatar();
+
+        if (useSession && await sessionWallet.IsSessionTokenInitialized())
+        {
+            tx.FeePayer = sessionWallet.Account.PublicKey;
+            accounts.SessionToken = sessionWallet.SessionTokenPDA;
+            accounts.Signer = sessionWallet.Account.PublicKey;
+            tx.Add(LumberjackProgram.ClaimWarReward(accounts, LumberjackProgramIdPubKey));
+            SendAndConfirmTransaction(sessionWallet, tx, "Claim war reward");
+        }
+        else
+        {
+            tx.FeePayer = Web3.Account.PublicKey;
+            accounts.Signer = Web3.Account.PublicKey;
+            tx.Add(LumberjackProgram.ClaimWarReward(accounts, LumberjackProgramIdPubKey));
+            SendAndConfirmTransaction(Web3.Wallet, tx, "Claim war reward without session");
+        }
+    }
+
+    // Refills up to the given amount of energy, paid per point with stone of the player
+    public async void RefillEnergyWithStone(bool useSession, ulong amount)
+    {
+        var tx = new Transaction()
+        {
+            FeePayer = Web3.Account,
+            Instructions = new List<TransactionInstruction>(),
+            RecentBlockHash = await Web3.BlockHash(maxSeconds:1)
+        };
+
+        RefillEnergyWithStoneAccounts accounts = new RefillEnergyWithStoneAccounts();
+        accounts.Player = PlayerDataPDA;
+        accounts.Board = BoardPDA;
+        accounts.GameActions = GameActionsPDA;
+        accounts.GameConfig = GameConfigPDA;
+        accounts.Avatar = GetAvatar();
+
+        if (useSession && await sessionWallet.IsSessionTokenInitialized())
+        {
+            tx.FeePayer = sessionWallet.Account.PublicKey;
+            accounts.SessionToken = sessionWallet.SessionTokenPDA;
+            accounts.Signer = sessionWallet.Account.PublicKey;
+            tx.Add(LumberjackProgram.RefillEnergyWithStone(accounts, amount, LumberjackProgramIdPubKey));
+            SendAndConfirmTransaction(sessionWallet, tx, "Refill energy with stone");
+        }
+        else
+        {
+            tx.FeePayer = Web3.Account.PublicKey;
+            accounts.Signer = Web3.Account.PublicKey;
+            tx.Add(LumberjackProgram.RefillEnergyWithStone(accounts, amount, LumberjackProgramIdPubKey));
+            SendAndConfirmTransaction(Web3.Wallet, tx, "Refill energy with stone without session");
+        }
+    }
+
+    // Damages a building of another player and steals part of its production
+    public async void Raid(bool useSession, byte x, byte y)
+    {
+        var tx = new Transaction()
+        {
+            FeePayer = Web3.Account,
+            Instructions = new List<TransactionInstruction>(),
+            RecentBlockHash = await Web3.BlockHash(maxSeconds:1)
+        };
+
+        RaidAccounts accounts = new RaidAccounts();
+        accounts.Player = PlayerDataPDA;
+        accounts.Board = BoardPDA;
+        accounts.GameActions = GameActionsPDA;
+        accounts.GameConfig = GameConfigPDA;
+        accounts.Avatar = GetAvatar();
+
+        if (useSession && await sessionWallet.IsSessionTokenInitialized())
+        {
+            tx.FeePayer = sessionWallet.Account.PublicKey;
+            accounts.SessionToken = sessionWallet.SessionTokenPDA;
+            accounts.Signer = sessionWallet.Account.PublicKey;
+            tx.Add(WithRaidedOwner(LumberjackProgram.Raid(accounts, x, y, LumberjackProgramIdPubKey), x, y));
+            SendAndConfirmTransaction(sessionWallet, tx, "Raid");
+        }
+        else
+        {
+            tx.FeePayer = Web3.Account.PublicKey;
+            accounts.Signer = Web3.Account.PublicKey;
+            tx.Add(WithRaidedOwner(LumberjackProgram.Raid(accounts, x, y, LumberjackProgramIdPubKey), x, y));
+            SendAndConfirmTransaction(Web3.Wallet, tx, "Raid without session");
+        }
+    }
+
+    // Repairs one of our buildings or one of a player who authorised us, costs stone
+    public async void Defend(bool useSession, byte x, byte y)
+    {
+        var tx = new Transaction()
+        {
+            FeePayer = Web3.Account,
+            Instructions = new List<TransactionInstruction>(),
+            RecentBlockHash = await Web3.BlockHash(maxSeconds:1)
+        };
+
+        DefendAccounts accounts = new DefendAccounts();
+        accounts.Player = PlayerDataPDA;
+        accounts.Board = BoardPDA;
+        accounts.GameActions = GameActionsPDA;
+        accounts.GameConfig = GameConfigPDA;
+        accounts.Avatar = GetAvatar();
+
+        if (useSession && await sessionWallet.IsSessionTokenInitialized())
+        {
+            tx.FeePayer = sessionWallet.Account.PublicKey;
+            accounts.SessionToken = sessionWallet.SessionTokenPDA;
+            accounts.Signer = sessionWallet.Account.PublicKey;
+            tx.Add(WithBuildingOwner(LumberjackProgram.Defend(accounts, x, y, LumberjackProgramIdPubKey), x, y));
+            SendAndConfirmTransaction(sessionWallet, tx, "Defend");
+        }
+        else
+        {
+            tx.FeePayer = Web3.Account.PublicKey;
+            accounts.Signer = Web3.Account.PublicKey;
+            tx.Add(WithBuildingOwner(LumberjackProgram.Defend(accounts, x, y, LumberjackProgramIdPubKey), x, y));
+            SendAndConfirmTransaction(Web3.Wallet, tx, "Defend without session");
+        }
+    }
+
+    // All open orders of the market
+    public async Task<List<MarketOrder>> GetMarketOrders()
+    {
+        var orders = await lumberjackClient.GetMarketOrdersAsync(LumberjackProgramIdPubKey, Commitment.Confirmed);
+        return orders.ParsedResult ?? new List<MarketOrder>();
+    }
+
+    // Offers wood or stone of the player, they are held by the order until it is filled or cancelled
+    public async void PlaceOrder(bool useSession, byte sellResource, ulong sellAmount, byte buyResource, ulong buyAmount)
+    {
+        var tx = new Transaction()
+        {
+            FeePayer = Web3.Account,
+            Instructions = new List<TransactionInstruction>(),
+            RecentBlockHash = await Web3.BlockHash(maxSeconds:1)
+        };
+
+        PlaceOrderAccounts accounts = new PlaceOrderAccounts();
+        accounts.Player = PlayerDataPDA;
+        accounts.Order = GetOrderPDA(PlayerDataPDA, CurrentPlayerData.NextOrderId);
+        accounts.SystemProgram = SystemProgram.ProgramIdKey;
+
+        if (useSession && await sessionWallet.IsSessionTokenInitialized())
+        {
+            tx.FeePayer = sessionWallet.Account.PublicKey;
+            accounts.SessionToken = sessionWallet.SessionTokenPDA;
+            accounts.Signer = sessionWallet.Account.PublicKey;
+            tx.Add(LumberjackProgram.PlaceOrder(accounts, sellResource, sellAmount, buyResource, buyAmount, LumberjackProgramIdPubKey));
+            SendAndConfirmTransaction(sessionWallet, tx, "Place order");
+        }
+        else
+        {
+            tx.FeePayer = Web3.Account.PublicKey;
+            accounts.Signer = Web3.Account.PublicKey;
+            tx.Add(LumberjackProgram.PlaceOrder(accounts, sellResource, sellAmount, buyResource, buyAmount, LumberjackProgramIdPubKey));
+            SendAndConfirmTransaction(Web3.Wallet, tx, "Place order without session");
+        }
+    }
+
+    public async void CancelOrder(bool useSession, MarketOrder order)
+    {
+        var tx = new Transaction()
+        {
+            FeePayer = Web3.Account,
+            Instructions = new List<TransactionInstruction>(),
+            RecentBlockHash = await Web3.BlockHash(maxSeconds:1)
+        };
+
+        CancelOrderAccounts accounts = new CancelOrderAccounts();
+        accounts.Player = PlayerDataPDA;
+        accounts.Order = GetOrderPDA(order.Maker, order.OrderId);
+        accounts.Authority = order.Authority;
+
+        if (useSession && await sessionWallet.IsSessionTokenInitialized())
+        {
+            tx.FeePayer = sessionWallet.Account.PublicKey;
+            accounts.SessionToken = sessionWallet.SessionTokenPDA;
+            accounts.Signer = sessionWallet.Account.PublicKey;
+            tx.Add(LumberjackProgram.CancelOrder(accounts, LumberjackProgramIdPubKey));
+            SendAndConfirmTransaction(sessionWallet, tx, "Cancel order");
+        }
+        else
+        {
+            tx.FeePayer = Web3.Account.PublicKey;
+            accounts.Signer = Web3.Account.PublicKey;
+            tx.Add(LumberjackProgram.CancelOrder(accounts, LumberjackProgramIdPubKey));
+            SendAndConfirmTransaction(Web3.Wallet, tx, "Cancel order without session");
+        }
+    }
+
+    // Buys the amount of the offered resource, orders asking for SOL are always paid by the wallet
+    public async void FillOrder(bool useSession, MarketOrder order, ulong amount)
+    {
+        var tx = new Transaction()
+        {
+            FeePayer = Web3.Account,
+            Instructions = new List<TransactionInstruction>(),
+            RecentBlockHash = await Web3.BlockHash(maxSeconds:1)
+        };
+
+        FillOrderAccounts accounts = new FillOrderAccounts();
+        accounts.Player = PlayerDataPDA;
+        accounts.Order = GetOrderPDA(order.Maker, order.OrderId);
+        accounts.Maker = order.Maker;
+        accounts.MakerAuthority = order.Authority;
+        accounts.SystemProgram = SystemProgram.ProgramIdKey;
+
+        if (useSession && order.BuyResource != RESOURCE_SOL && await sessionWallet.IsSessionTokenInitialized())
+        {
+            tx.FeePayer = sessionWallet.Account.PublicKey;
+            accounts.SessionToken = sessionWallet.SessionTokenPDA;
+            accounts.Signer = sessionWallet.Account.PublicKey;
+            tx.Add(LumberjackProgram.FillOrder(accounts, amount, LumberjackProgramIdPubKey));
+            SendAndConfirmTransaction(sessionWallet, tx, "Fill order");
+        }
+        else
+        {
+            tx.FeePayer = Web3.Account.PublicKey;
+            accounts.Signer = Web3.Account.PublicKey;
+            tx.Add(LumberjackProgram.FillOrder(accounts, amount, LumberjackProgramIdPubKey));
+            SendAndConfirmTransaction(Web3.Wallet, tx, "Fill order without session");
+        }
+    }
+
+    // Turns wood or stone of the player into tokens in the wallet
+    public async void WithdrawResources(byte resource, ulong amount)
+    {
+        var tx = new Transaction()
+        {
+            FeePayer = Web3.Account.PublicKey,
+            Instructions = new List<TransactionInstruction>(),
+            RecentBlockHash = await Web3.BlockHash(maxSeconds:1)
+        };
+
+        // The tokens belong to the wallet, so the bridge can't be used with a session key
+        var resourceMint = GetResourceMintPDA(resource);
+        WithdrawResourcesAccounts accounts = new WithdrawResourcesAccounts();
+        accounts.Player = PlayerDataPDA;
+        accounts.ResourceMint = resourceMint;
+        accounts.PlayerTokenAccount = AssociatedTokenAccountProgram.DeriveAssociatedTokenAccount(Web3.Account.PublicKey, resourceMint);
+        accounts.Signer = Web3.Account.PublicKey;
+        accounts.TokenProgram = TokenProgram.ProgramIdKey;
+        accounts.AssociatedTokenProgram = AssociatedTokenAccountProgram.ProgramIdKey;
+        accounts.SystemProgram = SystemProgram.ProgramIdKey;
+
+        tx.Add(LumberjackProgram.WithdrawResources(accounts, resource, amount, LumberjackProgramIdPubKey));
+        SendAndConfirmTransaction(Web3.Wallet, tx, "Withdraw resources");
+    }
+
+    // Burns tokens of the wallet and gives the player the resources back
+    public async void DepositResources(byte resource, ulong amount)
+    {
+        var tx = new Transaction()
+        {
+            FeePayer = Web3.Account.PublicKey,
+            Instructions = new List<TransactionInstruction>(),
+            RecentBlockHash = await Web3.BlockHash(maxSeconds:1)
+        };
+
+        // The tokens belong to the wallet, so the bridge can't be used with a session key
+        var resourceMint = GetResourceMintPDA(resource);
+        DepositResourcesAccounts accounts = new DepositResourcesAccounts();
+        accounts.Player = PlayerDataPDA;
+        accounts.ResourceMint = resourceMint;
+        accounts.PlayerTokenAccount = AssociatedTokenAccountProgram.DeriveAssociatedTokenAccount(Web3.Account.PublicKey, resourceMint);
+        accounts.Signer = Web3.Account.PublicKey;
+        accounts.TokenProgram = TokenProgram.ProgramIdKey;
+        accounts.AssociatedTokenProgram = AssociatedTokenAccountProgram.ProgramIdKey;
+        accounts.SystemProgram = SystemProgram.ProgramIdKey;
+
+        tx.Add(LumberjackProgram.DepositResources(accounts, resource, amount, LumberjackProgramIdPubKey));
+        SendAndConfirmTransaction(Web3.Wallet, tx, "Deposit resources");
+    }
+
+    // The good and evil buildings are upgraded with the shared treasury of the board
+    public static bool IsPaidFromTreasury(TileData tileData)
+    {
+        return tileData.BuildingType == BUILDING_TYPE_GOOD || tileData.BuildingType == BUILDING_TYPE_EVIL;
+    }
+
+    // Faction memberships only count for the game number they were made in
+    public static byte GetFaction(PlayerData playerData, BoardAccount boardAccount)
+    {
+        return playerData.FactionGame == boardAccount.GameNumber ? playerData.Faction : FACTION_NONE;
+    }
+
+    public static bool HasEnoughResources(BalancingService.Cost cost, bool fromTreasury = false)
+    {
+        return HasEnoughResources(cost.Wood, cost.Stone, fromTreasury);
     }
     
-    public static bool HasEnoughResources(ulong woodCost, ulong stoneCost)
+    public static bool HasEnoughResources(ulong woodCost, ulong stoneCost, bool fromTreasury = false)
     {
-        var hasEnoughWood = woodCost <= Instance.CurrentBoardAccount.Wood;
-        var hasEnoughStone = stoneCost <= Instance.CurrentBoardAccount.Stone;
+        var wood = fromTreasury ? Instance.CurrentBoardAccount.Wood : Instance.CurrentPlayerData.Wood;
+        var stone = fromTreasury ? Instance.CurrentBoardAccount.Stone : Instance.CurrentPlayerData.Stone;
+        var hasEnoughWood = woodCost <= wood;
+        var hasEnoughStone = stoneCost <= stone;
 
         return hasEnoughWood && hasEnoughStone;
     }
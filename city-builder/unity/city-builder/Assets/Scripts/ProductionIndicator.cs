@@ -57,12 +57,10 @@ private IEnumerator UpdateProgressBar()
                     
             long unixTime = ((DateTimeOffset)DateTime.UtcNow).ToUnixTimeSeconds();
 
-            var tileDataBuildingStartCollectTime = CurrentTileData.BuildingStartCollectTime + new TimeSpan(0,0,1,0).TotalSeconds;
-            //Debug.Log("Time: " + tileDataBuildingStartCollectTime + " current time " + unixTime + " diff " + (unixTime - tileDataBuildingStartCollectTime));
-        
-            //tileDataBuildingStartCollectTime < unixTime;
-            var dataBuildingStartCollectTime = tileDataBuildingStartCollectTime - unixTime;
-            ProgressBar.fillAmount = (float) (dataBuildingStartCollectTime / 60f);
+            // Shows how full the storage of the building is
+            var storage = BalancingService.GetStorage(CurrentTileData);
+            var accrued = BalancingService.GetCollection(CurrentTileData, unixTime);
+            ProgressBar.fillAmount = storage == 0 ? 0 : (float) accrued / storage;
 
             var isCollectable = LumberjackService.IsCollectable(CurrentTileData);
             CollectionIndicator.gameObject.SetActive(isCollectable);
@@ -1,19 +1,25 @@
+using Solana.Unity.SDK;
 using SolPlay.Scripts.Services;
 using SolPlay.Scripts.Ui;
 using UnityEngine;
 using UnityEngine.UI;
 
 /// <summary>
-/// Screen that lets you refill energy for sol 
+/// Screen that lets you refill energy for sol or stone
 /// </summary>
 public class RefillEnergyPopup : BasePopup
 {
     public Button RefillEnergyButton;
+    public Button RefillEnergyWithStoneButton;
     public GameObject LoadingSpinner;
     
     void Start()
     {
         RefillEnergyButton.onClick.AddListener(OnRefillEnergyButtonClicked);
+        if (RefillEnergyWithStoneButton != null)
+        {
+            RefillEnergyWithStoneButton.onClick.AddListener(OnRefillEnergyWithStoneButtonClicked);
+        }
     }
 
     public override void Open(UiService.UiData uiData)
@@ -32,8 +38,14 @@ public override void Open(UiService.UiData uiData)
     private async void OnRefillEnergyButtonClicked()
     {
         LoadingSpinner.gameObject.SetActive(true);
-        await LumberjackService.Instance.RefillEnergy();
+        await LumberjackService.Instance.RefillEnergy(LumberjackService.Instance.GetMissingEnergy());
         LoadingSpinner.gameObject.SetActive(false);
         Close();
     }
+
+    private void OnRefillEnergyWithStoneButtonClicked()
+    {
+        LumberjackService.Instance.RefillEnergyWithStone(!Web3.Rpc.NodeAddress.AbsoluteUri.Contains("localhost"), LumberjackService.Instance.GetMissingEnergy());
+        Close();
+    }
 }
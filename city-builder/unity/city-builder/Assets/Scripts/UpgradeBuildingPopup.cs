@@ -49,7 +49,7 @@ private async void ButtonClicked()
     {
         var refillUiData = (uiData as UpgradeBuildingPopupUiData);
 
-        if (LumberjackService.HasEnoughResources(BalancingService.GetUpgradeCost(refillUiData.TileData)))
+        if (LumberjackService.HasEnoughResources(BalancingService.GetUpgradeCost(refillUiData.TileData), LumberjackService.IsPaidFromTreasury(refillUiData.TileData)))
         {
             (uiData as UpgradeBuildingPopupUiData).OnClick?.Invoke();
             Close();
@@ -0,0 +1,27 @@
+//! Writes a JSON lines replay of a fight to stdout.
+//!
+//! cargo run --features client --bin replay -- level.bin actions-1.bin actions-2.bin > replay.jsonl
+//!
+//! The first file is the raw data of the `level` account, the others are dumps of the
+//! `gameActions` account taken while the fight went on, for example with
+//! `solana account <address> --output-file actions-1.bin`.
+use seven_seas::client::{decode_board, decode_game_actions};
+use seven_seas::replay::{replay, write_replay};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let level_path = args
+        .next()
+        .ok_or("usage: replay <level account file> <game actions files>...")?;
+
+    let board = decode_board(&std::fs::read(level_path)?).map_err(|err| err.to_string())?;
+    let mut actions = Vec::new();
+    for path in args {
+        let dump = decode_game_actions(&std::fs::read(path)?).map_err(|err| err.to_string())?;
+        actions.extend(dump);
+    }
+
+    let frames = replay(&board, &actions);
+    write_replay(std::io::stdout().lock(), &board, &frames)?;
+    Ok(())
+}
@@ -0,0 +1,659 @@
+//! Off chain helpers for bots and server tooling. Enable with the `client` feature.
+//!
+//! Contains the PDA derivations, one instruction builder per program instruction and
+//! a decoder that turns the raw `GameDataAccount` data into a typed board snapshot.
+use crate::state::{
+    BoardSummary, GameActionHistory, GameConfig, GameDataAccount, Tile, BOARD_SIZE_X, BOARD_SIZE_Y,
+    STATE_CHEST, STATE_DISABLED, STATE_EMPTY, STATE_NPC, STATE_PLAYER,
+};
+use crate::errors::SevenSeasError;
+use crate::{accounts, instruction, ID, THREAD_AUTHORITY_SEED};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{AccountDeserialize, Discriminator, InstructionData};
+use anchor_spl::associated_token::get_associated_token_address;
+use clockwork_sdk::state::Thread;
+
+pub fn level_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"level"], &ID)
+}
+
+pub fn chest_vault_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"chestVault"], &ID)
+}
+
+pub fn game_actions_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"gameActions"], &ID)
+}
+
+pub fn keeper_pool_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"keeperPool"], &ID)
+}
+
+pub fn game_config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"gameConfig"], &ID)
+}
+
+pub fn token_account_owner_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"token_account_owner_pda"], &ID)
+}
+
+pub fn token_vault_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"token_vault", mint.as_ref()], &ID)
+}
+
+pub fn ship_pda(nft_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"ship", nft_account.as_ref()], &ID)
+}
+
+pub fn thread_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[THREAD_AUTHORITY_SEED], &ID)
+}
+
+pub fn thread_pubkey(thread_id: &[u8]) -> Pubkey {
+    Thread::pubkey(thread_authority_pda().0, thread_id.to_vec())
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize(signer: Pubkey, gold_mint: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeAccounts {
+            signer,
+            new_game_data_account: level_pda().0,
+            chest_vault: chest_vault_pda().0,
+            game_actions: game_actions_pda().0,
+            game_config: game_config_pda().0,
+            token_account_owner_pda: token_account_owner_pda().0,
+            vault_token_account: token_vault_pda(&gold_mint).0,
+            mint_of_token_being_sent: gold_mint,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::Initialize {},
+    )
+}
+
+pub fn initialize_ship(signer: Pubkey, nft_account: Pubkey, class: u8) -> Instruction {
+    build(
+        accounts::InitializeShip {
+            signer,
+            new_ship: ship_pda(&nft_account).0,
+            nft_account,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeShip { class },
+    )
+}
+
+pub fn upgrade_ship(signer: Pubkey, nft_account: Pubkey, gold_mint: Pubkey) -> Instruction {
+    build(
+        accounts::UpgradeShip {
+            signer,
+            new_ship: ship_pda(&nft_account).0,
+            nft_account,
+            system_program: system_program::ID,
+            player_token_account: get_associated_token_address(&signer, &gold_mint),
+            vault_token_account: token_vault_pda(&gold_mint).0,
+            mint_of_token_being_sent: gold_mint,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::UpgradeShip {},
+    )
+}
+
+pub fn close_ship(signer: Pubkey, nft_account: Pubkey) -> Instruction {
+    build(
+        accounts::CloseShip {
+            signer,
+            ship: ship_pda(&nft_account).0,
+            nft_account,
+            game_data_account: level_pda().0,
+        },
+        instruction::CloseShip {},
+    )
+}
+
+/// Pass the id of the clockwork thread if one was started, it gets deleted with the game.
+pub fn close_game(
+    admin: Pubkey,
+    treasury: Pubkey,
+    gold_mint: Pubkey,
+    thread_id: Option<Vec<u8>>,
+) -> Instruction {
+    build(
+        accounts::CloseGame {
+            signer: admin,
+            treasury,
+            treasury_token_account: get_associated_token_address(&treasury, &gold_mint),
+            game_data_account: level_pda().0,
+            chest_vault: chest_vault_pda().0,
+            game_actions: game_actions_pda().0,
+            game_config: game_config_pda().0,
+            token_account_owner_pda: token_account_owner_pda().0,
+            vault_token_account: token_vault_pda(&gold_mint).0,
+            mint_of_token_being_sent: gold_mint,
+            token_program: anchor_spl::token::ID,
+            keeper_pool: keeper_pool_pda().0,
+            clockwork_program: thread_id.as_ref().map(|_| clockwork_sdk::ID),
+            thread: thread_id.as_deref().map(thread_pubkey),
+            thread_authority: thread_authority_pda().0,
+        },
+        instruction::CloseGame {},
+    )
+}
+
+pub fn update_game_config(admin: Pubkey, config: &GameConfig) -> Instruction {
+    build(
+        accounts::UpdateGameConfig {
+            signer: admin,
+            game_config: game_config_pda().0,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateGameConfig {
+            player_kill_reward: config.player_kill_reward,
+            chest_reward: config.chest_reward,
+            chest_coin_reward: config.chest_coin_reward,
+            destroy_ship_coin_reward: config.destroy_ship_coin_reward,
+            npc_kill_reward: config.npc_kill_reward,
+        },
+    )
+}
+
+pub fn reset(signer: Pubkey) -> Instruction {
+    build(
+        accounts::Reset {
+            signer,
+            game_data_account: level_pda().0,
+        },
+        instruction::Reset {},
+    )
+}
+
+pub fn reset_ship(signer: Pubkey) -> Instruction {
+    build(
+        accounts::ResetShip {
+            signer,
+            game_data_account: level_pda().0,
+        },
+        instruction::ResetShip {},
+    )
+}
+
+pub fn start_thread(payer: Pubkey, thread_id: Vec<u8>) -> Instruction {
+    build(
+        accounts::StartThread {
+            game_data_account: level_pda().0,
+            clockwork_program: clockwork_sdk::ID,
+            payer,
+            system_program: system_program::ID,
+            thread: thread_pubkey(&thread_id),
+            thread_authority: thread_authority_pda().0,
+        },
+        instruction::StartThread { thread_id },
+    )
+}
+
+pub fn pause_thread(payer: Pubkey, thread_id: Vec<u8>) -> Instruction {
+    build(
+        accounts::PauseThread {
+            payer,
+            clockwork_program: clockwork_sdk::ID,
+            thread: thread_pubkey(&thread_id),
+            thread_authority: thread_authority_pda().0,
+        },
+        instruction::PauseThread { thread_id },
+    )
+}
+
+pub fn resume_thread(payer: Pubkey, thread_id: Vec<u8>) -> Instruction {
+    build(
+        accounts::ResumeThread {
+            payer,
+            clockwork_program: clockwork_sdk::ID,
+            thread: thread_pubkey(&thread_id),
+            thread_authority: thread_authority_pda().0,
+        },
+        instruction::ResumeThread { thread_id },
+    )
+}
+
+/// Has to be signed by the admin, who receives the lamports of the thread.
+pub fn delete_thread(admin: Pubkey, thread_id: Vec<u8>) -> Instruction {
+    build(
+        accounts::DeleteThread {
+            payer: admin,
+            clockwork_program: clockwork_sdk::ID,
+            thread: thread_pubkey(&thread_id),
+            thread_authority: thread_authority_pda().0,
+        },
+        instruction::DeleteThread { thread_id },
+    )
+}
+
+/// Only the clockwork thread can sign this one, it is here for completeness.
+pub fn on_thread_tick(thread_id: &[u8]) -> Instruction {
+    build(
+        accounts::ThreadTick {
+            game_data: level_pda().0,
+            game_actions: game_actions_pda().0,
+            thread: thread_pubkey(thread_id),
+            thread_authority: thread_authority_pda().0,
+        },
+        instruction::OnThreadTick {},
+    )
+}
+
+pub fn crank_tick(caller: Pubkey) -> Instruction {
+    build(
+        accounts::CrankTick {
+            game_data_account: level_pda().0,
+            keeper_pool: keeper_pool_pda().0,
+            game_actions: game_actions_pda().0,
+            caller,
+        },
+        instruction::CrankTick {},
+    )
+}
+
+pub fn fund_keeper_pool(signer: Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::FundKeeperPool {
+            signer,
+            keeper_pool: keeper_pool_pda().0,
+            system_program: system_program::ID,
+        },
+        instruction::FundKeeperPool { amount },
+    )
+}
+
+pub fn spawn_player(
+    player: Pubkey,
+    token_account_owner: Pubkey,
+    nft_account: Pubkey,
+    cannon_mint: Pubkey,
+    rum_mint: Pubkey,
+    avatar: Pubkey,
+) -> Instruction {
+    build(
+        accounts::SpawnPlayer {
+            player,
+            token_account_owner,
+            chest_vault: chest_vault_pda().0,
+            game_data_account: level_pda().0,
+            game_config: game_config_pda().0,
+            ship: ship_pda(&nft_account).0,
+            nft_account,
+            system_program: system_program::ID,
+            cannon_token_account: get_associated_token_address(&token_account_owner, &cannon_mint),
+            cannon_mint,
+            rum_token_account: get_associated_token_address(&token_account_owner, &rum_mint),
+            rum_mint,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        instruction::SpawnPlayer { avatar },
+    )
+}
+
+pub fn spawn_npc(admin: Pubkey, avatar: Pubkey) -> Instruction {
+    build(
+        accounts::SpawnNpc {
+            signer: admin,
+            chest_vault: chest_vault_pda().0,
+            game_data_account: level_pda().0,
+            game_config: game_config_pda().0,
+            system_program: system_program::ID,
+        },
+        instruction::SpawnNpc { avatar },
+    )
+}
+
+pub fn cthulhu(player: Pubkey, token_account_owner: Pubkey, gold_mint: Pubkey) -> Instruction {
+    build(
+        accounts::Cthulhu {
+            chest_vault: chest_vault_pda().0,
+            game_data_account: level_pda().0,
+            game_actions: game_actions_pda().0,
+            player,
+            system_program: system_program::ID,
+            token_account_owner,
+            player_token_account: get_associated_token_address(&token_account_owner, &gold_mint),
+            vault_token_account: token_vault_pda(&gold_mint).0,
+            token_account_owner_pda: token_account_owner_pda().0,
+            mint_of_token_being_sent: gold_mint,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        instruction::Cthulhu { _block_bump: 0 },
+    )
+}
+
+pub fn shoot(player: Pubkey, token_account_owner: Pubkey, gold_mint: Pubkey) -> Instruction {
+    build(
+        accounts::Shoot {
+            chest_vault: chest_vault_pda().0,
+            game_data_account: level_pda().0,
+            game_actions: game_actions_pda().0,
+            game_config: game_config_pda().0,
+            player,
+            system_program: system_program::ID,
+            token_account_owner,
+            player_token_account: get_associated_token_address(&token_account_owner, &gold_mint),
+            vault_token_account: token_vault_pda(&gold_mint).0,
+            token_account_owner_pda: token_account_owner_pda().0,
+            mint_of_token_being_sent: gold_mint,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        instruction::Shoot { _block_bump: 0 },
+    )
+}
+
+pub fn board_ship(
+    player: Pubkey,
+    token_account_owner: Pubkey,
+    gold_mint: Pubkey,
+    direction: u8,
+) -> Instruction {
+    build(
+        accounts::BoardShip {
+            chest_vault: chest_vault_pda().0,
+            game_data_account: level_pda().0,
+            game_actions: game_actions_pda().0,
+            game_config: game_config_pda().0,
+            player,
+            system_program: system_program::ID,
+            token_account_owner,
+            player_token_account: get_associated_token_address(&token_account_owner, &gold_mint),
+            vault_token_account: token_vault_pda(&gold_mint).0,
+            token_account_owner_pda: token_account_owner_pda().0,
+            mint_of_token_being_sent: gold_mint,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        instruction::BoardShip {
+            direction,
+            _block_bump: 0,
+        },
+    )
+}
+
+/// Simulate it and read the summary with `decode_board_summary` from the return data
+pub fn board_summary() -> Instruction {
+    build(
+        accounts::BoardSummaryAccounts {
+            game_data_account: level_pda().0,
+        },
+        instruction::BoardSummary {},
+    )
+}
+
+pub fn move_player_v2(
+    player: Pubkey,
+    token_account_owner: Pubkey,
+    gold_mint: Pubkey,
+    direction: u8,
+) -> Instruction {
+    build(
+        accounts::MovePlayer {
+            chest_vault: chest_vault_pda().0,
+            game_data_account: level_pda().0,
+            player,
+            token_account_owner,
+            system_program: system_program::ID,
+            player_token_account: get_associated_token_address(&token_account_owner, &gold_mint),
+            vault_token_account: token_vault_pda(&gold_mint).0,
+            token_account_owner_pda: token_account_owner_pda().0,
+            mint_of_token_being_sent: gold_mint,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            game_actions: game_actions_pda().0,
+            game_config: game_config_pda().0,
+        },
+        instruction::MovePlayerV2 {
+            direction,
+            _block_bump: 0,
+        },
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileState {
+    Empty,
+    Player,
+    Chest,
+    Npc,
+    Disabled,
+    Unknown(u8),
+}
+
+impl From<u8> for TileState {
+    fn from(state: u8) -> Self {
+        match state {
+            STATE_EMPTY => TileState::Empty,
+            STATE_PLAYER => TileState::Player,
+            STATE_CHEST => TileState::Chest,
+            STATE_NPC => TileState::Npc,
+            STATE_DISABLED => TileState::Disabled,
+            other => TileState::Unknown(other),
+        }
+    }
+}
+
+impl From<TileState> for u8 {
+    fn from(state: TileState) -> Self {
+        match state {
+            TileState::Empty => STATE_EMPTY,
+            TileState::Player => STATE_PLAYER,
+            TileState::Chest => STATE_CHEST,
+            TileState::Npc => STATE_NPC,
+            TileState::Disabled => STATE_DISABLED,
+            TileState::Unknown(other) => other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileSnapshot {
+    pub x: usize,
+    pub y: usize,
+    pub state: TileState,
+    pub player: Pubkey,
+    pub avatar: Pubkey,
+    pub health: u64,
+    pub start_health: u64,
+    pub damage: u64,
+    pub range: u8,
+    pub ship_class: u8,
+    pub collect_reward: u64,
+    pub look_direction: u8,
+    pub ship_level: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardSnapshot {
+    pub action_id: u64,
+    pub last_tick_slot: u64,
+    pub last_tick_timestamp: i64,
+    pub wind_ticks: u32,
+    /// All tiles of the board ordered by x, then y. Use `tile` or `occupied` to look them up.
+    pub tiles: Vec<TileSnapshot>,
+}
+
+impl TileSnapshot {
+    pub fn empty(x: usize, y: usize) -> Self {
+        TileSnapshot {
+            x,
+            y,
+            state: TileState::Empty,
+            player: Pubkey::default(),
+            avatar: Pubkey::default(),
+            health: 0,
+            start_health: 0,
+            damage: 0,
+            range: 0,
+            ship_class: 0,
+            collect_reward: 0,
+            look_direction: 0,
+            ship_level: 0,
+        }
+    }
+}
+
+impl BoardSnapshot {
+    pub fn tile(&self, x: usize, y: usize) -> Option<&TileSnapshot> {
+        if x >= BOARD_SIZE_X || y >= BOARD_SIZE_Y {
+            return None;
+        }
+        self.tiles.get(x * BOARD_SIZE_Y + y)
+    }
+
+    pub fn tile_mut(&mut self, x: usize, y: usize) -> Option<&mut TileSnapshot> {
+        if x >= BOARD_SIZE_X || y >= BOARD_SIZE_Y {
+            return None;
+        }
+        self.tiles.get_mut(x * BOARD_SIZE_Y + y)
+    }
+
+    pub fn occupied(&self) -> impl Iterator<Item = &TileSnapshot> {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.state != TileState::Empty)
+    }
+
+    pub fn ships(&self) -> impl Iterator<Item = &TileSnapshot> {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.state == TileState::Player)
+    }
+
+    pub fn npcs(&self) -> impl Iterator<Item = &TileSnapshot> {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.state == TileState::Npc)
+    }
+}
+
+/// Decodes the raw data of the `level` account as returned by the RPC.
+/// Checks the discriminator and the size before reading the board.
+pub fn decode_board(data: &[u8]) -> Result<BoardSnapshot> {
+    let size = 8 + std::mem::size_of::<GameDataAccount>();
+    if data.len() < size {
+        return Err(ErrorCode::AccountDidNotDeserialize.into());
+    }
+    if data[..8] != GameDataAccount::discriminator() {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    let game: &GameDataAccount = bytemuck::try_from_bytes(&data[8..size])
+        .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))?;
+
+    let mut tiles = Vec::with_capacity(BOARD_SIZE_X * BOARD_SIZE_Y);
+    for x in 0..BOARD_SIZE_X {
+        for y in 0..BOARD_SIZE_Y {
+            let tile = game.board[x][y];
+            tiles.push(TileSnapshot {
+                x,
+                y,
+                state: tile.state.into(),
+                player: tile.player,
+                avatar: tile.avatar,
+                health: tile.health,
+                start_health: tile.start_health,
+                damage: tile.damage,
+                range: tile.range,
+                ship_class: tile.ship_class,
+                collect_reward: tile.collect_reward,
+                look_direction: tile.look_direction,
+                ship_level: tile.ship_level,
+            });
+        }
+    }
+
+    Ok(BoardSnapshot {
+        action_id: game.action_id,
+        last_tick_slot: game.last_tick_slot,
+        last_tick_timestamp: game.last_tick_timestamp,
+        wind_ticks: game.wind_ticks,
+        tiles,
+    })
+}
+
+/// Writes a board snapshot back into account data, the inverse of `decode_board`.
+/// Handy to set up fixtures for tests or on a local validator.
+/// Fails with `TileOutOfBounds` if a tile lies outside of the board.
+pub fn encode_board(board: &BoardSnapshot) -> Result<Vec<u8>> {
+    let mut game = GameDataAccount::default();
+    game.action_id = board.action_id;
+    game.last_tick_slot = board.last_tick_slot;
+    game.last_tick_timestamp = board.last_tick_timestamp;
+    game.wind_ticks = board.wind_ticks;
+    for tile in board.tiles.iter() {
+        if tile.x >= BOARD_SIZE_X || tile.y >= BOARD_SIZE_Y {
+            return err!(SevenSeasError::TileOutOfBounds);
+        }
+        game.board[tile.x][tile.y] = Tile {
+            player: tile.player,
+            state: tile.state.into(),
+            health: tile.health,
+            damage: tile.damage,
+            range: tile.range,
+            ship_class: tile.ship_class,
+            collect_reward: tile.collect_reward,
+            avatar: tile.avatar,
+            look_direction: tile.look_direction,
+            ship_level: tile.ship_level,
+            start_health: tile.start_health,
+        };
+    }
+
+    let mut data = GameDataAccount::discriminator().to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&game));
+    Ok(data)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameActionSnapshot {
+    pub action_id: u64,
+    pub action_type: u8,
+    pub player: Pubkey,
+    pub target: Pubkey,
+    /// Damage dealt or coins collected, depending on the action type
+    pub damage: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+    pub player_tile: (usize, usize),
+    pub target_tile: (usize, usize),
+}
+
+/// Decodes the raw data of the `gameActions` account, oldest action first.
+pub fn decode_game_actions(data: &[u8]) -> Result<Vec<GameActionSnapshot>> {
+    let history = GameActionHistory::try_deserialize(&mut &data[..])?;
+    Ok(history
+        .game_actions
+        .iter()
+        .map(|action| GameActionSnapshot {
+            action_id: action.action_id,
+            action_type: action.action_type,
+            player: action.player,
+            target: action.target,
+            damage: action.damage,
+            slot: action.slot,
+            timestamp: action.timestamp,
+            player_tile: (action.player_x as usize, action.player_y as usize),
+            target_tile: (action.target_x as usize, action.target_y as usize),
+        })
+        .collect())
+}
+
+/// Decodes the return data of a simulated `board_summary` instruction
+pub fn decode_board_summary(return_data: &[u8]) -> Result<BoardSummary> {
+    Ok(BoardSummary::try_from_slice(return_data)?)
+}
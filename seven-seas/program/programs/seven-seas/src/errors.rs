@@ -10,5 +10,15 @@ pub enum SevenSeasError {
     WrongDirectionInput,
     MaxShipLevelReached,
     CouldNotFindAShipToAttack,
+    TickTooEarly,
+    UnknownShipClass,
+    TriedToBoardWithPlayerThatWasNotOnTheBoard,
+    NoShipToBoard,
+    ShipNotDamagedEnoughToBoard,
+    ShipIsStillOnTheBoard,
+    NotTheShipOwner,
+    TokenAmountOverflow,
+    AvatarIsNotTheShipNft,
+    NotEnoughLamportsInChestVault,
 
 }
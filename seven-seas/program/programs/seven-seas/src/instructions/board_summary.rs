@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+use crate::{BoardSummary, GameDataAccount};
+pub use crate::errors::SevenSeasError;
+
+// Read only, meant to be simulated. The summary ends up in the return data of the transaction.
+pub fn board_summary(ctx: Context<BoardSummaryAccounts>) -> Result<BoardSummary> {
+    let game = ctx.accounts.game_data_account.load()?;
+    Ok(game.summary())
+}
+
+#[derive(Accounts)]
+pub struct BoardSummaryAccounts<'info> {
+    #[account(
+        seeds = [b"level"],
+        bump
+    )]
+    pub game_data_account: AccountLoader<'info, GameDataAccount>,
+}
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use crate::{
+    ChestVaultAccount, GameActionHistory, GameConfig, GameDataAccount, KeeperPoolAccount, ADMIN_PUBKEY,
+    THREAD_AUTHORITY_SEED,
+};
+pub use crate::errors::SevenSeasError;
+use anchor_spl::token::{CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+    let seeds = &[b"token_account_owner_pda".as_ref(), &[ctx.bumps["token_account_owner_pda"]]];
+    let signer = &[&seeds[..]];
+
+    // Drain the gold vault into the treasury and give the rent of the vault back to the admin
+    let transfer_instruction = Transfer {
+        from: ctx.accounts.vault_token_account.to_account_info(),
+        to: ctx.accounts.treasury_token_account.to_account_info(),
+        authority: ctx.accounts.token_account_owner_pda.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_instruction,
+        signer,
+    );
+    anchor_spl::token::transfer(cpi_ctx, ctx.accounts.vault_token_account.amount)?;
+
+    let close_instruction = CloseAccount {
+        account: ctx.accounts.vault_token_account.to_account_info(),
+        destination: ctx.accounts.signer.to_account_info(),
+        authority: ctx.accounts.token_account_owner_pda.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        close_instruction,
+        signer,
+    );
+    anchor_spl::token::close_account(cpi_ctx)?;
+
+    // The token owner PDA is a plain program owned account, so it is closed by hand after it signed
+    let token_account_owner_pda = ctx.accounts.token_account_owner_pda.to_account_info();
+    let signer_account = ctx.accounts.signer.to_account_info();
+    **signer_account.try_borrow_mut_lamports()? += token_account_owner_pda.lamports();
+    **token_account_owner_pda.try_borrow_mut_lamports()? = 0;
+    token_account_owner_pda.try_borrow_mut_data()?.fill(0);
+
+    // A running thread would keep ticking a closed game, its lamports go back to the admin
+    if let (Some(clockwork_program), Some(thread)) =
+        (&ctx.accounts.clockwork_program, &ctx.accounts.thread)
+    {
+        let bump = *ctx.bumps.get("thread_authority").unwrap();
+        clockwork_sdk::cpi::thread_delete(CpiContext::new_with_signer(
+            clockwork_program.to_account_info(),
+            clockwork_sdk::cpi::ThreadDelete {
+                authority: ctx.accounts.thread_authority.to_account_info(),
+                close_to: ctx.accounts.signer.to_account_info(),
+                thread: thread.to_account_info(),
+            },
+            &[&[THREAD_AUTHORITY_SEED, &[bump]]],
+        ))?;
+    }
+
+    // The chest vault, keeper pool, level, game actions and config accounts are closed by their
+    // close constraints
+    msg!("Game closed, vaults drained to {}", ctx.accounts.treasury.key());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseGame<'info> {
+    #[account(mut, address = ADMIN_PUBKEY)]
+    pub signer: Signer<'info>,
+    /// CHECK: receives the rewards left in the chest vault
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+    #[account(
+        mut,
+        token::mint = mint_of_token_being_sent,
+        token::authority = treasury
+    )]
+    pub treasury_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"level"],
+        bump
+    )]
+    pub game_data_account: AccountLoader<'info, GameDataAccount>,
+    // Rewards and rent of the chest vault all go to the treasury
+    #[account(
+        mut,
+        close = treasury,
+        seeds = [b"chestVault"],
+        bump
+    )]
+    pub chest_vault: Box<Account<'info, ChestVaultAccount>>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"gameActions"],
+        bump
+    )]
+    pub game_actions: Box<Account<'info, GameActionHistory>>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"gameConfig"],
+        bump
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+    /// CHECK: Derived PDA
+    #[account(
+        mut,
+        seeds=[b"token_account_owner_pda".as_ref()],
+        bump
+    )]
+    pub token_account_owner_pda: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds=[b"token_vault".as_ref(), mint_of_token_being_sent.key().as_ref()],
+        token::mint=mint_of_token_being_sent,
+        token::authority=token_account_owner_pda,
+        bump
+    )]
+    pub vault_token_account: Box<Account<'info, TokenAccount>>,
+    pub mint_of_token_being_sent: Box<Account<'info, Mint>>,
+    pub token_program: Program<'info, Token>,
+    // Bounties nobody cranked go to the treasury. Older games create the pool with
+    // fund_keeper_pool first, an amount of 0 is enough.
+    #[account(
+        mut,
+        close = treasury,
+        seeds = [b"keeperPool"],
+        bump
+    )]
+    pub keeper_pool: Box<Account<'info, KeeperPoolAccount>>,
+    /// Only needed to delete a thread which is still there.
+    #[account(address = clockwork_sdk::ID)]
+    pub clockwork_program: Option<Program<'info, clockwork_sdk::ThreadProgram>>,
+    /// CHECK: clockwork only deletes threads of the thread authority
+    #[account(mut)]
+    pub thread: Option<AccountInfo<'info>>,
+    /// CHECK: the pda that owns and manages the thread
+    #[account(seeds = [THREAD_AUTHORITY_SEED], bump)]
+    pub thread_authority: AccountInfo<'info>,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::{GameDataAccount, Ship, ADMIN_PUBKEY};
+pub use crate::errors::SevenSeasError;
+
+pub fn close_ship(ctx: Context<CloseShip>) -> Result<()> {
+    // After close_game the level account is gone and no ship can be on the board anymore
+    let level = ctx.accounts.game_data_account.to_account_info();
+    if level.owner == &crate::ID && !level.data_is_empty() {
+        let game_data_account = AccountLoader::<GameDataAccount>::try_from(&level)?;
+        let game = game_data_account.load()?;
+        if game.is_on_board(ctx.accounts.nft_account.key()) {
+            return Err(SevenSeasError::ShipIsStillOnTheBoard.into());
+        }
+    }
+    msg!("Ship closed, rent goes back to {}", ctx.accounts.signer.key());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseShip<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    // Ships from before the owner was saved can only be closed by the admin
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"ship", nft_account.key().as_ref()],
+        bump,
+        constraint = ship.owner == signer.key()
+            || (ship.owner == Pubkey::default() && signer.key() == ADMIN_PUBKEY)
+            @ SevenSeasError::NotTheShipOwner
+    )]
+    pub ship: Account<'info, Ship>,
+    /// CHECK:
+    pub nft_account: AccountInfo<'info>,
+    /// CHECK: the level account, it does not exist anymore once the game is closed
+    #[account(
+        seeds = [b"level"],
+        bump
+    )]
+    pub game_data_account: UncheckedAccount<'info>,
+}
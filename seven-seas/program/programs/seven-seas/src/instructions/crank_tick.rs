@@ -0,0 +1,57 @@
+//! Instruction: crank_tick
+//! Permissionless alternative to the clockwork thread. Anyone can move the ships
+//! once the minimum interval passed and gets a small bounty from the keeper pool.
+use anchor_lang::prelude::*;
+use crate::{GameActionHistory, GameDataAccount, KeeperPoolAccount, CRANK_TICK_BOUNTY};
+pub use crate::errors::SevenSeasError;
+
+pub fn crank_tick(ctx: Context<CrankTick>) -> Result<()> {
+    let game = &mut ctx.accounts.game_data_account.load_mut()?;
+    let clock = Clock::get()?;
+
+    if !game.can_crank_tick(clock.slot, clock.unix_timestamp) {
+        return Err(SevenSeasError::TickTooEarly.into());
+    }
+
+    game.tick(clock.slot, clock.unix_timestamp, &mut ctx.accounts.game_actions)?;
+
+    // Only pay the bounty if the pool stays rent exempt, the tick itself still counts.
+    // The chest vault is never touched, its lamports are the escrowed ship and chest rewards.
+    let keeper_pool = ctx.accounts.keeper_pool.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(keeper_pool.data_len());
+    if keeper_pool.lamports() >= rent_exempt_minimum + CRANK_TICK_BOUNTY {
+        **keeper_pool.try_borrow_mut_lamports()? -= CRANK_TICK_BOUNTY;
+        **ctx.accounts.caller.try_borrow_mut_lamports()? += CRANK_TICK_BOUNTY;
+        msg!("Paid crank bounty of {} lamports", CRANK_TICK_BOUNTY);
+    } else {
+        msg!("Keeper pool is empty, no crank bounty paid");
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CrankTick<'info> {
+    #[account(
+        mut,
+        seeds = [b"level"],
+        bump,
+    )]
+    pub game_data_account: AccountLoader<'info, GameDataAccount>,
+    // Funded with fund_keeper_pool
+    #[account(
+        mut,
+        seeds = [b"keeperPool"],
+        bump
+    )]
+    pub keeper_pool: Account<'info, KeeperPoolAccount>,
+    #[account(
+        mut,
+        seeds = [b"gameActions"],
+        bump
+    )]
+    pub game_actions: Account<'info, GameActionHistory>,
+    /// The keeper that cranks the tick and receives the bounty
+    #[account(mut)]
+    pub caller: Signer<'info>,
+}
@@ -0,0 +1,51 @@
+//! Instruction: delete_thread
+use anchor_lang::prelude::*;
+use crate::{ADMIN_PUBKEY, THREAD_AUTHORITY_SEED};
+use clockwork_sdk::state::{Thread};
+
+/// Deletes the thread and returns its lamports to the admin. Threads store the accounts
+/// of the ThreadTick instruction when they are created, so after these change the old
+/// thread has to be deleted and a new one started.
+pub fn delete_thread(ctx: Context<DeleteThread>, _thread_id: Vec<u8>) -> Result<()> {
+    let clockwork_program = &ctx.accounts.clockwork_program;
+    let payer = &ctx.accounts.payer;
+    let thread = &ctx.accounts.thread;
+    let thread_authority = &ctx.accounts.thread_authority;
+
+    // Delete Thread
+    let bump = *ctx.bumps.get("thread_authority").unwrap();
+    clockwork_sdk::cpi::thread_delete(
+        CpiContext::new_with_signer(
+            clockwork_program.to_account_info(),
+            clockwork_sdk::cpi::ThreadDelete {
+                authority: thread_authority.to_account_info(),
+                close_to: payer.to_account_info(),
+                thread: thread.to_account_info(),
+            },
+            &[&[THREAD_AUTHORITY_SEED, &[bump]]],
+        )
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(thread_id: Vec<u8>)]
+pub struct DeleteThread<'info> {
+    /// Only the admin can delete the thread, it receives the lamports of the thread.
+    #[account(mut, address = ADMIN_PUBKEY)]
+    pub payer: Signer<'info>,
+
+    /// The Clockwork thread program.
+    #[account(address = clockwork_sdk::ID)]
+    pub clockwork_program: Program<'info, clockwork_sdk::ThreadProgram>,
+
+    /// The thread to delete.
+    /// CHECK: the address is derived from the thread authority and id
+    #[account(mut, address = Thread::pubkey(thread_authority.key(), thread_id))]
+    pub thread: AccountInfo<'info>,
+
+    /// The pda that owns and manages the thread.
+    #[account(seeds = [THREAD_AUTHORITY_SEED], bump)]
+    pub thread_authority: SystemAccount<'info>,
+}
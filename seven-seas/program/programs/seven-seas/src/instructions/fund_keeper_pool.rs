@@ -0,0 +1,36 @@
+//! Instruction: fund_keeper_pool
+//! Anyone can top up the pool which pays the crank_tick bounties. It is kept apart from
+//! the chest vault, which holds the escrowed rewards of the ships and chests.
+use anchor_lang::prelude::*;
+use crate::KeeperPoolAccount;
+pub use crate::errors::SevenSeasError;
+
+pub fn fund_keeper_pool(ctx: Context<FundKeeperPool>, amount: u64) -> Result<()> {
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: ctx.accounts.signer.to_account_info(),
+            to: ctx.accounts.keeper_pool.to_account_info(),
+        },
+    );
+    anchor_lang::system_program::transfer(cpi_context, amount)?;
+
+    msg!("Added {} lamports to the keeper pool", amount);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FundKeeperPool<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    // Created by the first top up, so games started before the pool existed can fund it too
+    #[account(
+        init_if_needed,
+        seeds = [b"keeperPool"],
+        bump,
+        payer = signer,
+        space = 8
+    )]
+    pub keeper_pool: Account<'info, KeeperPoolAccount>,
+    pub system_program: Program<'info, System>,
+}
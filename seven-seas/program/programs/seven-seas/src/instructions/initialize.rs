@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::{GameDataAccount, ChestVaultAccount, GameActionHistory};
+use crate::{GameDataAccount, ChestVaultAccount, GameActionHistory, GameConfig};
 pub use crate::errors::SevenSeasError;
 use anchor_spl::{
     token::{Mint, Token, TokenAccount},
@@ -7,6 +7,7 @@ use anchor_spl::{
 use anchor_lang::prelude::Account;
 
 pub fn initialize(_ctx: Context<InitializeAccounts>) -> Result<()> {
+    _ctx.accounts.game_config.set_inner(GameConfig::default());
     msg!("Initialized!");
     Ok(())
 }
@@ -44,6 +45,15 @@ pub struct InitializeAccounts<'info> {
         space = 4096
     )]
     pub game_actions: Box<Account<'info, GameActionHistory>>,
+    // Rewards of this game instance, starts with the defaults from lib.rs
+    #[account(
+        init,
+        seeds = [b"gameConfig"],
+        bump,
+        payer = signer,
+        space = 8 + 5 * 8
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
     /// CHECK: Derived PDAs
     #[account(
         init,
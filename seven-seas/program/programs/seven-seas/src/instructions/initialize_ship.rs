@@ -1,15 +1,18 @@
 use anchor_lang::prelude::*;
-use crate::Ship;
+use crate::{Ship, ShipClass};
 pub use crate::errors::SevenSeasError;
 use anchor_lang::prelude::Account;
 
-pub fn initialize_ship(ctx: Context<InitializeShip>) -> Result<()> {
-    msg!("Ship Initialized!");
-    ctx.accounts.new_ship.health = 50;
-    ctx.accounts.new_ship.start_health = 50;
+pub fn initialize_ship(ctx: Context<InitializeShip>, class: u8) -> Result<()> {
+    let ship_class = ShipClass::get(class)?;
+    msg!("Ship Initialized! Class {}", class);
+    ctx.accounts.new_ship.class = class;
+    ctx.accounts.new_ship.owner = ctx.accounts.signer.key();
+    ctx.accounts.new_ship.health = ship_class.health;
+    ctx.accounts.new_ship.start_health = ship_class.health;
     ctx.accounts.new_ship.level = 1;
     ctx.accounts.new_ship.upgrades = 0;
-    ctx.accounts.new_ship.cannons = 1;
+    ctx.accounts.new_ship.cannons = ship_class.cannons;
     Ok(())
 }
 
@@ -2,21 +2,39 @@
 pub mod start_thread;
 pub mod pause_thread;
 pub mod resume_thread;
+pub mod delete_thread;
+pub mod crank_tick;
+pub mod fund_keeper_pool;
 pub mod initialize;
+pub mod update_game_config;
 pub mod initialize_ship;
 pub mod upgrade_ship;
+pub mod close_ship;
+pub mod close_game;
 pub mod spawn_player;
+pub mod spawn_npc;
 pub mod shoot;
+pub mod board_ship;
 pub mod move_player;
 pub mod cthulhu;
+pub mod board_summary;
 
 pub use start_thread::*;
 pub use pause_thread::*;
 pub use resume_thread::*;
+pub use delete_thread::*;
+pub use crank_tick::*;
+pub use fund_keeper_pool::*;
 pub use initialize::*;
+pub use update_game_config::*;
 pub use initialize_ship::*;
 pub use upgrade_ship::*;
+pub use close_ship::*;
+pub use close_game::*;
 pub use spawn_player::*;
+pub use spawn_npc::*;
 pub use shoot::*;
+pub use board_ship::*;
 pub use move_player::*;
 pub use cthulhu::*;
+pub use board_summary::*;
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::{GameDataAccount, GameActionHistory};
+use crate::{GameDataAccount, GameActionHistory, GameConfig};
 pub use crate::errors::SevenSeasError;
 use anchor_spl::{
     token::{Mint, Token, TokenAccount}, associated_token::AssociatedToken,
@@ -18,6 +18,8 @@ pub fn move_player_v2(ctx: Context<MovePlayer>, direction: u8) -> Result<()> {
         ctx.accounts.token_program.to_account_info(),
         ctx.bumps["token_account_owner_pda"],
         &mut ctx.accounts.game_actions,
+        &ctx.accounts.game_config,
+        ctx.accounts.mint_of_token_being_sent.decimals,
     ) {
         Ok(_val) => {}
         Err(err) => {
@@ -69,4 +71,9 @@ pub struct MovePlayer<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
     #[account(mut)]
     pub game_actions: Account<'info, GameActionHistory>,
+    #[account(
+        seeds = [b"gameConfig"],
+        bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
 }
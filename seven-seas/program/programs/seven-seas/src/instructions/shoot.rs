@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::{ GameDataAccount, GameActionHistory};
+use crate::{ GameDataAccount, GameActionHistory, GameConfig};
 pub use crate::errors::SevenSeasError;
 use anchor_spl::{
     token::{Mint, Token, TokenAccount}, associated_token::AssociatedToken,
@@ -17,6 +17,8 @@ pub fn shoot(ctx: Context<Shoot>) -> Result<()> {
         ctx.accounts.token_account_owner_pda.to_account_info(),
         ctx.accounts.token_program.to_account_info(),
         ctx.bumps["token_account_owner_pda"],
+        &ctx.accounts.game_config,
+        ctx.accounts.mint_of_token_being_sent.decimals,
     ) {
         Ok(_val) => {}
         Err(err) => {
@@ -40,6 +42,11 @@ pub struct Shoot<'info> {
     pub game_data_account: AccountLoader<'info, GameDataAccount>,
     #[account(mut)]
     pub game_actions: Account<'info, GameActionHistory>,
+    #[account(
+        seeds = [b"gameConfig"],
+        bump
+    )]
+    pub game_config: Account<'info, GameConfig>,
     #[account(mut)]
     pub player: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::{ChestVaultAccount, GameConfig, GameDataAccount, ADMIN_PUBKEY};
+pub use crate::errors::SevenSeasError;
+
+pub fn spawn_npc(ctx: Context<SpawnNpc>, avatar: Pubkey) -> Result<()> {
+    let game = &mut ctx.accounts.game_data_account.load_mut()?;
+    let npc_kill_reward = ctx.accounts.game_config.npc_kill_reward;
+    game.spawn_npc(avatar, npc_kill_reward)?;
+
+    // The admin funds the kill reward so whoever sinks the NPC can be paid from the vault
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: ctx.accounts.signer.to_account_info(),
+            to: ctx.accounts.chest_vault.to_account_info(),
+        },
+    );
+    anchor_lang::system_program::transfer(cpi_context, npc_kill_reward)?;
+
+    msg!("Spawned NPC {}", avatar);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SpawnNpc<'info> {
+    // Only the admin can put NPCs on the board
+    #[account(
+        mut,
+        address = ADMIN_PUBKEY
+    )]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"chestVault"],
+        bump
+    )]
+    pub chest_vault: Account<'info, ChestVaultAccount>,
+    #[account(
+        mut,
+        seeds = [b"level"],
+        bump,
+    )]
+    pub game_data_account: AccountLoader<'info, GameDataAccount>,
+    #[account(
+        seeds = [b"gameConfig"],
+        bump
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,22 +1,31 @@
 use anchor_lang::prelude::*;
-use crate::{PLAYER_KILL_REWARD, PLAY_GAME_FEE, Ship, ChestVaultAccount, GameDataAccount, CHEST_REWARD};
+use crate::{PLAY_GAME_FEE, Ship, ShipClass, ChestVaultAccount, GameDataAccount, GameConfig};
 pub use crate::errors::SevenSeasError;
 use anchor_spl::{
     token::{Mint, Token, TokenAccount}, associated_token::AssociatedToken,
 };
 
 pub fn spawn_player(ctx: Context<SpawnPlayer>, avatar: Pubkey) -> Result<()> {
+    // The avatar of the tile is how close_ship finds the ship on the board
+    if avatar != ctx.accounts.nft_account.key() {
+        return Err(SevenSeasError::AvatarIsNotTheShipNft.into());
+    }
     let game = &mut ctx.accounts.game_data_account.load_mut()?;
     let ship = &mut ctx.accounts.ship;
+    ship.player = ctx.accounts.player.key();
+    let config = &ctx.accounts.game_config;
     
     let decimals = ctx.accounts.cannon_mint.decimals;
-    ship.cannons = ctx.accounts.cannon_token_account.amount / ((u64::pow(10, decimals as u32) as u64));
+    // The cannon tokens come on top of the cannons of the class
+    let ship_class = ShipClass::get(ship.class)?;
+    ship.cannons = ship_class.cannons
+        + ctx.accounts.cannon_token_account.amount / ((u64::pow(10, decimals as u32) as u64));
 
     let extra_health = ctx.accounts.rum_token_account.amount / ((u64::pow(10, decimals as u32) as u64));
 
     msg!("Spawned player! With {} cannons", ship.cannons);
 
-    match game.spawn_player(ctx.accounts.player.to_account_info(), avatar, ship, extra_health) {
+    match game.spawn_player(ctx.accounts.player.to_account_info(), avatar, ship, extra_health, config) {
         Ok(_val) => {
             let cpi_context = CpiContext::new(
                 ctx.accounts.system_program.to_account_info().clone(),
@@ -27,14 +36,14 @@ pub fn spawn_player(ctx: Context<SpawnPlayer>, avatar: Pubkey) -> Result<()> {
             );
             anchor_lang::system_program::transfer(
                 cpi_context,
-                PLAYER_KILL_REWARD + PLAY_GAME_FEE,
+                config.player_kill_reward + PLAY_GAME_FEE,
             )?;
         }
         Err(err) => {
             panic!("Error: {}", err);
         }
     }
-    match game.spawn_chest(ctx.accounts.player.to_account_info()) {
+    match game.spawn_chest(ctx.accounts.player.to_account_info(), config) {
         Ok(_val) => {
             let cpi_context = CpiContext::new(
                 ctx.accounts.system_program.to_account_info().clone(),
@@ -43,7 +52,7 @@ pub fn spawn_player(ctx: Context<SpawnPlayer>, avatar: Pubkey) -> Result<()> {
                     to: ctx.accounts.chest_vault.to_account_info().clone(),
                 },
             );
-            anchor_lang::system_program::transfer(cpi_context, CHEST_REWARD)?;
+            anchor_lang::system_program::transfer(cpi_context, config.chest_reward)?;
         }
         Err(err) => {
             panic!("Error: {}", err);
@@ -67,6 +76,11 @@ pub struct SpawnPlayer<'info> {
     pub chest_vault: Account<'info, ChestVaultAccount>,
     #[account(mut)]
     pub game_data_account: AccountLoader<'info, GameDataAccount>,
+    #[account(
+        seeds = [b"gameConfig"],
+        bump
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
     #[account(
         mut,
         seeds = [b"ship", nft_account.key().as_ref()],
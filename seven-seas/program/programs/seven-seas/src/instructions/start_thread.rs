@@ -22,6 +22,7 @@ pub fn start_thread(ctx: Context<StartThread>, thread_id: Vec<u8>) -> Result<()>
         program_id: ID,
         accounts: crate::__client_accounts_thread_tick::ThreadTick {
             game_data: game_data.key(),
+            game_actions: Pubkey::find_program_address(&[b"gameActions"], &ID).0,
             thread: thread.key(),
             thread_authority: thread_authority.key(),
         }
@@ -84,10 +85,17 @@ pub struct StartThread<'info> {
     pub thread_authority: SystemAccount<'info>,
 }
 
+/// The thread stores these accounts when it is created. Threads started before
+/// `game_actions` was added fail on every tick, delete them with `delete_thread` and
+/// start a new thread.
 #[derive(Accounts)]
 pub struct ThreadTick<'info> {
     #[account(mut)]
     pub game_data: AccountLoader<'info, GameDataAccount>,
+
+    /// NPCs firing during the tick are added to the game actions
+    #[account(mut, seeds = [b"gameActions"], bump)]
+    pub game_actions: Account<'info, GameActionHistory>,
     
     /// Verify that only this thread can execute the ThreadTick Instruction
     #[account(signer, constraint = thread.authority.eq(&thread_authority.key()))]
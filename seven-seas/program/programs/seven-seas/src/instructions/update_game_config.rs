@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::{GameConfig, ADMIN_PUBKEY};
+pub use crate::errors::SevenSeasError;
+
+pub fn update_game_config(
+    ctx: Context<UpdateGameConfig>,
+    player_kill_reward: u64,
+    chest_reward: u64,
+    chest_coin_reward: u64,
+    destroy_ship_coin_reward: u64,
+    npc_kill_reward: u64,
+) -> Result<()> {
+    // Ships and chests already on the board keep the reward they were spawned with,
+    // their lamports are already in the chest vault.
+    let config = &mut ctx.accounts.game_config;
+    config.player_kill_reward = player_kill_reward;
+    config.chest_reward = chest_reward;
+    config.chest_coin_reward = chest_coin_reward;
+    config.destroy_ship_coin_reward = destroy_ship_coin_reward;
+    config.npc_kill_reward = npc_kill_reward;
+    msg!(
+        "Game config updated: kill {} chest {} chest coins {} kill coins {} npc kill {}",
+        player_kill_reward,
+        chest_reward,
+        chest_coin_reward,
+        destroy_ship_coin_reward,
+        npc_kill_reward
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateGameConfig<'info> {
+    #[account(mut, address = ADMIN_PUBKEY)]
+    pub signer: Signer<'info>,
+    // init_if_needed so games initialized before the config existed can create it
+    #[account(
+        init_if_needed,
+        payer = signer,
+        seeds = [b"gameConfig"],
+        bump,
+        space = 8 + 5 * 8
+    )]
+    pub game_config: Account<'info, GameConfig>,
+    pub system_program: Program<'info, System>,
+}
@@ -6,7 +6,7 @@ use anchor_spl::{
 };
 use anchor_lang::prelude::Account;
 
-use crate::{TOKEN_DECIMAL_MULTIPLIER, Ship};
+use crate::{token_amount, Ship, ShipClass};
 
 pub fn upgrade_ship(ctx: Context<UpgradeShip>) -> Result<()> {        
     let transfer_instruction = Transfer {
@@ -20,33 +20,18 @@ pub fn upgrade_ship(ctx: Context<UpgradeShip>) -> Result<()> {
         transfer_instruction
     );
 
-    let cost: u64;
-    match ctx.accounts.new_ship.upgrades {
-        0 => {
-            ctx.accounts.new_ship.health = 100;
-            ctx.accounts.new_ship.upgrades = 1;                
-            cost = 5;
-        },
-        1 => {
-            ctx.accounts.new_ship.health = 150;
-            ctx.accounts.new_ship.upgrades = 2;                
-            cost = 200;
-        },
-        2 => {
-            ctx.accounts.new_ship.health = 300;
-            ctx.accounts.new_ship.upgrades = 3;                
-            cost = 1500;
-        },
-        3 => {
-            ctx.accounts.new_ship.health = 500;
-            ctx.accounts.new_ship.upgrades = 4;                
-            cost = 25000;
-        }
-        _ => {
+    let ship_class = ShipClass::get(ctx.accounts.new_ship.class)?;
+    let upgrade = match ship_class.upgrades.get(ctx.accounts.new_ship.upgrades as usize) {
+        Some(upgrade) => upgrade,
+        None => {
             return Err(SevenSeasError::MaxShipLevelReached.into());
-        }  
-    }
-    anchor_spl::token::transfer(cpi_ctx, cost * TOKEN_DECIMAL_MULTIPLIER)?;           
+        }
+    };
+    ctx.accounts.new_ship.health = upgrade.health;
+    ctx.accounts.new_ship.start_health = upgrade.health;
+    ctx.accounts.new_ship.upgrades += 1;
+    let cost = upgrade.cost;
+    anchor_spl::token::transfer(cpi_ctx, token_amount(cost, ctx.accounts.mint_of_token_being_sent.decimals)?)?;           
 
     msg!("Ship upgraded to level: {}", ctx.accounts.new_ship.upgrades);
 
@@ -4,21 +4,39 @@ pub mod errors;
 pub mod state;
 pub use state::*;
 pub mod instructions;
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "client")]
+pub mod replay;
 
 use anchor_lang::prelude::Account;
 use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use solana_program::pubkey;
 use instructions::*;
 
 // This is your program's public key and it will update
 // automatically when you build the project.
 declare_id!("2a4NcnkF5zf14JQXHAv39AsRf7jMFj13wKmTL6ZcDQNd");
 
+// Defaults for the GameConfig account, the admin can change them with update_game_config
 pub const PLAYER_KILL_REWARD: u64 = LAMPORTS_PER_SOL / 20; // 0.05 SOL
 pub const CHEST_REWARD: u64 = LAMPORTS_PER_SOL / 20; // 0.05 SOL
+pub const CHEST_COIN_REWARD: u64 = 10; // gold tokens
+pub const DESTROY_SHIP_COIN_REWARD: u64 = 10; // gold tokens per ship level
+pub const NPC_KILL_REWARD: u64 = LAMPORTS_PER_SOL / 40; // 0.025 SOL, paid by the admin for every NPC
+
+// TODO: run "solana address" cli command and update ADMIN_PUBKEY with your own pubkey
+pub const ADMIN_PUBKEY: Pubkey = pubkey!("ownSX1SCfotCS3TMmkZtnrGPdjsVwf5E9sAG94eNQS2");
 
 //pub const PLAY_GAME_FEE: u64 = LAMPORTS_PER_SOL / 50; // 0.02 SOL
 pub const PLAY_GAME_FEE: u64 = 0; // 0.00 SOL
 
+// Keepers calling crank_tick get this bounty from the keeper pool for every tick
+pub const CRANK_TICK_BOUNTY: u64 = LAMPORTS_PER_SOL / 10000; // 0.0001 SOL
+// Same pace as the clockwork cron (every 2 seconds), the slot check guards against a stale clock
+pub const CRANK_TICK_MIN_SLOTS: u64 = 4;
+pub const CRANK_TICK_MIN_SECONDS: i64 = 2;
+
 /// Seed for thread_authority PDA.
 pub const THREAD_AUTHORITY_SEED: &[u8] = b"authority";
 
@@ -31,14 +49,40 @@ pub mod seven_seas {
         instructions::initialize::initialize(_ctx)
     }
 
-    pub fn initialize_ship(ctx: Context<InitializeShip>) -> Result<()> {
-        instructions::initialize_ship::initialize_ship(ctx)
+    pub fn initialize_ship(ctx: Context<InitializeShip>, class: u8) -> Result<()> {
+        instructions::initialize_ship::initialize_ship(ctx, class)
     }
 
     pub fn upgrade_ship(ctx: Context<UpgradeShip>) -> Result<()> {
         instructions::upgrade_ship::upgrade_ship(ctx)
     }
 
+    pub fn close_ship(ctx: Context<CloseShip>) -> Result<()> {
+        instructions::close_ship(ctx)
+    }
+
+    pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+        instructions::close_game(ctx)
+    }
+
+    pub fn update_game_config(
+        ctx: Context<UpdateGameConfig>,
+        player_kill_reward: u64,
+        chest_reward: u64,
+        chest_coin_reward: u64,
+        destroy_ship_coin_reward: u64,
+        npc_kill_reward: u64,
+    ) -> Result<()> {
+        instructions::update_game_config(
+            ctx,
+            player_kill_reward,
+            chest_reward,
+            chest_coin_reward,
+            destroy_ship_coin_reward,
+            npc_kill_reward,
+        )
+    }
+
     pub fn reset(_ctx: Context<Reset>) -> Result<()> {
         _ctx.accounts.game_data_account.load_mut()?.reset()
     }
@@ -62,15 +106,32 @@ pub mod seven_seas {
         instructions::resume_thread(ctx, thread_id)
     }
 
+    pub fn delete_thread(ctx: Context<DeleteThread>, thread_id: Vec<u8>) -> Result<()> {
+        instructions::delete_thread(ctx, thread_id)
+    }
+
     pub fn on_thread_tick(ctx: Context<ThreadTick>) -> Result<()> {
         let game = &mut ctx.accounts.game_data.load_mut()?;
-        game.move_in_direction_by_thread()
+        let clock = Clock::get()?;
+        game.tick(clock.slot, clock.unix_timestamp, &mut ctx.accounts.game_actions)
+    }
+
+    pub fn crank_tick(ctx: Context<CrankTick>) -> Result<()> {
+        instructions::crank_tick(ctx)
+    }
+
+    pub fn fund_keeper_pool(ctx: Context<FundKeeperPool>, amount: u64) -> Result<()> {
+        instructions::fund_keeper_pool(ctx, amount)
     }
 
     pub fn spawn_player(ctx: Context<SpawnPlayer>, avatar: Pubkey) -> Result<()> {
         instructions::spawn_player(ctx, avatar)
     }
 
+    pub fn spawn_npc(ctx: Context<SpawnNpc>, avatar: Pubkey) -> Result<()> {
+        instructions::spawn_npc(ctx, avatar)
+    }
+
     pub fn cthulhu(ctx: Context<Cthulhu>, _block_bump: u8) -> Result<()> {
         instructions::cthulhu(ctx)
     }
@@ -79,6 +140,14 @@ pub mod seven_seas {
         instructions::shoot(ctx)
     }
 
+    pub fn board_ship(ctx: Context<BoardShip>, direction: u8, _block_bump: u8) -> Result<()> {
+        instructions::board_ship(ctx, direction)
+    }
+
+    pub fn board_summary(ctx: Context<BoardSummaryAccounts>) -> Result<BoardSummary> {
+        instructions::board_summary(ctx)
+    }
+
     pub fn move_player_v2(ctx: Context<MovePlayer>, direction: u8, _block_bump: u8) -> Result<()> {
         instructions::move_player_v2(ctx, direction)
     }
@@ -0,0 +1,192 @@
+//! Rebuilds the board timeline from a `level` snapshot and the game actions that followed
+//! and writes it as a compact JSON lines replay. Enable with the `client` feature.
+//!
+//! Wind and normal moves are not game actions, so ships are put on the tile the next
+//! action says they were on. Ships spawned after the snapshot only show up once they
+//! are part of an action.
+use crate::client::{BoardSnapshot, GameActionSnapshot, TileSnapshot, TileState};
+use crate::state::{
+    GAME_ACTION_SHIP_BOARDED, GAME_ACTION_SHIP_COINS_COLLECTED,
+    GAME_ACTION_SHIP_CTHULUH_ATTACKED_SHIP, GAME_ACTION_SHIP_SHOT, GAME_ACTION_SHIP_TAKEN_DAMAGE,
+};
+use anchor_lang::prelude::*;
+use serde_json::{json, Value};
+use std::io::Write;
+
+pub struct ReplayFrame {
+    pub action: GameActionSnapshot,
+    /// Tiles which look different after the action
+    pub changes: Vec<TileSnapshot>,
+}
+
+/// Replays the actions on top of the snapshot. Actions can come from several dumps of
+/// the `gameActions` account, duplicates and actions older than the snapshot are skipped.
+pub fn replay(start: &BoardSnapshot, actions: &[GameActionSnapshot]) -> Vec<ReplayFrame> {
+    let mut actions: Vec<GameActionSnapshot> = actions
+        .iter()
+        .filter(|action| action.action_id >= start.action_id)
+        .cloned()
+        .collect();
+    actions.sort_by_key(|action| action.action_id);
+    actions.dedup_by_key(|action| action.action_id);
+
+    let mut board = start.clone();
+    let mut frames = Vec::with_capacity(actions.len());
+    for action in actions {
+        let before = board.tiles.clone();
+        apply(&mut board, &action);
+        let changes = board
+            .tiles
+            .iter()
+            .zip(before.iter())
+            .filter(|(after, before)| after != before)
+            .map(|(after, _)| after.clone())
+            .collect();
+        frames.push(ReplayFrame { action, changes });
+    }
+    frames
+}
+
+fn apply(board: &mut BoardSnapshot, action: &GameActionSnapshot) {
+    place_ship(board, action.player, action.player_tile);
+
+    match action.action_type {
+        GAME_ACTION_SHIP_TAKEN_DAMAGE | GAME_ACTION_SHIP_CTHULUH_ATTACKED_SHIP => {
+            let (x, y) = action.target_tile;
+            let by_npc = action.action_type == GAME_ACTION_SHIP_TAKEN_DAMAGE
+                && board
+                    .tile(action.player_tile.0, action.player_tile.1)
+                    .map_or(false, |attacker| attacker.state == TileState::Npc);
+            if let Some(tile) = board.tile_mut(x, y) {
+                if tile.state == TileState::Player
+                    || tile.state == TileState::Npc
+                    || tile.state == TileState::Disabled
+                {
+                    tile.health = tile.health.saturating_sub(action.damage);
+                    if tile.health == 0 && by_npc {
+                        *tile = npc_wreck(tile);
+                    } else if tile.health == 0 {
+                        *tile = TileSnapshot::empty(x, y);
+                    }
+                }
+            }
+        }
+        GAME_ACTION_SHIP_BOARDED => {
+            let (x, y) = action.target_tile;
+            if let Some(tile) = board.tile_mut(x, y) {
+                if tile.state == TileState::Player {
+                    tile.state = TileState::Disabled;
+                }
+            }
+        }
+        // Shots only matter for the damage actions that follow them and collected
+        // chests were already replaced by the ship moving onto them.
+        _ => {}
+    }
+}
+
+/// Ships sunk by an NPC become a chest with the kill reward, like `npc_fire` does on chain
+fn npc_wreck(ship: &TileSnapshot) -> TileSnapshot {
+    TileSnapshot {
+        state: TileState::Chest,
+        health: 1,
+        start_health: 1,
+        damage: 0,
+        range: 0,
+        ship_class: 0,
+        look_direction: 0,
+        ship_level: 0,
+        ..ship.clone()
+    }
+}
+
+fn place_ship(board: &mut BoardSnapshot, ship: Pubkey, position: (usize, usize)) {
+    let current = board.tiles.iter().position(|tile| {
+        tile.player == ship && (tile.state == TileState::Player || tile.state == TileState::Npc)
+    });
+    let Some(index) = current else {
+        return;
+    };
+
+    let (x, y) = (board.tiles[index].x, board.tiles[index].y);
+    if (x, y) == position || board.tile(position.0, position.1).is_none() {
+        return;
+    }
+
+    let mut moved = board.tiles[index].clone();
+    moved.x = position.0;
+    moved.y = position.1;
+    board.tiles[index] = TileSnapshot::empty(x, y);
+    if let Some(tile) = board.tile_mut(position.0, position.1) {
+        *tile = moved;
+    }
+}
+
+fn action_name(action_type: u8) -> &'static str {
+    match action_type {
+        GAME_ACTION_SHIP_SHOT => "shot",
+        GAME_ACTION_SHIP_TAKEN_DAMAGE => "damage",
+        GAME_ACTION_SHIP_CTHULUH_ATTACKED_SHIP => "cthulhu",
+        GAME_ACTION_SHIP_COINS_COLLECTED => "coins",
+        GAME_ACTION_SHIP_BOARDED => "boarded",
+        _ => "unknown",
+    }
+}
+
+fn tile_json(tile: &TileSnapshot) -> Value {
+    let state = match tile.state {
+        TileState::Empty => return json!({ "x": tile.x, "y": tile.y, "state": "empty" }),
+        TileState::Player => "ship".to_string(),
+        TileState::Chest => "chest".to_string(),
+        TileState::Npc => "npc".to_string(),
+        TileState::Disabled => "disabled".to_string(),
+        TileState::Unknown(state) => state.to_string(),
+    };
+    json!({
+        "x": tile.x,
+        "y": tile.y,
+        "state": state,
+        "player": tile.player.to_string(),
+        "avatar": tile.avatar.to_string(),
+        "health": tile.health,
+        "start_health": tile.start_health,
+        "look": tile.look_direction,
+    })
+}
+
+/// Writes one line with the occupied tiles of the snapshot and then one line per action
+/// with the tiles it changed.
+pub fn write_replay<W: Write>(
+    mut writer: W,
+    start: &BoardSnapshot,
+    frames: &[ReplayFrame],
+) -> std::io::Result<()> {
+    let start_line = json!({
+        "type": "start",
+        "action_id": start.action_id,
+        "slot": start.last_tick_slot,
+        "timestamp": start.last_tick_timestamp,
+        "tiles": start.occupied().map(tile_json).collect::<Vec<Value>>(),
+    });
+    writeln!(writer, "{}", start_line)?;
+
+    for frame in frames {
+        let action = &frame.action;
+        let line = json!({
+            "type": "action",
+            "action_id": action.action_id,
+            "slot": action.slot,
+            "timestamp": action.timestamp,
+            "action": action_name(action.action_type),
+            "player": action.player.to_string(),
+            "from": [action.player_tile.0, action.player_tile.1],
+            "target": action.target.to_string(),
+            "to": [action.target_tile.0, action.target_tile.1],
+            "amount": action.damage,
+            "changes": frame.changes.iter().map(tile_json).collect::<Vec<Value>>(),
+        });
+        writeln!(writer, "{}", line)?;
+    }
+
+    Ok(())
+}
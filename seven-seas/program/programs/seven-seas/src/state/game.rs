@@ -1,25 +1,35 @@
 pub use crate::errors::SevenSeasError;
-use crate::PLAYER_KILL_REWARD;
-use crate::{Ship, CHEST_REWARD};
+use crate::{token_amount, GameConfig, Ship, ShipClass, SHIP_CLASS_FRIGATE};
+use crate::{CRANK_TICK_MIN_SECONDS, CRANK_TICK_MIN_SLOTS};
 use anchor_lang::prelude::*;
 use anchor_spl::token::Transfer;
 
-const BOARD_SIZE_X: usize = 10;
-const BOARD_SIZE_Y: usize = 10;
+pub const BOARD_SIZE_X: usize = 10;
+pub const BOARD_SIZE_Y: usize = 10;
 
-const STATE_EMPTY: u8 = 0;
-const STATE_PLAYER: u8 = 1;
-const STATE_CHEST: u8 = 2;
+pub const STATE_EMPTY: u8 = 0;
+pub const STATE_PLAYER: u8 = 1;
+pub const STATE_CHEST: u8 = 2;
+pub const STATE_NPC: u8 = 3;
+pub const STATE_DISABLED: u8 = 4; // boarded ship, can be sunk but not move or shoot anymore
 
-const GAME_ACTION_SHIP_SHOT: u8 = 0;
-const GAME_ACTION_SHIP_TAKEN_DAMAGE: u8 = 1;
-const GAME_ACTION_SHIP_CTHULUH_ATTACKED_SHIP: u8 = 2;
-const GAME_ACTION_SHIP_COINS_COLLECTED: u8 = 3;
+pub const GAME_ACTION_SHIP_SHOT: u8 = 0;
+pub const GAME_ACTION_SHIP_TAKEN_DAMAGE: u8 = 1;
+pub const GAME_ACTION_SHIP_CTHULUH_ATTACKED_SHIP: u8 = 2;
+pub const GAME_ACTION_SHIP_COINS_COLLECTED: u8 = 3;
+pub const GAME_ACTION_SHIP_BOARDED: u8 = 4;
 
-const CHEST_COIN_REWARD: u64 = 10;
-const DESTROY_SHIP_COIN_REWARD: u64 = 10;
+const NPC_KILL_COIN_REWARD: u64 = 5;
 
-pub const TOKEN_DECIMAL_MULTIPLIER: u64 = 1000000000;
+const NPC_HEALTH: u64 = 60;
+const NPC_DAMAGE: u64 = 1;
+const NPC_CHASE_DISTANCE: usize = 4;
+const NPC_FLEE_HEALTH_PERCENT: u64 = 30;
+
+// Ships can only be boarded once their health dropped to a quarter of the start health
+const BOARDING_HEALTH_PERCENT: u64 = 25;
+// Part of the kill reward and kill coins the boarding crew takes
+const BOARDING_LOOT_PERCENT: u64 = 50;
 
 #[derive(Accounts)]
 pub struct Reset<'info> {
@@ -53,42 +63,126 @@ pub struct ResetShip<'info> {
 #[repr(packed)]
 #[derive(Default)]
 pub struct GameDataAccount {
-    board: [[Tile; BOARD_SIZE_X]; BOARD_SIZE_Y],
-    action_id: u64,
+    pub(crate) board: [[Tile; BOARD_SIZE_X]; BOARD_SIZE_Y],
+    pub(crate) action_id: u64,
+    pub(crate) last_tick_slot: u64, // 8 slot of the last wind tick (thread or crank)
+    pub(crate) last_tick_timestamp: i64, // 8 unix timestamp of the last wind tick
+    pub(crate) wind_ticks: u32,     // 4 slow ships only move on every second tick
 }
 
+/// `range` used to be a u16. Ranges never went above 2, so its high byte was always zero
+/// and now holds `ship_class`. The offsets of all fields stay the same and ships already
+/// on the board read as frigates, so a deployed game account keeps working without a reset.
+/// Changing the size of a tile moves every tile of the board though: that needs a new
+/// layout, the game account has to be reset and all ships spawned again.
 #[zero_copy(unsafe)]
 #[repr(packed)]
 #[derive(Default)]
 pub struct Tile {
-    player: Pubkey,      // 32
-    state: u8,           // 1
-    health: u64,         // 8
-    damage: u64,         // 8
-    range: u16,          // 2
-    collect_reward: u64, // 8
-    avatar: Pubkey,      // 32 used in the client to display the avatar
-    look_direction: u8,  // 1 (Up, right, down, left)
-    ship_level: u16,     // 2
-    start_health: u64,   // 8 (used to calculate the length of the health bar in the client)
+    pub(crate) player: Pubkey,      // 32
+    pub(crate) state: u8,           // 1
+    pub(crate) health: u64,         // 8
+    pub(crate) damage: u64,         // 8
+    pub(crate) range: u8,           // 1
+    pub(crate) ship_class: u8,      // 1 speed of the ship and used in the client to pick the model
+    pub(crate) collect_reward: u64, // 8
+    pub(crate) avatar: Pubkey,      // 32 nft of the ship, used in the client to display the avatar
+    pub(crate) look_direction: u8,  // 1 (Up, right, down, left)
+    pub(crate) ship_level: u16,     // 2
+    pub(crate) start_health: u64, // 8 (used to calculate the length of the health bar in the client)
 }
 
+// Pins the layout of the game account, it has to keep fitting the 10240 bytes of `initialize`
+const _: () = assert!(std::mem::size_of::<Tile>() == 102);
+const _: () = assert!(8 + std::mem::size_of::<GameDataAccount>() <= 10240);
+
 #[account]
 pub struct GameActionHistory {
-    id_counter: u64,
-    game_actions: Vec<GameAction>,
+    pub(crate) id_counter: u64,
+    pub(crate) game_actions: Vec<GameAction>,
 }
 
-#[derive(Debug, Clone, AnchorSerialize, AnchorDeserialize)]
+#[derive(Debug, Clone, Default, AnchorSerialize, AnchorDeserialize)]
 pub struct GameAction {
-    action_id: u64,  // 1
-    action_type: u8, // 1
-    player: Pubkey,  // 32
-    target: Pubkey,  // 32
-    damage: u64,     // 8
+    pub(crate) action_id: u64,  // 1
+    pub(crate) action_type: u8, // 1
+    pub(crate) player: Pubkey,  // 32
+    pub(crate) target: Pubkey,  // 32
+    pub(crate) damage: u64,     // 8
+    pub(crate) slot: u64,       // 8 set when the action is added
+    pub(crate) timestamp: i64,  // 8 set when the action is added
+    pub(crate) player_x: u8,    // 1 tile of the player when the action happened
+    pub(crate) player_y: u8,    // 1
+    pub(crate) target_x: u8,    // 1 tile of the target when the action happened
+    pub(crate) target_y: u8,    // 1
+}
+
+/// Compact view of the board for clients which can not download the whole account on
+/// every change. Returned by the board_summary instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BoardSummary {
+    pub action_id: u64,
+    pub occupancy: [u8; BOARD_SUMMARY_OCCUPANCY_BYTES], // one bit per tile, tile index is x * BOARD_SIZE_Y + y
+    pub tiles: Vec<TileSummary>, // occupied tiles in index order, cut off when the return data is full
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TileSummary {
+    pub index: u8,
+    pub state: u8,
+    pub look_direction: u8,
+    pub ship_class: u8,
+    pub ship_level: u8,
+    pub health: u32,
+    pub start_health: u32,
+    pub player: [u8; 8], // first bytes of the player pubkey, enough to tell the ships apart
+}
+
+pub const BOARD_SUMMARY_OCCUPANCY_BYTES: usize = (BOARD_SIZE_X * BOARD_SIZE_Y + 7) / 8;
+// Return data is limited to 1024 bytes: 8 action id + occupancy + 4 vec length + 21 per tile
+pub const BOARD_SUMMARY_MAX_TILES: usize = (1024 - 8 - BOARD_SUMMARY_OCCUPANCY_BYTES - 4) / 21;
+
+impl BoardSummary {
+    pub fn is_occupied(&self, x: usize, y: usize) -> bool {
+        let index = x * BOARD_SIZE_Y + y;
+        x < BOARD_SIZE_X && y < BOARD_SIZE_Y && self.occupancy[index / 8] & (1 << (index % 8)) != 0
+    }
 }
 
 impl GameDataAccount {
+    pub fn summary(&self) -> BoardSummary {
+        let mut summary = BoardSummary {
+            action_id: self.action_id,
+            occupancy: [0; BOARD_SUMMARY_OCCUPANCY_BYTES],
+            tiles: Vec::new(),
+        };
+        for x in 0..BOARD_SIZE_X {
+            for y in 0..BOARD_SIZE_Y {
+                let tile = self.board[x][y];
+                if tile.state == STATE_EMPTY {
+                    continue;
+                }
+                let index = x * BOARD_SIZE_Y + y;
+                summary.occupancy[index / 8] |= 1 << (index % 8);
+                if summary.tiles.len() < BOARD_SUMMARY_MAX_TILES {
+                    let mut player = [0u8; 8];
+                    player.copy_from_slice(&tile.player.to_bytes()[..8]);
+                    summary.tiles.push(TileSummary {
+                        index: index as u8,
+                        state: tile.state,
+                        look_direction: tile.look_direction,
+                        ship_class: tile.ship_class,
+                        ship_level: tile.ship_level.min(u8::MAX as u16) as u8,
+                        health: tile.health.min(u32::MAX as u64) as u32,
+                        start_health: tile.start_health.min(u32::MAX as u64) as u32,
+                        player,
+                    });
+                }
+            }
+        }
+        summary
+    }
+
     pub fn print(&mut self) -> Result<()> {
         // print will only work locally for debugging otherwise it eats too much compute
         /*
@@ -118,7 +212,10 @@ impl GameDataAccount {
     pub fn reset_ship(&mut self, ship_owner: Pubkey) -> Result<()> {
         for x in 0..BOARD_SIZE_X {
             for y in 0..BOARD_SIZE_Y {
-                if self.board[x][y].player == ship_owner && self.board[x][y].state == STATE_PLAYER {
+                let state = self.board[x][y].state;
+                if self.board[x][y].player == ship_owner
+                    && (state == STATE_PLAYER || state == STATE_DISABLED)
+                {
                     self.board[x][y].state = STATE_EMPTY
                 }
             }
@@ -126,6 +223,21 @@ impl GameDataAccount {
         Ok(())
     }
 
+    /// True while the ship of the nft is sailing or a disabled wreck on the board
+    pub fn is_on_board(&self, nft: Pubkey) -> bool {
+        for x in 0..BOARD_SIZE_X {
+            for y in 0..BOARD_SIZE_Y {
+                let tile = self.board[x][y];
+                if tile.avatar == nft
+                    && (tile.state == STATE_PLAYER || tile.state == STATE_DISABLED)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
     pub fn euclidean_distance(x1: &usize, x2: &usize, y1: &usize, y2: &usize) -> f64 {
         let dx = (x1 - x2) as f64;
         let dy = (y1 - y2) as f64;
@@ -198,6 +310,11 @@ impl GameDataAccount {
                     player: tile.player.key(),
                     target: tile.player.key(),
                     damage: damage,
+                    player_x: val.0 as u8,
+                    player_y: val.1 as u8,
+                    target_x: val.0 as u8,
+                    target_y: val.1 as u8,
+                    ..Default::default()
                 };
                 self.add_new_game_action(game_actions, item);
 
@@ -223,6 +340,8 @@ impl GameDataAccount {
         token_account_owner_pda: AccountInfo<'info>,
         token_program: AccountInfo<'info>,
         token_owner_bump: u8,
+        config: &GameConfig,
+        decimals: u8,
     ) -> Result<()> {
         let mut player_position: Option<(usize, usize)> = None;
 
@@ -247,72 +366,23 @@ impl GameDataAccount {
             Some(val) => {
                 msg!("Player position x:{} y:{}", val.0, val.1);
                 let player_tile: Tile = self.board[val.0][val.1];
-                let range_usize: usize = usize::from(player_tile.range);
                 let damage = player_tile.damage + 2;
-                for range in 1..range_usize + 1 {
-                    // Shoot left
-                    if player_tile.look_direction % 2 == 0 && val.0 >= range {
-                        self.attack_tile(
-                            (val.0 - range, val.1),
-                            damage,
-                            player.clone(),
-                            chest_vault.clone(),
-                            game_actions,
-                            &vault_token_account,
-                            &player_token_account,
-                            &token_account_owner_pda,
-                            &token_program,
-                            token_owner_bump,
-                        )?;
-                    }
-
-                    // Shoot right
-                    if player_tile.look_direction % 2 == 0 && val.0 < BOARD_SIZE_X - range {
-                        self.attack_tile(
-                            (val.0 + range, val.1),
-                            damage,
-                            player.clone(),
-                            chest_vault.clone(),
-                            game_actions,
-                            &vault_token_account,
-                            &player_token_account,
-                            &token_account_owner_pda,
-                            &token_program,
-                            token_owner_bump,
-                        )?;
-                    }
-
-                    // Shoot down
-                    if player_tile.look_direction % 2 == 1 && val.1 < BOARD_SIZE_Y - range {
-                        self.attack_tile(
-                            (val.0, val.1 + range),
-                            damage,
-                            player.clone(),
-                            chest_vault.clone(),
-                            game_actions,
-                            &vault_token_account,
-                            &player_token_account,
-                            &token_account_owner_pda,
-                            &token_program,
-                            token_owner_bump,
-                        )?;
-                    }
-
-                    // Shoot up
-                    if player_tile.look_direction % 2 == 1 && val.1 >= range {
-                        self.attack_tile(
-                            (val.0, val.1 - range),
-                            damage,
-                            player.clone(),
-                            chest_vault.clone(),
-                            game_actions,
-                            &vault_token_account,
-                            &player_token_account,
-                            &token_account_owner_pda,
-                            &token_program,
-                            token_owner_bump,
-                        )?;
-                    }
+                for target in Self::fire_line(val, player_tile.look_direction, player_tile.range) {
+                    self.attack_tile(
+                        target,
+                        val,
+                        damage,
+                        player.clone(),
+                        chest_vault.clone(),
+                        game_actions,
+                        &vault_token_account,
+                        &player_token_account,
+                        &token_account_owner_pda,
+                        &token_program,
+                        token_owner_bump,
+                        config,
+                        decimals,
+                    )?;
                 }
 
                 let item = GameAction {
@@ -321,6 +391,11 @@ impl GameDataAccount {
                     player: player.key(),
                     target: player.key(),
                     damage: damage,
+                    player_x: val.0 as u8,
+                    player_y: val.1 as u8,
+                    target_x: val.0 as u8,
+                    target_y: val.1 as u8,
+                    ..Default::default()
                 };
                 self.add_new_game_action(game_actions, item);
             }
@@ -329,11 +404,186 @@ impl GameDataAccount {
         Ok(())
     }
 
+    /// Boards the heavily damaged enemy ship next to the player in the given direction.
+    /// The crew with more health left is more likely to win. On success the player takes part
+    /// of the kill reward and coins and the enemy ship is disabled instead of sunk, on failure
+    /// the defending crew hurts the player.
+    pub fn board_ship<'info>(
+        &mut self,
+        player: AccountInfo,
+        direction: u8,
+        game_actions: &mut GameActionHistory,
+        chest_vault: AccountInfo,
+        vault_token_account: AccountInfo<'info>,
+        player_token_account: AccountInfo<'info>,
+        token_account_owner_pda: AccountInfo<'info>,
+        token_program: AccountInfo<'info>,
+        token_owner_bump: u8,
+        config: &GameConfig,
+        decimals: u8,
+    ) -> Result<()> {
+        let mut player_position: Option<(usize, usize)> = None;
+
+        // Find the player on the board
+        for x in 0..BOARD_SIZE_X {
+            for y in 0..BOARD_SIZE_Y {
+                let tile = self.board[x][y];
+                if tile.state == STATE_PLAYER && tile.player == player.key.clone() {
+                    player_position = Some((x, y));
+                }
+            }
+        }
+
+        let position = match player_position {
+            None => {
+                return Err(SevenSeasError::TriedToBoardWithPlayerThatWasNotOnTheBoard.into());
+            }
+            Some(val) => val,
+        };
+
+        let target_position = match direction {
+            0 if position.1 > 0 => (position.0, position.1 - 1),
+            1 if position.0 < BOARD_SIZE_X - 1 => (position.0 + 1, position.1),
+            2 if position.1 < BOARD_SIZE_Y - 1 => (position.0, position.1 + 1),
+            3 if position.0 > 0 => (position.0 - 1, position.1),
+            0..=3 => return Err(SevenSeasError::TileOutOfBounds.into()),
+            _ => return Err(SevenSeasError::WrongDirectionInput.into()),
+        };
+
+        let player_tile = self.board[position.0][position.1];
+        let target_tile = self.board[target_position.0][target_position.1];
+        if target_tile.state != STATE_PLAYER {
+            return Err(SevenSeasError::NoShipToBoard.into());
+        }
+        if target_tile.health * 100 > target_tile.start_health * BOARDING_HEALTH_PERCENT {
+            return Err(SevenSeasError::ShipNotDamagedEnoughToBoard.into());
+        }
+
+        // Same kind of predictable randomness as the cthulhu attack, good enough for a game
+        let mut rng = XorShift64 {
+            a: (Clock::get()?.slot ^ self.action_id) | 1,
+        };
+        let crew_strength = player_tile.health;
+        let roll = rng.next() % (crew_strength + target_tile.health).max(1);
+        msg!(
+            "Boarding roll {} against crew strength {}",
+            roll,
+            crew_strength
+        );
+
+        if roll >= crew_strength {
+            // The defenders fight the boarding crew off, but never sink the ship
+            let damage = target_tile.damage.min(player_tile.health.saturating_sub(1));
+            self.board[position.0][position.1].health = player_tile.health - damage;
+            let item = GameAction {
+                action_id: self.action_id,
+                action_type: GAME_ACTION_SHIP_TAKEN_DAMAGE,
+                player: target_tile.player.key(),
+                target: player.key(),
+                damage: damage,
+                player_x: target_position.0 as u8,
+                player_y: target_position.1 as u8,
+                target_x: position.0 as u8,
+                target_y: position.1 as u8,
+                ..Default::default()
+            };
+            self.add_new_game_action(game_actions, item);
+            return Ok(());
+        }
+
+        let loot = target_tile.collect_reward * BOARDING_LOOT_PERCENT / 100;
+        self.board[target_position.0][target_position.1].state = STATE_DISABLED;
+        self.board[target_position.0][target_position.1].collect_reward -= loot;
+        // The vault holds the rewards of all ships and chests and has to stay rent exempt
+        let vault_lamports = chest_vault
+            .lamports()
+            .checked_sub(loot)
+            .ok_or(SevenSeasError::NotEnoughLamportsInChestVault)?;
+        if vault_lamports < Rent::get()?.minimum_balance(chest_vault.data_len()) {
+            return Err(SevenSeasError::NotEnoughLamportsInChestVault.into());
+        }
+        **chest_vault.try_borrow_mut_lamports()? = vault_lamports;
+        **player.try_borrow_mut_lamports()? += loot;
+
+        let coins = (target_tile.ship_level as u64)
+            * config.destroy_ship_coin_reward
+            * BOARDING_LOOT_PERCENT
+            / 100;
+        let transfer_instruction = Transfer {
+            from: vault_token_account.to_account_info(),
+            to: player_token_account.to_account_info(),
+            authority: token_account_owner_pda.to_account_info(),
+        };
+        let seeds = &[b"token_account_owner_pda".as_ref(), &[token_owner_bump]];
+        let signer = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            transfer_instruction,
+            signer,
+        );
+        anchor_spl::token::transfer(cpi_ctx, token_amount(coins, decimals)?)?;
+
+        msg!(
+            "Boarded ship at x:{} y:{} pubkey: {}",
+            target_position.0,
+            target_position.1,
+            target_tile.player
+        );
+        let item = GameAction {
+            action_id: self.action_id,
+            action_type: GAME_ACTION_SHIP_BOARDED,
+            player: player.key(),
+            target: target_tile.player.key(),
+            damage: coins,
+            player_x: position.0 as u8,
+            player_y: position.1 as u8,
+            target_x: target_position.0 as u8,
+            target_y: target_position.1 as u8,
+            ..Default::default()
+        };
+        self.add_new_game_action(game_actions, item);
+
+        Ok(())
+    }
+
+    /// Tiles hit by a broadside. Ships looking up or down fire to the left and right,
+    /// ships looking left or right fire up and down.
+    fn fire_line(position: (usize, usize), look_direction: u8, range: u8) -> Vec<(usize, usize)> {
+        let mut targets: Vec<(usize, usize)> = Vec::new();
+        for range in 1..usize::from(range) + 1 {
+            // Shoot left
+            if look_direction % 2 == 0 && position.0 >= range {
+                targets.push((position.0 - range, position.1));
+            }
+
+            // Shoot right
+            if look_direction % 2 == 0 && position.0 < BOARD_SIZE_X - range {
+                targets.push((position.0 + range, position.1));
+            }
+
+            // Shoot down
+            if look_direction % 2 == 1 && position.1 < BOARD_SIZE_Y - range {
+                targets.push((position.0, position.1 + range));
+            }
+
+            // Shoot up
+            if look_direction % 2 == 1 && position.1 >= range {
+                targets.push((position.0, position.1 - range));
+            }
+        }
+        targets
+    }
+
     fn add_new_game_action(
         &mut self,
         game_actions: &mut GameActionHistory,
-        game_action: GameAction,
+        mut game_action: GameAction,
     ) {
+        // Lets replays put the actions on a timeline
+        if let Ok(clock) = Clock::get() {
+            game_action.slot = clock.slot;
+            game_action.timestamp = clock.unix_timestamp;
+        }
         {
             let option_add = self.action_id.checked_add(1);
             match option_add {
@@ -354,6 +604,7 @@ impl GameDataAccount {
     fn attack_tile<'info>(
         &mut self,
         attacked_position: (usize, usize),
+        attacker_position: (usize, usize),
         damage: u64,
         attacker: AccountInfo,
         chest_vault: AccountInfo,
@@ -363,6 +614,8 @@ impl GameDataAccount {
         token_account_owner_pda: &AccountInfo<'info>,
         token_program: &AccountInfo<'info>,
         token_owner_bump: u8,
+        config: &GameConfig,
+        decimals: u8,
     ) -> Result<()> {
         let mut attacked_tile: Tile = self.board[attacked_position.0][attacked_position.1];
         msg!("Attack x:{} y:{}", attacked_position.0, attacked_position.1);
@@ -390,9 +643,10 @@ impl GameDataAccount {
                     self.on_ship_died(attacked_position, attacked_tile, chest_vault, &attacker)?;
                     anchor_spl::token::transfer(
                         cpi_ctx,
-                        (attacked_tile.ship_level as u64)
-                            * DESTROY_SHIP_COIN_REWARD
-                            * TOKEN_DECIMAL_MULTIPLIER,
+                        token_amount(
+                            (attacked_tile.ship_level as u64) * config.destroy_ship_coin_reward,
+                            decimals,
+                        )?,
                     )?;
 
                     let new_game_action = GameAction {
@@ -400,7 +654,12 @@ impl GameDataAccount {
                         action_type: GAME_ACTION_SHIP_COINS_COLLECTED,
                         player: attacker.key(),
                         target: attacked_tile.player.key(),
-                        damage: DESTROY_SHIP_COIN_REWARD,
+                        damage: config.destroy_ship_coin_reward,
+                        player_x: attacker_position.0 as u8,
+                        player_y: attacker_position.1 as u8,
+                        target_x: attacked_position.0 as u8,
+                        target_y: attacked_position.1 as u8,
+                        ..Default::default()
                     };
                     self.add_new_game_action(game_actions, new_game_action);
                 }
@@ -416,16 +675,22 @@ impl GameDataAccount {
                         )?;
                         anchor_spl::token::transfer(
                             cpi_ctx,
-                            (attacked_tile.ship_level as u64)
-                                * DESTROY_SHIP_COIN_REWARD
-                                * TOKEN_DECIMAL_MULTIPLIER,
+                            token_amount(
+                                (attacked_tile.ship_level as u64) * config.destroy_ship_coin_reward,
+                                decimals,
+                            )?,
                         )?;
                         let item = GameAction {
                             action_id: self.action_id,
                             action_type: GAME_ACTION_SHIP_COINS_COLLECTED,
                             player: attacker.key(),
                             target: attacked_tile.player.key(),
-                            damage: DESTROY_SHIP_COIN_REWARD,
+                            damage: config.destroy_ship_coin_reward,
+                            player_x: attacker_position.0 as u8,
+                            player_y: attacker_position.1 as u8,
+                            target_x: attacked_position.0 as u8,
+                            target_y: attacked_position.1 as u8,
+                            ..Default::default()
                         };
                         self.add_new_game_action(game_actions, item);
                     }
@@ -437,6 +702,67 @@ impl GameDataAccount {
                 player: attacker.key(),
                 target: attacked_tile.player.key(),
                 damage: damage,
+                player_x: attacker_position.0 as u8,
+                player_y: attacker_position.1 as u8,
+                target_x: attacked_position.0 as u8,
+                target_y: attacked_position.1 as u8,
+                ..Default::default()
+            };
+            self.add_new_game_action(game_actions, item);
+        } else if attacked_tile.state == STATE_DISABLED {
+            // The boarding crew already took the coins, sinking the wreck only pays what is left of the kill reward
+            let new_health = attacked_tile.health.saturating_sub(damage);
+            self.board[attacked_position.0][attacked_position.1].health = new_health;
+            msg!("New wreck health {}", new_health);
+            if new_health == 0 {
+                self.on_ship_died(attacked_position, attacked_tile, chest_vault, &attacker)?;
+            }
+            let item = GameAction {
+                action_id: self.action_id,
+                action_type: GAME_ACTION_SHIP_TAKEN_DAMAGE,
+                player: attacker.key(),
+                target: attacked_tile.player.key(),
+                damage: damage,
+                player_x: attacker_position.0 as u8,
+                player_y: attacker_position.1 as u8,
+                target_x: attacked_position.0 as u8,
+                target_y: attacked_position.1 as u8,
+                ..Default::default()
+            };
+            self.add_new_game_action(game_actions, item);
+        } else if attacked_tile.state == STATE_NPC {
+            let new_health = attacked_tile.health.saturating_sub(damage);
+            self.board[attacked_position.0][attacked_position.1].health = new_health;
+            msg!("New NPC health {}", new_health);
+            if new_health == 0 {
+                // NPCs pay less than real ships, their kill reward was funded by the admin at spawn
+                self.on_ship_died(attacked_position, attacked_tile, chest_vault, &attacker)?;
+                anchor_spl::token::transfer(cpi_ctx, token_amount(NPC_KILL_COIN_REWARD, decimals)?)?;
+                let item = GameAction {
+                    action_id: self.action_id,
+                    action_type: GAME_ACTION_SHIP_COINS_COLLECTED,
+                    player: attacker.key(),
+                    target: attacked_tile.player.key(),
+                    damage: NPC_KILL_COIN_REWARD,
+                    player_x: attacker_position.0 as u8,
+                    player_y: attacker_position.1 as u8,
+                    target_x: attacked_position.0 as u8,
+                    target_y: attacked_position.1 as u8,
+                    ..Default::default()
+                };
+                self.add_new_game_action(game_actions, item);
+            }
+            let item = GameAction {
+                action_id: self.action_id,
+                action_type: GAME_ACTION_SHIP_TAKEN_DAMAGE,
+                player: attacker.key(),
+                target: attacked_tile.player.key(),
+                damage: damage,
+                player_x: attacker_position.0 as u8,
+                player_y: attacker_position.1 as u8,
+                target_x: attacked_position.0 as u8,
+                target_y: attacked_position.1 as u8,
+                ..Default::default()
             };
             self.add_new_game_action(game_actions, item);
         }
@@ -462,6 +788,30 @@ impl GameDataAccount {
         Ok(())
     }
 
+    /// Returns true if enough slots and seconds passed since the last tick so that
+    /// a permissionless crank is allowed to move the ships again.
+    pub fn can_crank_tick(&self, slot: u64, timestamp: i64) -> bool {
+        let last_tick_slot = self.last_tick_slot;
+        let last_tick_timestamp = self.last_tick_timestamp;
+        slot.saturating_sub(last_tick_slot) >= CRANK_TICK_MIN_SLOTS
+            && timestamp.saturating_sub(last_tick_timestamp) >= CRANK_TICK_MIN_SECONDS
+    }
+
+    /// Moves all ships one tile in their look direction and remembers when it happened.
+    /// Used by the clockwork thread as well as by the permissionless crank.
+    pub fn tick(
+        &mut self,
+        slot: u64,
+        timestamp: i64,
+        game_actions: &mut GameActionHistory,
+    ) -> Result<()> {
+        self.last_tick_slot = slot;
+        self.last_tick_timestamp = timestamp;
+        self.wind_ticks = self.wind_ticks.wrapping_add(1);
+        self.move_in_direction_by_thread()?;
+        self.move_npcs_by_thread(game_actions)
+    }
+
     pub fn move_in_direction_by_thread<'info>(&mut self) -> Result<()> {
         let mut alive_players: Vec<(usize, usize)> = Vec::new();
 
@@ -477,57 +827,244 @@ impl GameDataAccount {
 
         for player in alive_players {
             if self.board[player.0][player.1].state == STATE_PLAYER {
-                let mut new_position: (usize, usize) = (player.0, player.1);
                 msg!("Player found at x:{} y:{}", player.0, player.1);
-                match self.board[player.0][player.1].look_direction {
-                    // Up
-                    0 => {
-                        if new_position.1 == 0 {
-                            new_position.1 = BOARD_SIZE_Y - 1;
-                        } else {
-                            new_position.1 -= 1;
+                // Speed is counted in tiles per two wind ticks
+                let speed = ShipClass::get(self.board[player.0][player.1].ship_class)?.speed;
+                let steps = speed / 2 + speed % 2 * (self.wind_ticks % 2) as u8;
+                let mut position = player;
+                for _ in 0..steps {
+                    let mut new_position: (usize, usize) = (position.0, position.1);
+                    match self.board[position.0][position.1].look_direction {
+                        // Up
+                        0 => {
+                            if new_position.1 == 0 {
+                                new_position.1 = BOARD_SIZE_Y - 1;
+                            } else {
+                                new_position.1 -= 1;
+                            }
                         }
-                    }
-                    // Right
-                    1 => {
-                        if new_position.0 == BOARD_SIZE_X - 1 {
-                            new_position.0 = 0;
-                        } else {
-                            new_position.0 += 1;
+                        // Right
+                        1 => {
+                            if new_position.0 == BOARD_SIZE_X - 1 {
+                                new_position.0 = 0;
+                            } else {
+                                new_position.0 += 1;
+                            }
                         }
-                    }
-                    // Down
-                    2 => {
-                        if new_position.1 == BOARD_SIZE_Y - 1 {
-                            new_position.1 = 0;
-                        } else {
-                            new_position.1 += 1;
+                        // Down
+                        2 => {
+                            if new_position.1 == BOARD_SIZE_Y - 1 {
+                                new_position.1 = 0;
+                            } else {
+                                new_position.1 += 1;
+                            }
                         }
-                    }
-                    // Left
-                    3 => {
-                        if new_position.0 == 0 {
-                            new_position.0 = BOARD_SIZE_X - 1;
-                        } else {
-                            new_position.0 -= 1;
+                        // Left
+                        3 => {
+                            if new_position.0 == 0 {
+                                new_position.0 = BOARD_SIZE_X - 1;
+                            } else {
+                                new_position.0 -= 1;
+                            }
+                        }
+                        _ => {
+                            return Err(SevenSeasError::WrongDirectionInput.into());
                         }
                     }
-                    _ => {
-                        return Err(SevenSeasError::WrongDirectionInput.into());
+
+                    if self.board[new_position.0][new_position.1].state == STATE_EMPTY {
+                        msg!("Move to x:{} y:{}", new_position.0, new_position.1);
+                        self.board[new_position.0][new_position.1] =
+                            self.board[position.0][position.1];
+                        self.board[position.0][position.1].state = STATE_EMPTY;
+                        position = new_position;
+                    } else {
+                        break;
                     }
                 }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// NPCs flee from the closest ship when their health is low, chase ships which come
+    /// close and otherwise patrol in their look direction. Afterwards they fire their broadside.
+    pub fn move_npcs_by_thread(&mut self, game_actions: &mut GameActionHistory) -> Result<()> {
+        let mut npcs: Vec<(usize, usize)> = Vec::new();
+
+        for x in 0..BOARD_SIZE_X {
+            for y in 0..BOARD_SIZE_Y {
+                if self.board[x][y].state == STATE_NPC {
+                    npcs.push((x, y));
+                }
+            }
+        }
+
+        for npc in npcs {
+            let tile = self.board[npc.0][npc.1];
+            let mut position = npc;
 
-                if self.board[new_position.0][new_position.1].state == STATE_EMPTY {
-                    msg!("Move to x:{} y:{}", new_position.0, new_position.1);
-                    self.board[new_position.0][new_position.1] = self.board[player.0][player.1];
-                    self.board[player.0][player.1].state = STATE_EMPTY;
+            match self.find_closest_ship(npc) {
+                Some(ship) if tile.health * 100 <= tile.start_health * NPC_FLEE_HEALTH_PERCENT => {
+                    msg!("NPC at x:{} y:{} flees", npc.0, npc.1);
+                    let direction = (Self::direction_towards(npc, ship) + 2) % 4;
+                    position = self.move_npc(npc, direction);
+                }
+                Some(ship) if Self::distance(npc, ship) <= NPC_CHASE_DISTANCE => {
+                    msg!("NPC at x:{} y:{} chases", npc.0, npc.1);
+                    let range = usize::from(tile.range);
+                    if ship.1 == npc.1 && Self::distance(npc, ship) <= range {
+                        // Same row, turn up or down so the broadside points at the ship
+                        if tile.look_direction % 2 == 1 {
+                            self.board[npc.0][npc.1].look_direction = 0;
+                        }
+                    } else if ship.0 == npc.0 && Self::distance(npc, ship) <= range {
+                        // Same column, turn left or right
+                        if tile.look_direction % 2 == 0 {
+                            self.board[npc.0][npc.1].look_direction = 1;
+                        }
+                    } else {
+                        position = self.move_npc(npc, Self::direction_towards(npc, ship));
+                    }
+                }
+                _ => {
+                    // Patrol, turn clockwise when the way is blocked
+                    let next = self.move_npc(npc, tile.look_direction);
+                    if next == npc {
+                        self.board[npc.0][npc.1].look_direction = (tile.look_direction + 1) % 4;
+                    }
+                    position = next;
                 }
             }
+
+            self.npc_fire(position, game_actions);
         }
 
         Ok(())
     }
 
+    fn find_closest_ship(&self, position: (usize, usize)) -> Option<(usize, usize)> {
+        let mut closest: Option<(usize, usize)> = None;
+        for x in 0..BOARD_SIZE_X {
+            for y in 0..BOARD_SIZE_Y {
+                if self.board[x][y].state == STATE_PLAYER {
+                    let is_closer = match closest {
+                        None => true,
+                        Some(val) => {
+                            Self::distance(position, (x, y)) < Self::distance(position, val)
+                        }
+                    };
+                    if is_closer {
+                        closest = Some((x, y));
+                    }
+                }
+            }
+        }
+        closest
+    }
+
+    fn distance(a: (usize, usize), b: (usize, usize)) -> usize {
+        a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+    }
+
+    // Up, right, down, left like the look direction
+    fn direction_towards(from: (usize, usize), to: (usize, usize)) -> u8 {
+        if from.0.abs_diff(to.0) >= from.1.abs_diff(to.1) {
+            if to.0 > from.0 {
+                1
+            } else {
+                3
+            }
+        } else if to.1 > from.1 {
+            2
+        } else {
+            0
+        }
+    }
+
+    /// Moves the NPC one tile if it is free. NPCs do not wrap around the board.
+    /// Returns the new position, or the old one if the NPC could not move.
+    fn move_npc(&mut self, position: (usize, usize), direction: u8) -> (usize, usize) {
+        self.board[position.0][position.1].look_direction = direction;
+        let new_position = match direction {
+            0 if position.1 > 0 => (position.0, position.1 - 1),
+            1 if position.0 < BOARD_SIZE_X - 1 => (position.0 + 1, position.1),
+            2 if position.1 < BOARD_SIZE_Y - 1 => (position.0, position.1 + 1),
+            3 if position.0 > 0 => (position.0 - 1, position.1),
+            _ => return position,
+        };
+
+        if self.board[new_position.0][new_position.1].state != STATE_EMPTY {
+            return position;
+        }
+        self.board[new_position.0][new_position.1] = self.board[position.0][position.1];
+        self.board[position.0][position.1].state = STATE_EMPTY;
+        new_position
+    }
+
+    fn npc_fire(&mut self, position: (usize, usize), game_actions: &mut GameActionHistory) {
+        let npc_tile = self.board[position.0][position.1];
+        let targets: Vec<(usize, usize)> =
+            Self::fire_line(position, npc_tile.look_direction, npc_tile.range)
+                .into_iter()
+                .filter(|target| self.board[target.0][target.1].state == STATE_PLAYER)
+                .collect();
+        if targets.is_empty() {
+            return;
+        }
+
+        let damage = npc_tile.damage + 2;
+        for target in targets {
+            let attacked_tile = self.board[target.0][target.1];
+            let new_health = attacked_tile.health.saturating_sub(damage);
+            self.board[target.0][target.1].health = new_health;
+            if new_health == 0 {
+                // Sunk by an NPC. The kill reward is still escrowed in the chest vault,
+                // so the wreck becomes a chest that pays it to whoever sails there first.
+                msg!("NPC sunk ship x:{} y:{}", target.0, target.1);
+                self.board[target.0][target.1] = Tile {
+                    state: STATE_CHEST,
+                    health: 1,
+                    start_health: 1,
+                    damage: 0,
+                    range: 0,
+                    ship_class: 0,
+                    look_direction: 0,
+                    ship_level: 0,
+                    ..attacked_tile
+                };
+            }
+            let item = GameAction {
+                action_id: self.action_id,
+                action_type: GAME_ACTION_SHIP_TAKEN_DAMAGE,
+                player: npc_tile.player,
+                target: attacked_tile.player,
+                damage: damage,
+                player_x: position.0 as u8,
+                player_y: position.1 as u8,
+                target_x: target.0 as u8,
+                target_y: target.1 as u8,
+                ..Default::default()
+            };
+            self.add_new_game_action(game_actions, item);
+        }
+
+        let item = GameAction {
+            action_id: self.action_id,
+            action_type: GAME_ACTION_SHIP_SHOT,
+            player: npc_tile.player,
+            target: npc_tile.player,
+            damage: damage,
+            player_x: position.0 as u8,
+            player_y: position.1 as u8,
+            target_x: position.0 as u8,
+            target_y: position.1 as u8,
+            ..Default::default()
+        };
+        self.add_new_game_action(game_actions, item);
+    }
+
     pub fn move_in_direction<'info>(
         &mut self,
         direction: u8,
@@ -539,6 +1076,8 @@ impl GameDataAccount {
         token_program: AccountInfo<'info>,
         token_owner_bump: u8,
         game_actions: &mut GameActionHistory,
+        config: &GameConfig,
+        decimals: u8,
     ) -> Result<()> {
         let mut player_position: Option<(usize, usize)> = None;
 
@@ -649,7 +1188,7 @@ impl GameDataAccount {
                         );
                         anchor_spl::token::transfer(
                             cpi_ctx,
-                            CHEST_COIN_REWARD * TOKEN_DECIMAL_MULTIPLIER,
+                            token_amount(config.chest_coin_reward, decimals)?,
                         )?;
 
                         let item = GameAction {
@@ -657,14 +1196,20 @@ impl GameDataAccount {
                             action_type: GAME_ACTION_SHIP_COINS_COLLECTED,
                             player: player.key(),
                             target: player.key(),
-                            damage: CHEST_COIN_REWARD,
+                            damage: config.chest_coin_reward,
+                            player_x: new_player_position.0 as u8,
+                            player_y: new_player_position.1 as u8,
+                            target_x: new_player_position.0 as u8,
+                            target_y: new_player_position.1 as u8,
+                            ..Default::default()
                         };
                         self.add_new_game_action(game_actions, item);
 
                         msg!("Collected Chest");
-                    } else if new_tile.state == STATE_PLAYER {
+                    } else if new_tile.state == STATE_PLAYER || new_tile.state == STATE_NPC {
                         self.attack_tile(
                             (new_player_position.0, new_player_position.1),
+                            player_position.unwrap(),
                             1,
                             player.clone(),
                             chest_vault.clone(),
@@ -674,6 +1219,8 @@ impl GameDataAccount {
                             &token_account_owner_pda,
                             &token_program,
                             token_owner_bump,
+                            config,
+                            decimals,
                         )?;
 
                         msg!("Other player killed");
@@ -702,6 +1249,7 @@ impl GameDataAccount {
         avatar: Pubkey,
         ship: &mut Ship,
         extra_health: u64,
+        config: &GameConfig,
     ) -> Result<()> {
         let mut empty_slots: Vec<(usize, usize)> = Vec::new();
 
@@ -734,16 +1282,7 @@ impl GameDataAccount {
             random_empty_slot.1
         );
 
-        let mut range: u16 = 1;
-
-        match ship.upgrades {
-            0 | 1 | 2 => {
-                range = 1;
-            }
-            _ => {
-                range = 2;
-            }
-        }
+        let ship_class = ShipClass::get(ship.class)?;
 
         self.board[random_empty_slot.0][random_empty_slot.1] = Tile {
             player: player.key.clone(),
@@ -751,9 +1290,10 @@ impl GameDataAccount {
             state: STATE_PLAYER,
             health: ship.health + extra_health,
             start_health: ship.health + extra_health,
-            damage: ship.cannons,
-            range: range,
-            collect_reward: PLAYER_KILL_REWARD,
+            damage: ship.cannons + ship_class.extra_cannons,
+            range: ship_class.range(ship.upgrades),
+            ship_class: ship.class,
+            collect_reward: config.player_kill_reward,
             look_direction: 0,
             ship_level: ship.upgrades,
         };
@@ -761,7 +1301,51 @@ impl GameDataAccount {
         Ok(())
     }
 
-    pub fn spawn_chest(&mut self, player: AccountInfo) -> Result<()> {
+    pub fn spawn_npc(&mut self, avatar: Pubkey, kill_reward: u64) -> Result<()> {
+        let mut empty_slots: Vec<(usize, usize)> = Vec::new();
+
+        for x in 0..BOARD_SIZE_X {
+            for y in 0..BOARD_SIZE_Y {
+                if self.board[x][y].state == STATE_EMPTY {
+                    empty_slots.push((x, y));
+                }
+            }
+        }
+
+        if empty_slots.len() == 0 {
+            return Err(SevenSeasError::BoardIsFull.into());
+        }
+
+        let mut rng = XorShift64 {
+            a: (empty_slots.len() + 2) as u64,
+        };
+
+        let random_empty_slot = empty_slots[(rng.next() % (empty_slots.len() as u64)) as usize];
+        msg!(
+            "NPC spawn at {} {}",
+            random_empty_slot.0,
+            random_empty_slot.1
+        );
+
+        // NPCs have no wallet, the avatar doubles as their id in the game actions
+        self.board[random_empty_slot.0][random_empty_slot.1] = Tile {
+            player: avatar,
+            avatar: avatar,
+            state: STATE_NPC,
+            health: NPC_HEALTH,
+            start_health: NPC_HEALTH,
+            damage: NPC_DAMAGE,
+            range: 1,
+            ship_class: SHIP_CLASS_FRIGATE,
+            collect_reward: kill_reward,
+            look_direction: 0,
+            ship_level: 1,
+        };
+
+        Ok(())
+    }
+
+    pub fn spawn_chest(&mut self, player: AccountInfo, config: &GameConfig) -> Result<()> {
         let mut empty_slots: Vec<(usize, usize)> = Vec::new();
 
         for x in 0..BOARD_SIZE_X {
@@ -798,7 +1382,8 @@ impl GameDataAccount {
             start_health: 1,
             damage: 0,
             range: 0,
-            collect_reward: CHEST_REWARD,
+            ship_class: 0,
+            collect_reward: config.chest_reward,
             look_direction: 0,
             ship_level: 0,
         };
@@ -810,6 +1395,10 @@ impl GameDataAccount {
 #[account]
 pub struct ChestVaultAccount {}
 
+/// Pays the crank_tick bounties, funded separately from the chest vault
+#[account]
+pub struct KeeperPoolAccount {}
+
 pub struct XorShift64 {
     a: u64,
 }
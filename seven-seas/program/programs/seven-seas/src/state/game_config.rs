@@ -0,0 +1,36 @@
+use crate::{
+    CHEST_COIN_REWARD, CHEST_REWARD, DESTROY_SHIP_COIN_REWARD, NPC_KILL_REWARD, PLAYER_KILL_REWARD,
+};
+use crate::errors::SevenSeasError;
+use anchor_lang::prelude::*;
+
+/// Rewards of one game instance. Created with the defaults from lib.rs in `initialize`
+/// and tuned by the admin with `update_game_config`.
+#[account]
+pub struct GameConfig {
+    pub player_kill_reward: u64, // lamports each player puts into the chest vault when spawning
+    pub chest_reward: u64,       // lamports put into the chest vault for every chest
+    pub chest_coin_reward: u64,  // gold tokens without decimals for collecting a chest
+    pub destroy_ship_coin_reward: u64, // gold tokens without decimals per ship level for a kill
+    pub npc_kill_reward: u64,    // lamports the admin puts into the chest vault for every NPC
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            player_kill_reward: PLAYER_KILL_REWARD,
+            chest_reward: CHEST_REWARD,
+            chest_coin_reward: CHEST_COIN_REWARD,
+            destroy_ship_coin_reward: DESTROY_SHIP_COIN_REWARD,
+            npc_kill_reward: NPC_KILL_REWARD,
+        }
+    }
+}
+
+/// Converts whole tokens into the smallest unit of a mint with the given decimals.
+/// Fails with `TokenAmountOverflow` if the amount does not fit into a u64.
+pub fn token_amount(tokens: u64, decimals: u8) -> Result<u64> {
+    u64::checked_pow(10, decimals as u32)
+        .and_then(|unit| tokens.checked_mul(unit))
+        .ok_or_else(|| error!(SevenSeasError::TokenAmountOverflow))
+}
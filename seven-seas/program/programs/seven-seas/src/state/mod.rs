@@ -1,5 +1,7 @@
 pub use game::*;
+pub use game_config::*;
 pub use ship::*;
 
 pub mod game;
-pub mod ship;
\ No newline at end of file
+pub mod game_config;
+pub mod ship;
@@ -1,5 +1,11 @@
+pub use crate::errors::SevenSeasError;
 use anchor_lang::prelude::*;
 
+// The frigate is the original ship, so ships created before classes existed stay frigates
+pub const SHIP_CLASS_FRIGATE: u8 = 0;
+pub const SHIP_CLASS_SLOOP: u8 = 1;
+pub const SHIP_CLASS_GALLEON: u8 = 2;
+
 #[account]
 pub struct Ship {
     pub health: u64,
@@ -9,4 +15,136 @@ pub struct Ship {
     pub xp: u16,
     pub level: u16,
     pub start_health: u64,
-}
\ No newline at end of file
+    pub class: u8,
+    pub owner: Pubkey, // wallet which initialized the ship, default for ships created before
+    pub player: Pubkey, // board player of the last spawn
+}
+
+pub struct ShipUpgrade {
+    pub health: u64,
+    pub range: u8,
+    pub cost: u64, // in gold tokens without decimals
+}
+
+pub struct ShipClass {
+    pub health: u64,
+    pub cannons: u64,       // the cannon tokens of the player are added when spawning
+    pub extra_cannons: u64, // added to the damage when spawning, the galleon broadside
+    pub range: u8,
+    pub speed: u8, // tiles moved every two wind ticks
+    pub upgrades: [ShipUpgrade; 4],
+}
+
+const FRIGATE: ShipClass = ShipClass {
+    health: 50,
+    cannons: 1,
+    extra_cannons: 0,
+    range: 1,
+    speed: 2,
+    upgrades: [
+        ShipUpgrade {
+            health: 100,
+            range: 1,
+            cost: 5,
+        },
+        ShipUpgrade {
+            health: 150,
+            range: 1,
+            cost: 200,
+        },
+        ShipUpgrade {
+            health: 300,
+            range: 2,
+            cost: 1500,
+        },
+        ShipUpgrade {
+            health: 500,
+            range: 2,
+            cost: 25000,
+        },
+    ],
+};
+
+const SLOOP: ShipClass = ShipClass {
+    health: 30,
+    cannons: 1,
+    extra_cannons: 0,
+    range: 1,
+    speed: 4,
+    upgrades: [
+        ShipUpgrade {
+            health: 60,
+            range: 1,
+            cost: 5,
+        },
+        ShipUpgrade {
+            health: 90,
+            range: 2,
+            cost: 200,
+        },
+        ShipUpgrade {
+            health: 180,
+            range: 2,
+            cost: 1500,
+        },
+        ShipUpgrade {
+            health: 300,
+            range: 3,
+            cost: 25000,
+        },
+    ],
+};
+
+const GALLEON: ShipClass = ShipClass {
+    health: 80,
+    cannons: 2,
+    extra_cannons: 2,
+    range: 1,
+    speed: 1,
+    upgrades: [
+        ShipUpgrade {
+            health: 160,
+            range: 1,
+            cost: 5,
+        },
+        ShipUpgrade {
+            health: 240,
+            range: 1,
+            cost: 200,
+        },
+        ShipUpgrade {
+            health: 450,
+            range: 1,
+            cost: 1500,
+        },
+        ShipUpgrade {
+            health: 750,
+            range: 2,
+            cost: 25000,
+        },
+    ],
+};
+
+impl ShipClass {
+    pub fn get(class: u8) -> Result<&'static ShipClass> {
+        match class {
+            SHIP_CLASS_FRIGATE => Ok(&FRIGATE),
+            SHIP_CLASS_SLOOP => Ok(&SLOOP),
+            SHIP_CLASS_GALLEON => Ok(&GALLEON),
+            _ => Err(SevenSeasError::UnknownShipClass.into()),
+        }
+    }
+
+    /// Cannon range after the given amount of upgrades
+    pub fn range(&self, upgrades: u16) -> u8 {
+        match upgrades {
+            0 => self.range,
+            _ => {
+                self.upgrades
+                    .get(upgrades as usize - 1)
+                    .unwrap_or(&self.upgrades[self.upgrades.len() - 1])
+                    .range
+            }
+        }
+    }
+}
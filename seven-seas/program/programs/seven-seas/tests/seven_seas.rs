@@ -0,0 +1,1284 @@
+//! In process integration tests using solana-program-test. They run offline, no
+//! validator or devnet wallet needed: `cargo test`
+//!
+//! The clockwork thread is replaced by a stub thread account which signs the tick.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::{instruction::Instruction, sysvar::clock::Clock};
+use anchor_lang::{AccountDeserialize, AccountSerialize, InstructionData};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use clockwork_sdk::state::{ClockData, Thread, Trigger};
+use seven_seas::client::{self, BoardSnapshot, TileSnapshot, TileState};
+use seven_seas::replay::{replay, write_replay};
+use seven_seas::{
+    GameConfig, Ship, CHEST_REWARD, CRANK_TICK_BOUNTY, GAME_ACTION_SHIP_SHOT,
+    GAME_ACTION_SHIP_TAKEN_DAMAGE, NPC_KILL_REWARD, PLAYER_KILL_REWARD, SHIP_CLASS_FRIGATE,
+    SHIP_CLASS_GALLEON, SHIP_CLASS_SLOOP,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+// Anchor's entry wants the same lifetime for the slice and the accounts, program-test
+// hands out different ones. Leaking the accounts is fine for a test process.
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    seven_seas::entry(program_id, accounts, data)
+}
+
+struct TestGame {
+    context: ProgramTestContext,
+    gold_mint: Pubkey,
+    cannon_mint: Pubkey,
+    rum_mint: Pubkey,
+    nft: Pubkey,
+}
+
+// All test mints have 9 decimals
+const TOKEN_DECIMAL_MULTIPLIER: u64 = 1_000_000_000;
+
+fn mint_account(authority: &Pubkey) -> Account {
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: Some(*authority).into(),
+        supply: 0,
+        decimals: 9,
+        is_initialized: true,
+        freeze_authority: None.into(),
+    }
+    .pack_into_slice(&mut data);
+    Account {
+        lamports: LAMPORTS_PER_SOL,
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> AccountSharedData {
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data);
+    AccountSharedData::from(Account {
+        lamports: LAMPORTS_PER_SOL,
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    })
+}
+
+async fn setup() -> TestGame {
+    let mut program_test = ProgramTest::new(
+        "seven_seas",
+        seven_seas::ID,
+        processor!(process_instruction),
+    );
+    // The bundled token program shrinks closed accounts, which native CPIs can't copy back.
+    // The native build of spl-token only zeroes them.
+    program_test.add_program(
+        "spl_token",
+        spl_token::ID,
+        processor!(spl_token::processor::Processor::process),
+    );
+
+    // Nobody mints in the tests, token balances are written directly into the accounts
+    let gold_mint = Pubkey::new_unique();
+    let cannon_mint = Pubkey::new_unique();
+    let rum_mint = Pubkey::new_unique();
+    let mint_authority = Pubkey::default();
+    program_test.add_account(gold_mint, mint_account(&mint_authority));
+    program_test.add_account(cannon_mint, mint_account(&mint_authority));
+    program_test.add_account(rum_mint, mint_account(&mint_authority));
+
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.pubkey();
+    send(&mut context, &[client::initialize(payer, gold_mint)], &[])
+        .await
+        .unwrap();
+    // crank_tick pays its bounty from the keeper pool
+    send(
+        &mut context,
+        &[client::fund_keeper_pool(payer, LAMPORTS_PER_SOL / 100)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    TestGame {
+        context,
+        gold_mint,
+        cannon_mint,
+        rum_mint,
+        nft: Pubkey::new_unique(),
+    }
+}
+
+async fn send(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> std::result::Result<(), BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+async fn account(context: &mut ProgramTestContext, address: Pubkey) -> Account {
+    context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("account not found")
+}
+
+async fn board(context: &mut ProgramTestContext) -> BoardSnapshot {
+    let level = account(context, client::level_pda().0).await;
+    client::decode_board(&level.data).unwrap()
+}
+
+async fn set_board(context: &mut ProgramTestContext, board: &BoardSnapshot) {
+    let mut level = account(context, client::level_pda().0).await;
+    let encoded = client::encode_board(board).unwrap();
+    level.data[..encoded.len()].copy_from_slice(&encoded);
+    context.set_account(&client::level_pda().0, &level.into());
+}
+
+async fn token_balance(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    let token_account = account(context, address).await;
+    spl_token::state::Account::unpack(&token_account.data)
+        .unwrap()
+        .amount
+}
+
+fn fund_vault(game: &mut TestGame, amount: u64) {
+    let vault = client::token_vault_pda(&game.gold_mint).0;
+    let owner = client::token_account_owner_pda().0;
+    game.context
+        .set_account(&vault, &token_account(&game.gold_mint, &owner, amount));
+}
+
+async fn spawn(game: &mut TestGame) {
+    let payer = game.context.payer.pubkey();
+    let instructions = [
+        client::initialize_ship(payer, game.nft, SHIP_CLASS_FRIGATE),
+        client::spawn_player(
+            payer,
+            payer,
+            game.nft,
+            game.cannon_mint,
+            game.rum_mint,
+            game.nft,
+        ),
+    ];
+    send(&mut game.context, &instructions, &[]).await.unwrap();
+}
+
+fn ship_tile(x: usize, y: usize, player: Pubkey, health: u64, look_direction: u8) -> TileSnapshot {
+    TileSnapshot {
+        x,
+        y,
+        state: TileState::Player,
+        player,
+        avatar: player,
+        health,
+        start_health: health,
+        damage: 1,
+        range: 1,
+        ship_class: 0,
+        collect_reward: PLAYER_KILL_REWARD,
+        look_direction,
+        ship_level: 1,
+    }
+}
+
+fn empty_board() -> BoardSnapshot {
+    let mut tiles = Vec::new();
+    for x in 0..seven_seas::BOARD_SIZE_X {
+        for y in 0..seven_seas::BOARD_SIZE_Y {
+            tiles.push(TileSnapshot::empty(x, y));
+        }
+    }
+    BoardSnapshot {
+        action_id: 0,
+        last_tick_slot: 0,
+        last_tick_timestamp: 0,
+        wind_ticks: 0,
+        tiles,
+    }
+}
+
+fn put(board: &mut BoardSnapshot, tile: TileSnapshot) {
+    let index = tile.x * seven_seas::BOARD_SIZE_Y + tile.y;
+    board.tiles[index] = tile;
+}
+
+fn funded_keypair(context: &mut ProgramTestContext, keypair: Keypair) -> Keypair {
+    context.set_account(
+        &keypair.pubkey(),
+        &AccountSharedData::new(LAMPORTS_PER_SOL, 0, &solana_sdk::system_program::ID),
+    );
+    keypair
+}
+
+// The admin keypair is checked in next to Anchor.toml
+fn admin(context: &mut ProgramTestContext) -> Keypair {
+    let keypair = read_keypair_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ownSX1SCfotCS3TMmkZtnrGPdjsVwf5E9sAG94eNQS2.json"
+    ))
+    .unwrap();
+    funded_keypair(context, keypair)
+}
+
+async fn crank(context: &mut ProgramTestContext) {
+    advance_clock(context, 10, 10).await;
+    let keeper = funded_keypair(context, Keypair::new());
+    send(context, &[client::crank_tick(keeper.pubkey())], &[&keeper])
+        .await
+        .unwrap();
+}
+
+async fn advance_clock(context: &mut ProgramTestContext, slots: u64, seconds: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.slot += slots;
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+#[tokio::test]
+async fn initialize_creates_game_accounts() {
+    let mut game = setup().await;
+
+    let board = board(&mut game.context).await;
+    assert_eq!(board.occupied().count(), 0);
+
+    let vault = client::token_vault_pda(&game.gold_mint).0;
+    assert_eq!(token_balance(&mut game.context, vault).await, 0);
+    account(&mut game.context, client::chest_vault_pda().0).await;
+    account(&mut game.context, client::game_actions_pda().0).await;
+}
+
+#[test]
+fn encode_board_rejects_tiles_outside_of_the_board() {
+    let mut board = empty_board();
+    board.tiles[0].x = seven_seas::BOARD_SIZE_X;
+    assert!(client::encode_board(&board).is_err());
+}
+
+#[test]
+fn token_amount_rejects_overflowing_amounts() {
+    assert_eq!(seven_seas::token_amount(5, 9).unwrap(), 5_000_000_000);
+    assert!(seven_seas::token_amount(u64::MAX, 9).is_err());
+    assert!(seven_seas::token_amount(1, 20).is_err());
+}
+
+#[tokio::test]
+async fn initialize_and_upgrade_ship() {
+    let mut game = setup().await;
+    let payer = game.context.payer.pubkey();
+
+    send(
+        &mut game.context,
+        &[client::initialize_ship(payer, game.nft, SHIP_CLASS_FRIGATE)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let ship_data = account(&mut game.context, client::ship_pda(&game.nft).0).await;
+    let ship = Ship::try_deserialize(&mut ship_data.data.as_slice()).unwrap();
+    assert_eq!(ship.health, 50);
+    assert_eq!(ship.cannons, 1);
+    assert_eq!(ship.upgrades, 0);
+
+    let player_gold = get_associated_token_address(&payer, &game.gold_mint);
+    game.context.set_account(
+        &player_gold,
+        &token_account(&game.gold_mint, &payer, 5 * TOKEN_DECIMAL_MULTIPLIER),
+    );
+    send(
+        &mut game.context,
+        &[client::upgrade_ship(payer, game.nft, game.gold_mint)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let ship_data = account(&mut game.context, client::ship_pda(&game.nft).0).await;
+    let ship = Ship::try_deserialize(&mut ship_data.data.as_slice()).unwrap();
+    assert_eq!(ship.health, 100);
+    assert_eq!(ship.start_health, 100);
+    assert_eq!(ship.upgrades, 1);
+    assert_eq!(token_balance(&mut game.context, player_gold).await, 0);
+    let vault = client::token_vault_pda(&game.gold_mint).0;
+    assert_eq!(
+        token_balance(&mut game.context, vault).await,
+        5 * TOKEN_DECIMAL_MULTIPLIER
+    );
+}
+
+#[tokio::test]
+async fn ship_class_sets_stats() {
+    let mut game = setup().await;
+    let payer = game.context.payer.pubkey();
+
+    send(
+        &mut game.context,
+        &[client::initialize_ship(payer, game.nft, SHIP_CLASS_GALLEON)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let ship_data = account(&mut game.context, client::ship_pda(&game.nft).0).await;
+    let ship = Ship::try_deserialize(&mut ship_data.data.as_slice()).unwrap();
+    assert_eq!(ship.class, SHIP_CLASS_GALLEON);
+    assert_eq!(ship.health, 80);
+    assert_eq!(ship.cannons, 2);
+
+    // Two cannons of the class and the broadside of two, the player has no cannon tokens
+    send(
+        &mut game.context,
+        &[client::spawn_player(
+            payer,
+            payer,
+            game.nft,
+            game.cannon_mint,
+            game.rum_mint,
+            game.nft,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    let board = board(&mut game.context).await;
+    let galleon = board.ships().next().unwrap();
+    assert_eq!(galleon.ship_class, SHIP_CLASS_GALLEON);
+    assert_eq!(galleon.damage, 4);
+
+    let unknown_class = client::initialize_ship(payer, Pubkey::new_unique(), 7);
+    assert!(send(&mut game.context, &[unknown_class], &[])
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn ship_class_speed_on_wind_tick() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    let sloop = Pubkey::new_unique();
+    let galleon = Pubkey::new_unique();
+
+    // Both sail to the right
+    let mut fixture = empty_board();
+    put(
+        &mut fixture,
+        TileSnapshot {
+            ship_class: SHIP_CLASS_SLOOP,
+            ..ship_tile(0, 2, sloop, 30, 1)
+        },
+    );
+    put(
+        &mut fixture,
+        TileSnapshot {
+            ship_class: SHIP_CLASS_GALLEON,
+            ..ship_tile(0, 6, galleon, 80, 1)
+        },
+    );
+    set_board(&mut game.context, &fixture).await;
+
+    crank(&mut game.context).await;
+    let board_after = board(&mut game.context).await;
+    assert_eq!(board_after.tile(2, 2).unwrap().player, sloop);
+    assert_eq!(board_after.tile(1, 6).unwrap().player, galleon);
+
+    // The galleon only moves on every second tick
+    crank(&mut game.context).await;
+    let board_after = board(&mut game.context).await;
+    assert_eq!(board_after.tile(4, 2).unwrap().player, sloop);
+    assert_eq!(board_after.tile(1, 6).unwrap().player, galleon);
+}
+
+#[tokio::test]
+async fn spawn_places_ship_and_chest() {
+    let mut game = setup().await;
+    let chest_vault_before = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+
+    spawn(&mut game).await;
+
+    let payer = game.context.payer.pubkey();
+    let board = board(&mut game.context).await;
+    let ships: Vec<&TileSnapshot> = board.ships().collect();
+    assert_eq!(ships.len(), 1);
+    assert_eq!(ships[0].player, payer);
+    assert_eq!(ships[0].health, 50);
+    assert_eq!(
+        board
+            .occupied()
+            .filter(|tile| tile.state == TileState::Chest)
+            .count(),
+        1
+    );
+
+    let chest_vault_after = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+    assert_eq!(
+        chest_vault_after - chest_vault_before,
+        PLAYER_KILL_REWARD + CHEST_REWARD
+    );
+
+    // Spawning the same player twice is not allowed
+    let respawn = client::spawn_player(
+        payer,
+        payer,
+        game.nft,
+        game.cannon_mint,
+        game.rum_mint,
+        game.nft,
+    );
+    assert!(send(&mut game.context, &[respawn], &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn move_player_moves_ship() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    let payer = game.context.payer.pubkey();
+
+    let mut board_before = empty_board();
+    put(&mut board_before, ship_tile(4, 4, payer, 50, 0));
+    set_board(&mut game.context, &board_before).await;
+
+    // Right
+    send(
+        &mut game.context,
+        &[client::move_player_v2(payer, payer, game.gold_mint, 1)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let board_after = board(&mut game.context).await;
+    assert_eq!(board_after.tile(4, 4).unwrap().state, TileState::Empty);
+    let ship = board_after.tile(5, 4).unwrap();
+    assert_eq!(ship.state, TileState::Player);
+    assert_eq!(ship.player, payer);
+    assert_eq!(ship.look_direction, 1);
+}
+
+#[tokio::test]
+async fn shoot_kill_pays_out() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    fund_vault(&mut game, 100 * TOKEN_DECIMAL_MULTIPLIER);
+    let payer = game.context.payer.pubkey();
+    let enemy = Pubkey::new_unique();
+
+    // Looking up means the cannons fire to the left and right
+    let mut fixture = empty_board();
+    put(&mut fixture, ship_tile(4, 4, payer, 50, 0));
+    put(&mut fixture, ship_tile(5, 4, enemy, 1, 0));
+    set_board(&mut game.context, &fixture).await;
+
+    let chest_vault_before = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+
+    send(
+        &mut game.context,
+        &[client::shoot(payer, payer, game.gold_mint)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let board_after = board(&mut game.context).await;
+    assert_eq!(board_after.tile(5, 4).unwrap().state, TileState::Empty);
+    assert_eq!(board_after.tile(4, 4).unwrap().state, TileState::Player);
+
+    let chest_vault_after = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+    assert_eq!(chest_vault_before - chest_vault_after, PLAYER_KILL_REWARD);
+
+    let player_gold = get_associated_token_address(&payer, &game.gold_mint);
+    assert_eq!(
+        token_balance(&mut game.context, player_gold).await,
+        10 * TOKEN_DECIMAL_MULTIPLIER
+    );
+}
+
+#[tokio::test]
+async fn boarding_disables_damaged_ship() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    fund_vault(&mut game, 100 * TOKEN_DECIMAL_MULTIPLIER);
+    let payer = game.context.payer.pubkey();
+    let damaged = Pubkey::new_unique();
+    let healthy = Pubkey::new_unique();
+
+    // A huge crew makes the boarding roll practically certain
+    let mut fixture = empty_board();
+    put(&mut fixture, ship_tile(4, 4, payer, 10000, 0));
+    put(
+        &mut fixture,
+        TileSnapshot {
+            start_health: 100,
+            ..ship_tile(5, 4, damaged, 10, 0)
+        },
+    );
+    put(&mut fixture, ship_tile(4, 5, healthy, 100, 0));
+    set_board(&mut game.context, &fixture).await;
+
+    let healthy_target = client::board_ship(payer, payer, game.gold_mint, 2);
+    assert!(send(&mut game.context, &[healthy_target], &[])
+        .await
+        .is_err());
+
+    let chest_vault_before = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+    send(
+        &mut game.context,
+        &[client::board_ship(payer, payer, game.gold_mint, 1)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let board_after = board(&mut game.context).await;
+    let boarded = board_after.tile(5, 4).unwrap();
+    assert_eq!(boarded.state, TileState::Disabled);
+    assert_eq!(boarded.collect_reward, PLAYER_KILL_REWARD / 2);
+
+    let chest_vault_after = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+    assert_eq!(
+        chest_vault_before - chest_vault_after,
+        PLAYER_KILL_REWARD / 2
+    );
+
+    let player_gold = get_associated_token_address(&payer, &game.gold_mint);
+    assert_eq!(
+        token_balance(&mut game.context, player_gold).await,
+        5 * TOKEN_DECIMAL_MULTIPLIER
+    );
+}
+
+#[tokio::test]
+async fn boarding_keeps_the_chest_vault_rent_exempt() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    fund_vault(&mut game, 100 * TOKEN_DECIMAL_MULTIPLIER);
+    let payer = game.context.payer.pubkey();
+
+    let mut fixture = empty_board();
+    put(&mut fixture, ship_tile(4, 4, payer, 10000, 0));
+    put(
+        &mut fixture,
+        TileSnapshot {
+            start_health: 100,
+            ..ship_tile(5, 4, Pubkey::new_unique(), 10, 0)
+        },
+    );
+    set_board(&mut game.context, &fixture).await;
+
+    // Only the rent is left in the vault, there is nothing to loot
+    let chest_vault = client::chest_vault_pda().0;
+    let mut vault = account(&mut game.context, chest_vault).await;
+    let rent = game.context.banks_client.get_rent().await.unwrap();
+    vault.lamports = rent.minimum_balance(vault.data.len());
+    game.context.set_account(&chest_vault, &vault.into());
+
+    let board_ship = client::board_ship(payer, payer, game.gold_mint, 1);
+    assert!(send(&mut game.context, &[board_ship], &[]).await.is_err());
+    assert_eq!(
+        board(&mut game.context).await.tile(5, 4).unwrap().state,
+        TileState::Player
+    );
+}
+
+#[tokio::test]
+async fn chest_collection_pays_out() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    fund_vault(&mut game, 100 * TOKEN_DECIMAL_MULTIPLIER);
+    let payer = game.context.payer.pubkey();
+
+    let mut fixture = empty_board();
+    put(&mut fixture, ship_tile(4, 4, payer, 50, 0));
+    put(
+        &mut fixture,
+        TileSnapshot {
+            state: TileState::Chest,
+            collect_reward: CHEST_REWARD,
+            range: 0,
+            damage: 0,
+            ship_level: 0,
+            ..ship_tile(4, 3, payer, 1, 0)
+        },
+    );
+    set_board(&mut game.context, &fixture).await;
+
+    let chest_vault_before = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+
+    // Up
+    send(
+        &mut game.context,
+        &[client::move_player_v2(payer, payer, game.gold_mint, 0)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let board_after = board(&mut game.context).await;
+    assert_eq!(board_after.tile(4, 3).unwrap().state, TileState::Player);
+    assert_eq!(board_after.tile(4, 4).unwrap().state, TileState::Empty);
+
+    let chest_vault_after = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+    assert_eq!(chest_vault_before - chest_vault_after, CHEST_REWARD);
+
+    let player_gold = get_associated_token_address(&payer, &game.gold_mint);
+    assert_eq!(
+        token_balance(&mut game.context, player_gold).await,
+        10 * TOKEN_DECIMAL_MULTIPLIER
+    );
+}
+
+#[tokio::test]
+async fn replay_rebuilds_shoot_kill() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    fund_vault(&mut game, 100 * TOKEN_DECIMAL_MULTIPLIER);
+    let payer = game.context.payer.pubkey();
+    let enemy = Pubkey::new_unique();
+
+    let mut fixture = empty_board();
+    put(&mut fixture, ship_tile(4, 4, payer, 50, 0));
+    put(&mut fixture, ship_tile(5, 4, enemy, 1, 0));
+    set_board(&mut game.context, &fixture).await;
+
+    send(
+        &mut game.context,
+        &[client::shoot(payer, payer, game.gold_mint)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let actions_data = account(&mut game.context, client::game_actions_pda().0)
+        .await
+        .data;
+    let actions = client::decode_game_actions(&actions_data).unwrap();
+    let shot = actions
+        .iter()
+        .find(|action| action.action_type == GAME_ACTION_SHIP_SHOT)
+        .unwrap();
+    assert_eq!(shot.player_tile, (4, 4));
+    assert!(shot.slot > 0);
+
+    let frames = replay(&fixture, &actions);
+    let damage = frames
+        .iter()
+        .find(|frame| frame.action.action_type == GAME_ACTION_SHIP_TAKEN_DAMAGE)
+        .unwrap();
+    assert_eq!(damage.action.target_tile, (5, 4));
+    assert_eq!(damage.changes.len(), 1);
+    assert_eq!(damage.changes[0].state, TileState::Empty);
+
+    let mut output = Vec::new();
+    write_replay(&mut output, &fixture, &frames).unwrap();
+    let lines = String::from_utf8(output).unwrap();
+    assert_eq!(lines.lines().count(), frames.len() + 1);
+}
+
+#[tokio::test]
+async fn game_config_changes_rewards() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    fund_vault(&mut game, 100 * TOKEN_DECIMAL_MULTIPLIER);
+    let payer = game.context.payer.pubkey();
+
+    let config = GameConfig {
+        chest_coin_reward: 25,
+        ..GameConfig::default()
+    };
+    let not_admin = client::update_game_config(payer, &config);
+    assert!(send(&mut game.context, &[not_admin], &[]).await.is_err());
+
+    let admin = admin(&mut game.context);
+    send(
+        &mut game.context,
+        &[client::update_game_config(admin.pubkey(), &config)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    let config_data = account(&mut game.context, client::game_config_pda().0).await;
+    let stored = GameConfig::try_deserialize(&mut config_data.data.as_slice()).unwrap();
+    assert_eq!(stored.chest_coin_reward, 25);
+    assert_eq!(stored.player_kill_reward, PLAYER_KILL_REWARD);
+
+    let mut fixture = empty_board();
+    put(&mut fixture, ship_tile(4, 4, payer, 50, 0));
+    put(
+        &mut fixture,
+        TileSnapshot {
+            state: TileState::Chest,
+            collect_reward: CHEST_REWARD,
+            ..ship_tile(4, 3, payer, 1, 0)
+        },
+    );
+    set_board(&mut game.context, &fixture).await;
+
+    send(
+        &mut game.context,
+        &[client::move_player_v2(payer, payer, game.gold_mint, 0)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let player_gold = get_associated_token_address(&payer, &game.gold_mint);
+    assert_eq!(
+        token_balance(&mut game.context, player_gold).await,
+        25 * TOKEN_DECIMAL_MULTIPLIER
+    );
+}
+
+#[tokio::test]
+async fn close_ship_only_when_not_sailing() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    let payer = game.context.payer.pubkey();
+    let ship = client::ship_pda(&game.nft).0;
+
+    let close = client::close_ship(payer, game.nft);
+    assert!(send(&mut game.context, &[close.clone()], &[])
+        .await
+        .is_err());
+
+    let stranger = funded_keypair(&mut game.context, Keypair::new());
+    send(&mut game.context, &[client::reset_ship(payer)], &[])
+        .await
+        .unwrap();
+    let stranger_close = client::close_ship(stranger.pubkey(), game.nft);
+    assert!(send(&mut game.context, &[stranger_close], &[&stranger])
+        .await
+        .is_err());
+
+    send(&mut game.context, &[close], &[]).await.unwrap();
+    assert!(game
+        .context
+        .banks_client
+        .get_account(ship)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn close_ship_while_another_ship_of_the_player_sails() {
+    let mut game = setup().await;
+    let payer = game.context.payer.pubkey();
+    let docked = Pubkey::new_unique();
+    send(
+        &mut game.context,
+        &[
+            client::initialize_ship(payer, docked, SHIP_CLASS_FRIGATE),
+            client::spawn_player(payer, payer, docked, game.cannon_mint, game.rum_mint, docked),
+            client::reset_ship(payer),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    spawn(&mut game).await;
+
+    // Spawning needs the ship nft as avatar, it is how close_ship finds the ship on the board
+    let wrong_avatar = client::spawn_player(
+        payer,
+        payer,
+        docked,
+        game.cannon_mint,
+        game.rum_mint,
+        Pubkey::new_unique(),
+    );
+    assert!(send(&mut game.context, &[wrong_avatar], &[]).await.is_err());
+
+    send(&mut game.context, &[client::close_ship(payer, docked)], &[])
+        .await
+        .unwrap();
+    let sailing = client::close_ship(payer, game.nft);
+    assert!(send(&mut game.context, &[sailing], &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn close_ship_after_close_game() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    let payer = game.context.payer.pubkey();
+    let admin = admin(&mut game.context);
+    let treasury = Pubkey::new_unique();
+    game.context.set_account(
+        &get_associated_token_address(&treasury, &game.gold_mint),
+        &token_account(&game.gold_mint, &treasury, 0),
+    );
+    send(
+        &mut game.context,
+        &[client::close_game(admin.pubkey(), treasury, game.gold_mint, None)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+
+    send(&mut game.context, &[client::close_ship(payer, game.nft)], &[])
+        .await
+        .unwrap();
+    assert!(game
+        .context
+        .banks_client
+        .get_account(client::ship_pda(&game.nft).0)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn close_game_drains_vaults_to_treasury() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    fund_vault(&mut game, 100 * TOKEN_DECIMAL_MULTIPLIER);
+    let admin = admin(&mut game.context);
+    let treasury = Pubkey::new_unique();
+    let treasury_gold = get_associated_token_address(&treasury, &game.gold_mint);
+    game.context.set_account(
+        &treasury_gold,
+        &token_account(&game.gold_mint, &treasury, 0),
+    );
+
+    let chest_vault = client::chest_vault_pda().0;
+    let chest_vault_lamports = account(&mut game.context, chest_vault).await.lamports;
+    let keeper_pool = client::keeper_pool_pda().0;
+    let keeper_pool_lamports = account(&mut game.context, keeper_pool).await.lamports;
+
+    send(
+        &mut game.context,
+        &[client::close_game(admin.pubkey(), treasury, game.gold_mint, None)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        token_balance(&mut game.context, treasury_gold).await,
+        100 * TOKEN_DECIMAL_MULTIPLIER
+    );
+    assert_eq!(
+        account(&mut game.context, treasury).await.lamports,
+        chest_vault_lamports + keeper_pool_lamports
+    );
+    for closed in [
+        client::level_pda().0,
+        chest_vault,
+        keeper_pool,
+        client::game_actions_pda().0,
+        client::game_config_pda().0,
+        client::token_account_owner_pda().0,
+        client::token_vault_pda(&game.gold_mint).0,
+    ] {
+        assert!(game
+            .context
+            .banks_client
+            .get_account(closed)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
+
+#[tokio::test]
+async fn board_summary_lists_occupied_tiles() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    let payer = game.context.payer.pubkey();
+
+    let mut fixture = empty_board();
+    fixture.action_id = 7;
+    put(&mut fixture, ship_tile(4, 4, payer, 50, 2));
+    put(
+        &mut fixture,
+        TileSnapshot {
+            state: TileState::Chest,
+            collect_reward: CHEST_REWARD,
+            ..ship_tile(9, 9, payer, 1, 0)
+        },
+    );
+    set_board(&mut game.context, &fixture).await;
+
+    let blockhash = game.context.get_new_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[client::board_summary()],
+        Some(&payer),
+        &[&game.context.payer],
+        blockhash,
+    );
+    let simulation = game
+        .context
+        .banks_client
+        .simulate_transaction(transaction)
+        .await
+        .unwrap();
+    let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+    assert_eq!(return_data.program_id, seven_seas::ID);
+
+    let summary = client::decode_board_summary(&return_data.data).unwrap();
+    assert_eq!(summary.action_id, 7);
+    assert!(summary.is_occupied(4, 4));
+    assert!(summary.is_occupied(9, 9));
+    assert!(!summary.is_occupied(4, 5));
+    assert_eq!(summary.tiles.len(), 2);
+    assert_eq!(summary.tiles[0].index, 44);
+    assert_eq!(summary.tiles[0].health, 50);
+    assert_eq!(summary.tiles[0].look_direction, 2);
+    assert_eq!(summary.tiles[0].player, payer.to_bytes()[..8]);
+    assert_eq!(summary.tiles[1].index, 99);
+    assert!(return_data.data.len() < 100);
+}
+
+#[tokio::test]
+async fn reset_clears_board() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    let payer = game.context.payer.pubkey();
+    assert_eq!(board(&mut game.context).await.occupied().count(), 2);
+
+    send(&mut game.context, &[client::reset(payer)], &[])
+        .await
+        .unwrap();
+
+    assert_eq!(board(&mut game.context).await.occupied().count(), 0);
+}
+
+#[tokio::test]
+async fn thread_tick_stub_moves_ships() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    let payer = game.context.payer.pubkey();
+
+    let mut fixture = empty_board();
+    put(&mut fixture, ship_tile(4, 4, payer, 50, 2));
+    set_board(&mut game.context, &fixture).await;
+
+    // Stand in for the clockwork thread. The tick only checks that the thread signs
+    // and belongs to the thread authority, so any keypair owned by clockwork works.
+    let thread = Keypair::new();
+    let thread_authority = client::thread_authority_pda().0;
+    let thread_data = Thread {
+        authority: thread_authority,
+        bump: 0,
+        created_at: ClockData {
+            slot: 0,
+            epoch: 0,
+            unix_timestamp: 0,
+        },
+        exec_context: None,
+        fee: 0,
+        id: b"thread-wind".to_vec(),
+        instructions: vec![],
+        name: "thread-wind".into(),
+        next_instruction: None,
+        paused: false,
+        rate_limit: u64::MAX,
+        trigger: Trigger::Cron {
+            schedule: "*/2 * * * * * *".into(),
+            skippable: true,
+        },
+    };
+    let mut data = Vec::new();
+    thread_data.try_serialize(&mut data).unwrap();
+    game.context.set_account(
+        &thread.pubkey(),
+        &AccountSharedData::from(Account {
+            lamports: LAMPORTS_PER_SOL,
+            data,
+            owner: clockwork_sdk::ID,
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+
+    let tick = Instruction {
+        program_id: seven_seas::ID,
+        accounts: seven_seas::accounts::ThreadTick {
+            game_data: client::level_pda().0,
+            game_actions: client::game_actions_pda().0,
+            thread: thread.pubkey(),
+            thread_authority,
+        }
+        .to_account_metas(None),
+        data: seven_seas::instruction::OnThreadTick {}.data(),
+    };
+    send(&mut game.context, &[tick], &[&thread]).await.unwrap();
+
+    // Down
+    let board_after = board(&mut game.context).await;
+    assert_eq!(board_after.tile(4, 4).unwrap().state, TileState::Empty);
+    assert_eq!(board_after.tile(4, 5).unwrap().state, TileState::Player);
+}
+
+#[tokio::test]
+async fn crank_tick_respects_interval_and_pays_bounty() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    let payer = game.context.payer.pubkey();
+
+    let mut fixture = empty_board();
+    put(&mut fixture, ship_tile(4, 4, payer, 50, 1));
+    set_board(&mut game.context, &fixture).await;
+
+    let keeper = funded_keypair(&mut game.context, Keypair::new());
+    advance_clock(&mut game.context, 10, 10).await;
+
+    let vault_before = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+    send(
+        &mut game.context,
+        &[client::crank_tick(keeper.pubkey())],
+        &[&keeper],
+    )
+    .await
+    .unwrap();
+    let board_after = board(&mut game.context).await;
+    assert_eq!(board_after.tile(5, 4).unwrap().state, TileState::Player);
+    assert_eq!(
+        account(&mut game.context, keeper.pubkey()).await.lamports,
+        LAMPORTS_PER_SOL + CRANK_TICK_BOUNTY
+    );
+    // The escrowed rewards in the chest vault are left alone
+    assert_eq!(
+        account(&mut game.context, client::chest_vault_pda().0)
+            .await
+            .lamports,
+        vault_before
+    );
+
+    // Same clock, a second keeper is too early
+    let second_keeper = funded_keypair(&mut game.context, Keypair::new());
+    let crank = client::crank_tick(second_keeper.pubkey());
+    assert!(send(&mut game.context, &[crank.clone()], &[&second_keeper])
+        .await
+        .is_err());
+
+    advance_clock(&mut game.context, 10, 10).await;
+    send(&mut game.context, &[crank], &[&second_keeper])
+        .await
+        .unwrap();
+    let board_after = board(&mut game.context).await;
+    assert_eq!(board_after.tile(6, 4).unwrap().state, TileState::Player);
+}
+
+#[tokio::test]
+async fn npc_kill_pays_reduced_reward() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    fund_vault(&mut game, 100 * TOKEN_DECIMAL_MULTIPLIER);
+    let payer = game.context.payer.pubkey();
+    let npc = Pubkey::new_unique();
+
+    // Only the admin can spawn NPCs
+    assert!(
+        send(&mut game.context, &[client::spawn_npc(payer, npc)], &[])
+            .await
+            .is_err()
+    );
+
+    let chest_vault_before = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+    let admin = admin(&mut game.context);
+    send(
+        &mut game.context,
+        &[client::spawn_npc(admin.pubkey(), npc)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    assert_eq!(board(&mut game.context).await.npcs().count(), 1);
+    let chest_vault_after_spawn = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+    assert_eq!(
+        chest_vault_after_spawn - chest_vault_before,
+        NPC_KILL_REWARD
+    );
+
+    let mut fixture = empty_board();
+    put(&mut fixture, ship_tile(4, 4, payer, 50, 0));
+    put(
+        &mut fixture,
+        TileSnapshot {
+            state: TileState::Npc,
+            collect_reward: NPC_KILL_REWARD,
+            ..ship_tile(5, 4, npc, 1, 0)
+        },
+    );
+    set_board(&mut game.context, &fixture).await;
+
+    send(
+        &mut game.context,
+        &[client::shoot(payer, payer, game.gold_mint)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(board(&mut game.context).await.npcs().count(), 0);
+    let chest_vault_after_kill = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+    assert_eq!(
+        chest_vault_after_spawn - chest_vault_after_kill,
+        NPC_KILL_REWARD
+    );
+    let player_gold = get_associated_token_address(&payer, &game.gold_mint);
+    assert_eq!(
+        token_balance(&mut game.context, player_gold).await,
+        5 * TOKEN_DECIMAL_MULTIPLIER
+    );
+}
+
+#[tokio::test]
+async fn game_config_sets_npc_kill_reward() {
+    let mut game = setup().await;
+    let npc = Pubkey::new_unique();
+    let admin = admin(&mut game.context);
+    let config = GameConfig {
+        npc_kill_reward: 2 * NPC_KILL_REWARD,
+        ..GameConfig::default()
+    };
+    send(
+        &mut game.context,
+        &[client::update_game_config(admin.pubkey(), &config)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+
+    let chest_vault_before = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+    send(
+        &mut game.context,
+        &[client::spawn_npc(admin.pubkey(), npc)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    let chest_vault_after = account(&mut game.context, client::chest_vault_pda().0)
+        .await
+        .lamports;
+    assert_eq!(chest_vault_after - chest_vault_before, 2 * NPC_KILL_REWARD);
+    let board = board(&mut game.context).await;
+    let spawned = board.npcs().next().unwrap();
+    assert_eq!(spawned.collect_reward, 2 * NPC_KILL_REWARD);
+}
+
+#[tokio::test]
+async fn npc_turns_and_fires_at_close_ship() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    let payer = game.context.payer.pubkey();
+    let npc = Pubkey::new_unique();
+
+    // The ship wants to sail left into the NPC and stays where it is. The NPC looks
+    // right, so it has to turn up before its broadside points at the ship.
+    let mut fixture = empty_board();
+    put(&mut fixture, ship_tile(5, 4, payer, 50, 3));
+    put(
+        &mut fixture,
+        TileSnapshot {
+            state: TileState::Npc,
+            collect_reward: NPC_KILL_REWARD,
+            ..ship_tile(4, 4, npc, 60, 1)
+        },
+    );
+    set_board(&mut game.context, &fixture).await;
+
+    crank(&mut game.context).await;
+
+    let board_after = board(&mut game.context).await;
+    let npc_tile = board_after.tile(4, 4).unwrap();
+    assert_eq!(npc_tile.state, TileState::Npc);
+    assert_eq!(npc_tile.look_direction, 0);
+    // Damage is the cannons plus two, like for players
+    assert_eq!(board_after.tile(5, 4).unwrap().health, 47);
+}
+
+#[tokio::test]
+async fn ship_sunk_by_npc_leaves_its_reward_in_a_chest() {
+    let mut game = setup().await;
+    spawn(&mut game).await;
+    let payer = game.context.payer.pubkey();
+    let npc = Pubkey::new_unique();
+
+    let mut fixture = empty_board();
+    put(&mut fixture, ship_tile(5, 4, payer, 1, 3));
+    put(
+        &mut fixture,
+        TileSnapshot {
+            state: TileState::Npc,
+            collect_reward: NPC_KILL_REWARD,
+            ..ship_tile(4, 4, npc, 60, 1)
+        },
+    );
+    set_board(&mut game.context, &fixture).await;
+
+    crank(&mut game.context).await;
+
+    let wreck = board(&mut game.context).await.tile(5, 4).unwrap().clone();
+    assert_eq!(wreck.state, TileState::Chest);
+    assert_eq!(wreck.collect_reward, PLAYER_KILL_REWARD);
+
+    // The replay ends with the same chest as the account
+    let actions_data = account(&mut game.context, client::game_actions_pda().0)
+        .await
+        .data;
+    let actions = client::decode_game_actions(&actions_data).unwrap();
+    let frames = replay(&fixture, &actions);
+    let sunk = frames
+        .iter()
+        .find(|frame| frame.action.action_type == GAME_ACTION_SHIP_TAKEN_DAMAGE)
+        .unwrap();
+    assert_eq!(sunk.changes, vec![wreck]);
+}